@@ -1,4 +1,4 @@
-#![allow(clippy::module_name_repetitions)]
+#![allow(clippy::module_name_repetitions, clippy::missing_panics_doc)]
 
 use chrono::{DateTime, TimeZone, Utc, Weekday};
 use rrule::{NWeekday, Tz};
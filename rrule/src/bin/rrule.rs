@@ -1,7 +1,7 @@
 use std::{fmt::Display, str::FromStr};
 
 use clap::Parser;
-use rrule::RRuleSet;
+use rrule::{IterLimits, RRuleSet};
 
 /// Recurrence Rule parser and iterator
 ///
@@ -20,9 +20,8 @@ use rrule::RRuleSet;
 struct Opts {
     /// Limits the amount of iteration
     /// If no limit is set, it will default to `100`.
-    /// The maximum limit is `65535`.
     #[clap(short, long)]
-    limit: Option<u16>,
+    limit: Option<usize>,
 
     /// The `RRULE` string you want to iterator over.
     input: String,
@@ -34,9 +33,12 @@ fn main() -> Result<(), String> {
     let limit = opts.limit.unwrap_or(100);
     let rrule_str = opts.input.replace("\\n", "\n");
     let rrule: RRuleSet = parse_rule(&rrule_str)?;
-    let rrule = rrule.limit();
-    let iter = rrule.into_iter();
-    iterator_dates(iter, limit);
+    let result = rrule
+        .iter_limits(IterLimits::default().max_results(limit))
+        .all_within_limits();
+    for date in result.dates {
+        println!("{}", date);
+    }
 
     Ok(())
 }
@@ -53,16 +55,3 @@ where
         }
     }
 }
-
-fn iterator_dates<T>(mut rule_iter: T, limit: u16)
-where
-    T: Iterator,
-    <T as Iterator>::Item: Display,
-{
-    for _ in 0..limit {
-        let next = rule_iter.next();
-        if let Some(value) = next {
-            println!("{}", value);
-        }
-    }
-}
@@ -0,0 +1,300 @@
+use crate::parser::component::{Component, ComponentProperty};
+use crate::parser::{
+    parse_recurrence_id, ContentLineCaptures, Grammar, Located, ParseError, PropertyName,
+};
+use crate::{RRuleError, RRuleSet, RecurrenceOverride, RecurrenceRange, Tz, VTimezone};
+use chrono::DateTime;
+use std::str::FromStr;
+
+/// A property of an iCalendar component that is not used to generate the recurrences,
+/// e.g. `SUMMARY`, `LOCATION` or any `X-` property.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Property {
+    /// The name of the property, e.g. `SUMMARY`.
+    pub(crate) name: String,
+    /// The raw property parameters, e.g. `LANGUAGE=en`.
+    pub(crate) parameters: Option<String>,
+    /// The raw value of the property.
+    pub(crate) value: String,
+}
+
+impl Property {
+    /// Returns the name of the property.
+    #[must_use]
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the raw property parameters, if any.
+    #[must_use]
+    pub fn get_parameters(&self) -> Option<&str> {
+        self.parameters.as_deref()
+    }
+
+    /// Returns the raw value of the property.
+    #[must_use]
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl From<&ComponentProperty> for Property {
+    fn from(property: &ComponentProperty) -> Self {
+        Self {
+            name: property.name.clone(),
+            parameters: property.parameters.clone(),
+            value: property.value.clone(),
+        }
+    }
+}
+
+/// A `VEVENT` component, containing the [`RRuleSet`] built from its recurrence properties.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VEvent {
+    /// The `UID` of the event.
+    pub(crate) uid: Option<String>,
+    /// The recurrence set built from `DTSTART`, `DTEND` or `DURATION`, `RRULE`, `EXRULE`,
+    /// `RDATE` and `EXDATE`.
+    pub(crate) rrule_set: RRuleSet,
+    /// All the other properties of the event, in the order they were found.
+    pub(crate) properties: Vec<Property>,
+    /// The original start of the instance modified by the event and the instances it
+    /// applies to, given by `RECURRENCE-ID`.
+    pub(crate) recurrence_id: Option<(DateTime<Tz>, RecurrenceRange)>,
+    /// The events with the same `UID` and a `RECURRENCE-ID`, which modify instances of this one.
+    pub(crate) modified_instances: Vec<Self>,
+}
+
+impl VEvent {
+    /// Returns the `UID` of the event, if it has one.
+    #[must_use]
+    pub fn get_uid(&self) -> Option<&str> {
+        self.uid.as_deref()
+    }
+
+    /// Returns the recurrence set of the event.
+    #[must_use]
+    pub fn get_rrule_set(&self) -> &RRuleSet {
+        &self.rrule_set
+    }
+
+    /// Consumes the event and returns its recurrence set.
+    #[must_use]
+    pub fn into_rrule_set(self) -> RRuleSet {
+        self.rrule_set
+    }
+
+    /// Returns the properties of the event which are not recurrence properties.
+    #[must_use]
+    pub fn get_properties(&self) -> &Vec<Property> {
+        &self.properties
+    }
+
+    /// Returns the first property with the given name (case insensitive).
+    #[must_use]
+    pub fn get_property(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|property| property.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the original start of the instance modified by the event, if it has a
+    /// `RECURRENCE-ID`.
+    #[must_use]
+    pub fn get_recurrence_id(&self) -> Option<&DateTime<Tz>> {
+        self.recurrence_id
+            .as_ref()
+            .map(|(recurrence_id, _)| recurrence_id)
+    }
+
+    /// Returns the events modifying instances of this one, in the order they were found.
+    ///
+    /// Their new start and duration are applied by [`RRuleSet::occurrences`] of the event,
+    /// and [`VEvent::get_recurrence_id`] matches the `recurrence_id` of the occurrence.
+    #[must_use]
+    pub fn get_modified_instances(&self) -> &Vec<Self> {
+        &self.modified_instances
+    }
+
+    /// Adds an event with a `RECURRENCE-ID` as a [`RecurrenceOverride`] of the set.
+    fn add_modified_instance(&mut self, instance: Self) {
+        if let Some((recurrence_id, range)) = &instance.recurrence_id {
+            let mut instance_override =
+                RecurrenceOverride::new(instance.rrule_set.dt_start).range(*range);
+            if let Some(duration) = instance.rrule_set.duration {
+                instance_override = instance_override.duration(duration);
+            }
+            let recurrence_id = recurrence_id.with_timezone(&self.rrule_set.dt_start.timezone());
+            self.rrule_set
+                .overrides
+                .insert(recurrence_id, instance_override);
+        }
+        self.modified_instances.push(instance);
+    }
+
+    fn from_component(
+        component: &Component,
+        timezones: &[&'static VTimezone],
+    ) -> Result<Self, Located<RRuleError>> {
+        let mut captures = vec![];
+        let mut properties = vec![];
+        let mut recurrence_id = None;
+
+        for property in &component.properties {
+            if property.name.eq_ignore_ascii_case("RECURRENCE-ID") {
+                recurrence_id = Some(
+                    parse_recurrence_id(property.parameters.as_deref(), &property.value, timezones)
+                        .map_err(|error| error.at(&property.source, Some("RECURRENCE-ID")).map())?,
+                );
+            }
+            match PropertyName::from_str(&property.name) {
+                Ok(property_name) => captures.push((
+                    &property.source,
+                    ContentLineCaptures {
+                        property_name,
+                        parameters: property.parameters.as_deref(),
+                        value: &property.value,
+                    },
+                )),
+                Err(_) => properties.push(Property::from(property)),
+            }
+        }
+
+        let grammar = Grammar::from_captures(captures, timezones).map_err(Located::map)?;
+        let has_date_generation_rules = grammar.has_date_generation_rules();
+        let Grammar {
+            start,
+            duration,
+            content_lines,
+        } = grammar;
+        let start = start.ok_or(RRuleError::from(ParseError::MissingStartDate))?;
+
+        let mut rrule_set = RRuleSet::new(start.datetime).set_from_content_lines(content_lines)?;
+        if let Some(duration) = duration {
+            rrule_set = rrule_set.duration(duration);
+        }
+        // An event without any recurrence rule only occurs once, at its start date.
+        if !has_date_generation_rules {
+            rrule_set = rrule_set.rdate(start.datetime);
+        }
+
+        let uid = properties
+            .iter()
+            .find(|property| property.name.eq_ignore_ascii_case("UID"))
+            .map(|property| property.value.clone());
+
+        Ok(Self {
+            uid,
+            rrule_set,
+            properties,
+            recurrence_id,
+            modified_instances: vec![],
+        })
+    }
+}
+
+/// An iCalendar object, like the content of an `.ics` file.
+///
+/// All the `VEVENT` components found in the input are parsed into a [`VEvent`], and all the
+/// `VTIMEZONE` components into a [`VTimezone`] which the events can refer to by their `TZID`.
+/// Other components (e.g. `VTODO` or `VALARM`) are ignored.
+///
+/// An event with a `RECURRENCE-ID` modifies an instance of the event with the same `UID`: it
+/// is added to the [`RRuleSet`] of that event as a [`RecurrenceOverride`], and kept in
+/// [`VEvent::get_modified_instances`] instead of the events of the calendar. It is only kept
+/// as an event of its own if the calendar has no event with its `UID` to modify.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::VCalendar;
+///
+/// let calendar: VCalendar = "BEGIN:VCALENDAR
+/// BEGIN:VEVENT
+/// UID:standup@example.com
+/// SUMMARY:Standup
+/// DTSTART:20210101T090000Z
+/// RRULE:FREQ=DAILY;COUNT=3
+/// END:VEVENT
+/// END:VCALENDAR"
+///     .parse()
+///     .unwrap();
+///
+/// let event = &calendar.get_events()[0];
+/// assert_eq!(event.get_uid(), Some("standup@example.com"));
+/// assert_eq!(event.get_property("SUMMARY").unwrap().get_value(), "Standup");
+/// assert_eq!(event.get_rrule_set().clone().all(10).dates.len(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VCalendar {
+    /// The events of the calendar.
+    pub(crate) events: Vec<VEvent>,
+    /// The timezones defined in the calendar.
+    pub(crate) timezones: Vec<&'static VTimezone>,
+}
+
+impl VCalendar {
+    /// Returns the events of the calendar.
+    #[must_use]
+    pub fn get_events(&self) -> &Vec<VEvent> {
+        &self.events
+    }
+
+    /// Returns the timezones defined by the `VTIMEZONE` components of the calendar.
+    #[must_use]
+    pub fn get_timezones(&self) -> &Vec<&'static VTimezone> {
+        &self.timezones
+    }
+
+    /// Consumes the calendar and returns its events.
+    #[must_use]
+    pub fn into_events(self) -> Vec<VEvent> {
+        self.events
+    }
+
+    /// Parses the calendar like [`VCalendar::from_str`], but keeps the location of the input
+    /// which caused the error, see [`crate::Diagnostic`].
+    pub(crate) fn parse_located(s: &str) -> Result<Self, Located<RRuleError>> {
+        let root = Component::parse_root(s).map_err(Located::map)?;
+        let timezones = root
+            .find_components("VTIMEZONE")
+            .into_iter()
+            .map(|component| VTimezone::from_component(component).map(VTimezone::intern))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut events: Vec<VEvent> = vec![];
+        let mut modified_instances = vec![];
+        for component in root.find_components("VEVENT") {
+            let event = VEvent::from_component(component, &timezones)?;
+            if event.recurrence_id.is_some() {
+                modified_instances.push(event);
+            } else {
+                events.push(event);
+            }
+        }
+        for instance in modified_instances {
+            let master = events
+                .iter_mut()
+                .find(|event| event.uid.is_some() && event.uid == instance.uid);
+            match master {
+                Some(master) => master.add_modified_instance(instance),
+                None => events.push(instance),
+            }
+        }
+
+        Ok(Self { events, timezones })
+    }
+}
+
+impl FromStr for VCalendar {
+    type Err = RRuleError;
+
+    /// Parses all the `VEVENT` components of an iCalendar string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if the components are malformed or an event contains
+    /// invalid recurrence properties.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_located(s).map_err(|located| located.error)
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::{DateTime, TimeZone};
+
+/// Conversion of a datetime into a [`chrono::DateTime`], which is used by [`RRuleSet`](crate::RRuleSet)
+/// and [`RRule`](crate::RRule) to accept datetimes of other datetime libraries.
+pub trait IntoDateTime<TZ: TimeZone> {
+    /// Converts the value into a [`chrono::DateTime`].
+    fn into_datetime(self) -> DateTime<TZ>;
+}
+
+impl<TZ: TimeZone> IntoDateTime<TZ> for DateTime<TZ> {
+    fn into_datetime(self) -> Self {
+        self
+    }
+}
+
+impl<TZ: TimeZone> IntoDateTime<TZ> for &DateTime<TZ> {
+    fn into_datetime(self) -> DateTime<TZ> {
+        self.clone()
+    }
+}
+
+/// Conversion of a [`chrono::DateTime`] into a datetime of another datetime library.
+///
+/// Used by [`RRuleSetIter::convert`](crate::RRuleSetIter::convert) to yield the recurrences
+/// as another type.
+pub trait FromDateTime<TZ: TimeZone>: Sized {
+    /// Converts the [`chrono::DateTime`], returns `None` if it can not be represented by `Self`.
+    fn from_datetime(dt: DateTime<TZ>) -> Option<Self>;
+}
+
+impl<TZ: TimeZone> FromDateTime<TZ> for DateTime<TZ> {
+    fn from_datetime(dt: Self) -> Option<Self> {
+        Some(dt)
+    }
+}
@@ -1,5 +1,6 @@
 use super::timezone::Tz;
-use chrono::{Datelike, Duration, NaiveTime, Timelike};
+use super::EventDuration;
+use chrono::{Datelike, Duration, NaiveTime, TimeZone, Timelike};
 
 pub(crate) fn duration_from_midnight(time: NaiveTime) -> Duration {
     Duration::hours(i64::from(time.hour()))
@@ -7,28 +8,30 @@ pub(crate) fn duration_from_midnight(time: NaiveTime) -> Duration {
         + Duration::seconds(i64::from(time.second()))
 }
 
-pub(crate) fn get_month(dt: &chrono::DateTime<Tz>) -> u8 {
+pub(crate) fn get_month<TZ: TimeZone>(dt: &chrono::DateTime<TZ>) -> u8 {
     u8::try_from(dt.month()).expect("month is between 1-12 which is covered by u8")
 }
 
-pub(crate) fn get_day(dt: &chrono::DateTime<Tz>) -> i8 {
+pub(crate) fn get_day<TZ: TimeZone>(dt: &chrono::DateTime<TZ>) -> i8 {
     i8::try_from(dt.day()).expect("day is between 1-31 which is covered by i8")
 }
 
-pub(crate) fn get_hour(dt: &chrono::DateTime<Tz>) -> u8 {
+pub(crate) fn get_hour<TZ: TimeZone>(dt: &chrono::DateTime<TZ>) -> u8 {
     u8::try_from(dt.hour()).expect("hour is between 0-23 which is covered by u8")
 }
 
-pub(crate) fn get_minute(dt: &chrono::DateTime<Tz>) -> u8 {
+pub(crate) fn get_minute<TZ: TimeZone>(dt: &chrono::DateTime<TZ>) -> u8 {
     u8::try_from(dt.minute()).expect("minute is between 0-59 which is covered by u8")
 }
 
-pub(crate) fn get_second(dt: &chrono::DateTime<Tz>) -> u8 {
+pub(crate) fn get_second<TZ: TimeZone>(dt: &chrono::DateTime<TZ>) -> u8 {
     u8::try_from(dt.second()).expect("second is between 0-59 which is covered by u8")
 }
 
 /// Generates an iCalendar date-time string format with the prefix symbols.
 /// Like: `:19970714T173000Z` or `;TZID=America/New_York:19970714T133000`
+///
+/// A fixed offset is written as a `TZID` in the UTC offset format, like `;TZID=+0530`.
 /// ref: <https://tools.ietf.org/html/rfc5545#section-3.3.5>
 pub(crate) fn datetime_to_ical_format(dt: &chrono::DateTime<Tz>) -> String {
     let mut tz_prefix = String::new();
@@ -44,8 +47,57 @@ pub(crate) fn datetime_to_ical_format(dt: &chrono::DateTime<Tz>) -> String {
                 tz_prefix = format!(";TZID={}", tz.name());
             }
         },
+        Tz::VTimezone(tz) => {
+            tz_prefix = format!(";TZID={}", tz.get_tzid());
+        }
+        Tz::Fixed(offset) => {
+            tz_prefix = format!(";TZID={}", utc_offset_to_ical_format(offset));
+        }
     }
 
     let dt = dt.format("%Y%m%dT%H%M%S");
     format!("{}:{}{}", tz_prefix, dt, tz_postfix)
 }
+
+/// Generates an iCalendar duration string, like `PT1H30M`, `P1DT12H` or `P2W`.
+/// ref: <https://tools.ietf.org/html/rfc5545#section-3.3.6>
+pub(crate) fn duration_to_ical_format(duration: EventDuration) -> String {
+    let days = duration.days;
+    let seconds = duration.time.num_seconds().unsigned_abs();
+    if seconds == 0 && days > 0 && days % 7 == 0 {
+        return format!("P{}W", days / 7);
+    }
+
+    let mut value = "P".to_string();
+    if days > 0 {
+        value.push_str(&format!("{days}D"));
+    }
+    if seconds > 0 || days == 0 {
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        value.push('T');
+        if hours > 0 {
+            value.push_str(&format!("{hours}H"));
+        }
+        // Minutes can not be left out between hours and seconds
+        if minutes > 0 || (hours > 0 && seconds > 0) {
+            value.push_str(&format!("{minutes}M"));
+        }
+        if seconds > 0 || (hours == 0 && minutes == 0) {
+            value.push_str(&format!("{seconds}S"));
+        }
+    }
+    value
+}
+
+/// Formats an offset like `+0530`, with the seconds only when they are not zero.
+fn utc_offset_to_ical_format(offset: chrono::FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.unsigned_abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if seconds == 0 {
+        format!("{sign}{hours:02}{minutes:02}")
+    } else {
+        format!("{sign}{hours:02}{minutes:02}{seconds:02}")
+    }
+}
@@ -0,0 +1,139 @@
+use crate::iter::add_time_to_date;
+use chrono::{DateTime, Days, Duration, TimeZone};
+
+/// The duration of the recurrences of an [`RRuleSet`](crate::RRuleSet), like its `DURATION`
+/// property.
+///
+/// As described in [RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.6),
+/// days and weeks are nominal: they are added to the local date of a recurrence, so `P1D`
+/// ends at the same local time on the next day, even when the clocks are moved in between.
+/// The hours, minutes and seconds are exact and added afterwards.
+///
+/// # Usage
+///
+/// ```
+/// use chrono::{Duration, TimeZone};
+/// use rrule::{EventDuration, RRuleSet, Tz};
+///
+/// let dt_start = Tz::Europe__Berlin.with_ymd_and_hms(2023, 3, 25, 9, 0, 0).unwrap();
+/// let rrule_set = RRuleSet::new(dt_start)
+///     .rdate(dt_start)
+///     .duration(EventDuration::new(1, Duration::zero()));
+///
+/// // The clocks are moved forward on the 26th of March in Berlin
+/// let occurrence = rrule_set.occurrences().next().unwrap();
+/// assert_eq!(occurrence.end.to_rfc3339(), "2023-03-26T09:00:00+02:00");
+/// assert_eq!(rrule_set.to_string().lines().nth(1), Some("DURATION:P1D"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EventDuration {
+    /// Nominal days, with a week counting as 7 days.
+    pub(crate) days: u32,
+    /// Exact part of the duration, which is never negative.
+    pub(crate) time: Duration,
+}
+
+impl EventDuration {
+    /// Creates a duration of nominal `days` followed by the exact duration `time`.
+    ///
+    /// A negative `time` is replaced by zero.
+    #[must_use]
+    pub fn new(days: u32, time: Duration) -> Self {
+        Self {
+            days,
+            time: time.max(Duration::zero()),
+        }
+    }
+
+    /// Returns the number of nominal days.
+    #[must_use]
+    pub fn get_days(&self) -> u32 {
+        self.days
+    }
+
+    /// Returns the exact part of the duration.
+    #[must_use]
+    pub fn get_time(&self) -> Duration {
+        self.time
+    }
+
+    /// Returns the end of a recurrence starting at `start`, or `None` if it can not be
+    /// represented.
+    ///
+    /// The days are added to the local date, and the local time is resolved like the
+    /// recurrences are when it is skipped or repeated by a daylight saving time transition.
+    pub(crate) fn add_to<TZ: TimeZone>(&self, start: &DateTime<TZ>) -> Option<DateTime<TZ>> {
+        let start = if self.days == 0 {
+            start.clone()
+        } else {
+            let local = start.naive_local();
+            let date = local
+                .date()
+                .checked_add_days(Days::new(u64::from(self.days)))?;
+            add_time_to_date(&start.timezone(), date, local.time())?
+        };
+        start.checked_add_signed(self.time)
+    }
+
+    /// Returns an upper bound of the exact length of the duration, whatever its start.
+    ///
+    /// A nominal day is usually 23 to 25 hours long, but a timezone can skip a whole day, so
+    /// one more day is added.
+    pub(crate) fn max_exact(&self) -> Duration {
+        let days = match self.days {
+            0 => 0,
+            days => i64::from(days) + 1,
+        };
+        Duration::days(days) + self.time
+    }
+}
+
+impl From<Duration> for EventDuration {
+    /// Converts an exact duration. A negative duration is replaced by zero.
+    fn from(duration: Duration) -> Self {
+        Self::new(0, duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tz;
+
+    #[test]
+    fn adds_days_in_local_time() {
+        let start = Tz::Europe__Berlin
+            .with_ymd_and_hms(2023, 3, 25, 9, 0, 0)
+            .unwrap();
+        let tests = [
+            (
+                EventDuration::new(1, Duration::zero()),
+                "2023-03-26T09:00:00+02:00",
+            ),
+            (
+                EventDuration::new(0, Duration::days(1)),
+                "2023-03-26T10:00:00+02:00",
+            ),
+            (
+                EventDuration::new(7, Duration::hours(1)),
+                "2023-04-01T10:00:00+02:00",
+            ),
+        ];
+        for (duration, expected_end) in tests {
+            let end = duration.add_to(&start).unwrap();
+            assert_eq!(end.to_rfc3339(), expected_end, "{duration:?}");
+            assert!(end - start <= duration.max_exact());
+        }
+    }
+
+    #[test]
+    fn resolves_days_ending_in_a_gap() {
+        let start = Tz::Europe__Berlin
+            .with_ymd_and_hms(2023, 3, 25, 2, 30, 0)
+            .unwrap();
+        let end = EventDuration::new(1, Duration::zero())
+            .add_to(&start)
+            .unwrap();
+        assert_eq!(end.to_rfc3339(), "2023-03-26T03:30:00+02:00");
+    }
+}
@@ -0,0 +1,383 @@
+use super::convert::{FromDateTime, IntoDateTime};
+use crate::Tz;
+use chrono::{
+    DateTime, Datelike, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike,
+};
+use jiff::{civil, tz::AmbiguousOffset, Timestamp, Zoned};
+use std::str::FromStr;
+
+/// A [`chrono::TimeZone`] backed by a [`jiff::tz::TimeZone`], which allows an
+/// [`RRuleSet`](crate::RRuleSet) to accept and yield [`jiff::Zoned`] values.
+///
+/// Local times are resolved like with the other timezones of `chrono`: a time in a gap (e.g.
+/// when the clocks are moved forward) doesn't exist, and a time in a fold (e.g. when the clocks
+/// are moved back) is ambiguous. The recurrences falling in a gap or a fold are resolved like
+/// with [`Tz`].
+///
+/// A [`jiff::Zoned`] can be converted into a `DateTime<JiffTz>` and into a `DateTime<Tz>`, so
+/// the timezone of the set has to be named when nothing else infers it, e.g.
+/// `RRuleSet::<JiffTz>::new(zoned)`.
+///
+/// # Usage
+///
+/// ```
+/// use jiff::{civil::date, Zoned};
+/// use rrule::{Frequency, RRule, RRuleSet};
+///
+/// let dt_start = date(2023, 3, 25).at(2, 30, 0, 0).in_tz("Europe/Berlin").unwrap();
+/// let rrule = RRule::new(Frequency::Daily).count(2).validate(&dt_start).unwrap();
+/// let rrule_set = RRuleSet::new(&dt_start).rrule(rrule);
+///
+/// // 02:30 does not exist on the 26th of March in Berlin
+/// let dates: Vec<Zoned> = rrule_set.into_iter().convert().collect();
+/// assert_eq!(dates[0], dt_start);
+/// assert_eq!(dates[1].to_string(), "2023-03-26T03:30:00+02:00[Europe/Berlin]");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JiffTz(jiff::tz::TimeZone);
+
+impl JiffTz {
+    /// Creates a [`JiffTz`] from a [`jiff::tz::TimeZone`].
+    #[must_use]
+    pub fn new(tz: jiff::tz::TimeZone) -> Self {
+        Self(tz)
+    }
+
+    /// Returns the [`jiff::tz::TimeZone`].
+    #[must_use]
+    pub fn get_time_zone(&self) -> &jiff::tz::TimeZone {
+        &self.0
+    }
+
+    /// Returns the offset of the timezone at the given timestamp.
+    fn offset_at(&self, timestamp: Timestamp) -> JiffOffset {
+        JiffOffset {
+            tz: self.clone(),
+            offset: to_fixed_offset(self.0.to_offset(timestamp)),
+        }
+    }
+}
+
+impl From<jiff::tz::TimeZone> for JiffTz {
+    fn from(tz: jiff::tz::TimeZone) -> Self {
+        Self(tz)
+    }
+}
+
+/// The offset of a [`JiffTz`] at a given instant.
+#[derive(Clone, Debug)]
+pub struct JiffOffset {
+    tz: JiffTz,
+    offset: FixedOffset,
+}
+
+impl Offset for JiffOffset {
+    fn fix(&self) -> FixedOffset {
+        self.offset
+    }
+}
+
+impl std::fmt::Display for JiffOffset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.offset.fmt(f)
+    }
+}
+
+impl TimeZone for JiffTz {
+    type Offset = JiffOffset;
+
+    fn from_offset(offset: &Self::Offset) -> Self {
+        offset.tz.clone()
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+        self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+        self.from_local_datetime(local)
+            .map(|dt| dt.offset().clone())
+    }
+
+    fn from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<DateTime<Self>> {
+        let Some(civil) = to_civil_datetime(local) else {
+            return LocalResult::None;
+        };
+        let at_offset = |offset: jiff::tz::Offset| {
+            let timestamp = offset.to_timestamp(civil).ok()?;
+            Some(DateTime::from_naive_utc_and_offset(
+                to_naive_utc(timestamp),
+                self.offset_at(timestamp),
+            ))
+        };
+        match self.0.to_ambiguous_timestamp(civil).offset() {
+            AmbiguousOffset::Unambiguous { offset } => {
+                at_offset(offset).map_or(LocalResult::None, LocalResult::Single)
+            }
+            AmbiguousOffset::Gap { .. } => LocalResult::None,
+            // The offset before the fold is the larger one, so it gives the earlier instant
+            AmbiguousOffset::Fold { before, after } => {
+                match (at_offset(before), at_offset(after)) {
+                    (Some(earliest), Some(latest)) => LocalResult::Ambiguous(earliest, latest),
+                    (Some(dt), None) | (None, Some(dt)) => LocalResult::Single(dt),
+                    (None, None) => LocalResult::None,
+                }
+            }
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+        // `jiff` supports a smaller range of years than `chrono`, so the offset at the closest
+        // supported instant is used outside of it.
+        let seconds = utc
+            .and_utc()
+            .timestamp()
+            .clamp(Timestamp::MIN.as_second(), Timestamp::MAX.as_second());
+        let timestamp = Timestamp::from_second(seconds).unwrap_or(Timestamp::UNIX_EPOCH);
+        self.offset_at(timestamp)
+    }
+}
+
+impl IntoDateTime<JiffTz> for Zoned {
+    fn into_datetime(self) -> DateTime<JiffTz> {
+        (&self).into_datetime()
+    }
+}
+
+impl IntoDateTime<JiffTz> for &Zoned {
+    fn into_datetime(self) -> DateTime<JiffTz> {
+        let tz = JiffTz(self.time_zone().clone());
+        let timestamp = self.timestamp();
+        DateTime::from_naive_utc_and_offset(to_naive_utc(timestamp), tz.offset_at(timestamp))
+    }
+}
+
+/// Allows a [`Zoned`] to be used with an `RRuleSet<Tz>`. As it also converts into a
+/// `DateTime<JiffTz>`, the timezone has to be named when it can't be inferred.
+impl IntoDateTime<Tz> for Zoned {
+    fn into_datetime(self) -> DateTime<Tz> {
+        (&self).into_datetime()
+    }
+}
+
+impl IntoDateTime<Tz> for &Zoned {
+    /// Converts into the [`Tz`] with the same IANA name, or into a [`Tz::Fixed`] with the
+    /// offset of the datetime if the timezone is not known by `chrono_tz`.
+    fn into_datetime(self) -> DateTime<Tz> {
+        let tz = self
+            .time_zone()
+            .iana_name()
+            .and_then(|name| chrono_tz::Tz::from_str(name).ok())
+            .map_or_else(|| Tz::Fixed(to_fixed_offset(self.offset())), Tz::Tz);
+        tz.from_utc_datetime(&to_naive_utc(self.timestamp()))
+    }
+}
+
+impl FromDateTime<JiffTz> for Zoned {
+    fn from_datetime(dt: DateTime<JiffTz>) -> Option<Self> {
+        let nanosecond = i32::try_from(dt.timestamp_subsec_nanos()).ok()?;
+        let timestamp = Timestamp::new(dt.timestamp(), nanosecond).ok()?;
+        Some(Self::new(timestamp, dt.timezone().0))
+    }
+}
+
+fn to_civil_datetime(dt: &NaiveDateTime) -> Option<civil::DateTime> {
+    civil::DateTime::new(
+        i16::try_from(dt.year()).ok()?,
+        i8::try_from(dt.month()).ok()?,
+        i8::try_from(dt.day()).ok()?,
+        i8::try_from(dt.hour()).ok()?,
+        i8::try_from(dt.minute()).ok()?,
+        i8::try_from(dt.second()).ok()?,
+        i32::try_from(dt.nanosecond()).ok()?,
+    )
+    .ok()
+}
+
+fn to_naive_utc(timestamp: Timestamp) -> NaiveDateTime {
+    let (mut second, mut nanosecond) = (timestamp.as_second(), timestamp.subsec_nanosecond());
+    // The fractional part of a negative `jiff` timestamp is negative as well
+    if nanosecond < 0 {
+        second -= 1;
+        nanosecond += 1_000_000_000;
+    }
+    let nanosecond = u32::try_from(nanosecond).expect("nanosecond is not negative");
+    DateTime::from_timestamp(second, nanosecond)
+        .expect("the range of jiff timestamps is within the range of chrono")
+        .naive_utc()
+}
+
+fn to_fixed_offset(offset: jiff::tz::Offset) -> FixedOffset {
+    FixedOffset::east_opt(offset.seconds()).unwrap_or_else(|| {
+        // `jiff` allows offsets of up to 26 hours, which never occur in the tz database
+        FixedOffset::east_opt(offset.seconds() % 86_400).expect("offset is within a day")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frequency, RRule, RRuleSet};
+    use jiff::civil::date;
+
+    fn berlin(dt: civil::DateTime) -> Zoned {
+        dt.in_tz("Europe/Berlin").unwrap()
+    }
+
+    #[test]
+    fn converts_zoned_to_and_from_datetime() {
+        let tests = [
+            berlin(date(2023, 7, 1).at(9, 30, 15, 123)),
+            date(1960, 1, 1).at(0, 0, 0, 500).in_tz("UTC").unwrap(),
+            date(-500, 3, 1)
+                .at(12, 0, 0, 0)
+                .in_tz("America/New_York")
+                .unwrap(),
+        ];
+        for zoned in tests {
+            let dt: DateTime<JiffTz> = (&zoned).into_datetime();
+            assert_eq!(
+                (dt.year(), dt.hour(), dt.nanosecond()),
+                (
+                    i32::from(zoned.year()),
+                    u32::try_from(zoned.hour()).unwrap(),
+                    u32::try_from(zoned.subsec_nanosecond()).unwrap()
+                )
+            );
+            assert_eq!(
+                dt.offset().fix().local_minus_utc(),
+                zoned.offset().seconds()
+            );
+            assert_eq!(Zoned::from_datetime(dt), Some(zoned));
+        }
+    }
+
+    #[test]
+    fn converts_zoned_to_tz() {
+        let zoned = berlin(date(2023, 7, 1).at(9, 0, 0, 0));
+        let dt: DateTime<Tz> = zoned.into_datetime();
+        assert_eq!(dt.timezone(), Tz::Europe__Berlin);
+        assert_eq!(dt.to_rfc3339(), "2023-07-01T09:00:00+02:00");
+
+        let fixed = date(2023, 7, 1)
+            .at(9, 0, 0, 0)
+            .to_zoned(jiff::tz::TimeZone::fixed(jiff::tz::offset(-3)))
+            .unwrap();
+        let dt: DateTime<Tz> = fixed.into_datetime();
+        assert_eq!(
+            dt.timezone(),
+            Tz::Fixed(FixedOffset::west_opt(3 * 3600).unwrap())
+        );
+        assert_eq!(dt.to_rfc3339(), "2023-07-01T09:00:00-03:00");
+    }
+
+    #[test]
+    fn resolves_local_times_like_tz() {
+        let tz = JiffTz::new(jiff::tz::TimeZone::get("Europe/Berlin").unwrap());
+        let local = |month, day, h, m| {
+            NaiveDate::from_ymd_opt(2023, month, day)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .unwrap()
+        };
+        let rfc3339 = |res: LocalResult<DateTime<JiffTz>>| res.map(|dt| dt.to_rfc3339());
+        let rfc3339_tz = |res: LocalResult<DateTime<Tz>>| res.map(|dt| dt.to_rfc3339());
+
+        let tests = [
+            // Gap
+            (local(3, 26, 2, 30), LocalResult::None),
+            // Fold
+            (
+                local(10, 29, 2, 30),
+                LocalResult::Ambiguous(
+                    "2023-10-29T02:30:00+02:00".to_string(),
+                    "2023-10-29T02:30:00+01:00".to_string(),
+                ),
+            ),
+            (
+                local(10, 29, 3, 30),
+                LocalResult::Single("2023-10-29T03:30:00+01:00".to_string()),
+            ),
+        ];
+        for (local, expected) in tests {
+            assert_eq!(rfc3339(tz.from_local_datetime(&local)), expected, "{local}");
+            assert_eq!(
+                rfc3339_tz(Tz::Europe__Berlin.from_local_datetime(&local)),
+                expected,
+                "{local}"
+            );
+        }
+    }
+
+    #[test]
+    fn iterates_over_gaps_and_folds_like_tz() {
+        let dates = |dt_start: &Zoned| {
+            let rrule = RRule::new(Frequency::Daily)
+                .count(3)
+                .validate(dt_start)
+                .unwrap();
+            let jiff_dates = RRuleSet::<JiffTz>::new(dt_start)
+                .rrule(rrule.clone())
+                .into_iter()
+                .map(|dt| dt.to_rfc3339())
+                .collect::<Vec<_>>();
+            let tz_dates = RRuleSet::<Tz>::new(dt_start)
+                .rrule(rrule)
+                .into_iter()
+                .map(|dt| dt.to_rfc3339())
+                .collect::<Vec<_>>();
+            assert_eq!(jiff_dates, tz_dates, "{dt_start}");
+            jiff_dates
+        };
+
+        assert_eq!(
+            dates(&berlin(date(2023, 3, 25).at(2, 30, 0, 0))),
+            [
+                "2023-03-25T02:30:00+01:00",
+                "2023-03-26T03:30:00+02:00",
+                "2023-03-27T02:30:00+02:00",
+            ]
+        );
+        assert_eq!(
+            dates(&berlin(date(2023, 10, 28).at(2, 30, 0, 0))),
+            [
+                "2023-10-28T02:30:00+02:00",
+                "2023-10-29T02:30:00+02:00",
+                "2023-10-30T02:30:00+01:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn iterates_over_zoned_values() {
+        let dt_start = berlin(date(2023, 10, 27).at(2, 30, 0, 0));
+        let rrule = RRule::new(Frequency::Daily)
+            .count(4)
+            .validate(&dt_start)
+            .unwrap();
+        let rrule_set = RRuleSet::new(&dt_start)
+            .rrule(rrule)
+            .exdate(berlin(date(2023, 10, 28).at(2, 30, 0, 0)))
+            .rdate(berlin(date(2023, 11, 1).at(12, 0, 0, 0)));
+
+        let dates = rrule_set
+            .into_iter()
+            .convert::<Zoned>()
+            .map(|zoned| zoned.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            [
+                "2023-10-27T02:30:00+02:00[Europe/Berlin]",
+                "2023-10-29T02:30:00+02:00[Europe/Berlin]",
+                "2023-10-30T02:30:00+01:00[Europe/Berlin]",
+                "2023-11-01T12:00:00+01:00[Europe/Berlin]",
+            ]
+        );
+    }
+}
@@ -1,16 +1,38 @@
+mod calendar;
+mod convert;
 mod datetime;
+mod event_duration;
+#[cfg(feature = "jiff")]
+mod jiff_tz;
+mod recurrence_override;
 mod rrule;
 mod rruleset;
+mod rscale;
+#[cfg(feature = "time")]
+mod time_convert;
 mod timezone;
 mod timezone_impl;
 pub(crate) mod utils;
+mod vtimezone;
 
+pub use self::calendar::{Property, VCalendar, VEvent};
+pub use self::convert::{FromDateTime, IntoDateTime};
+pub use self::event_duration::EventDuration;
+pub(crate) use self::recurrence_override::{
+    max_backward_shift, override_occurrence, RecurrenceOverrides,
+};
+pub use self::recurrence_override::{RecurrenceOverride, RecurrenceRange};
 pub use self::rrule::{Frequency, NWeekday, RRule};
-pub use self::rruleset::{RRuleResult, RRuleSet};
+pub use self::rruleset::{Occurrence, RRuleResult, RRuleSet};
+pub(crate) use self::rscale::CalendarMonth;
+pub use self::rscale::{RScale, Skip};
 pub(crate) use datetime::{
     duration_from_midnight, get_day, get_hour, get_minute, get_month, get_second,
 };
+#[cfg(feature = "jiff")]
+pub use jiff_tz::{JiffOffset, JiffTz};
 pub use timezone::Tz;
+pub use vtimezone::{Observance, ObservanceKind, VTimezone};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 /// An empty struct to keep the validated stage
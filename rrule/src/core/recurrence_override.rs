@@ -0,0 +1,250 @@
+//! Modified instances of an [`RRuleSet`](crate::RRuleSet), identified by their
+//! `RECURRENCE-ID` as defined in
+//! [RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545#section-3.8.4.4).
+
+use crate::core::{EventDuration, Occurrence};
+use crate::parser::ParseError;
+use crate::{IntoDateTime, Tz};
+use chrono::{DateTime, Duration, TimeZone};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The overrides of a set, keyed by the original start of the instance they modify.
+pub(crate) type RecurrenceOverrides<TZ> = BTreeMap<DateTime<TZ>, RecurrenceOverride<TZ>>;
+
+/// Which instances a [`RecurrenceOverride`] applies to, set by the `RANGE` parameter of
+/// `RECURRENCE-ID`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RecurrenceRange {
+    /// Only the instance starting at the `RECURRENCE-ID`.
+    #[default]
+    ThisInstance,
+    /// The instance starting at the `RECURRENCE-ID` and all the later ones.
+    ThisAndFuture,
+}
+
+impl Display for RecurrenceRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::ThisInstance => "THISINSTANCE",
+            Self::ThisAndFuture => "THISANDFUTURE",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for RecurrenceRange {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_uppercase()[..] {
+            "THISINSTANCE" => Ok(Self::ThisInstance),
+            "THISANDFUTURE" => Ok(Self::ThisAndFuture),
+            _ => Err(ParseError::InvalidRecurrenceRange(s.into())),
+        }
+    }
+}
+
+/// A modified instance of an [`RRuleSet`](crate::RRuleSet), which is moved to a new start and
+/// can have its own duration and payload.
+///
+/// With [`RecurrenceRange::ThisAndFuture`], all the later instances are moved by the same amount
+/// of time as well, and get the same duration and payload.
+///
+/// # Usage
+///
+/// ```
+/// use chrono::TimeZone;
+/// use rrule::{RRuleSet, RecurrenceOverride, Tz};
+///
+/// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY;COUNT=3"
+///     .parse()
+///     .unwrap();
+/// let rrule_set = rrule_set.recurrence_override(
+///     Tz::UTC.with_ymd_and_hms(2021, 1, 1, 9, 0, 0).unwrap(),
+///     RecurrenceOverride::new(Tz::UTC.with_ymd_and_hms(2021, 1, 2, 15, 0, 0).unwrap())
+///         .payload("moved to the afternoon"),
+/// );
+///
+/// let occurrences: Vec<_> = rrule_set.occurrences().collect();
+/// assert_eq!(occurrences[0].start.to_rfc3339(), "2021-01-02T09:00:00+00:00");
+/// assert_eq!(occurrences[1].start.to_rfc3339(), "2021-01-02T15:00:00+00:00");
+/// assert_eq!(occurrences[1].payload.as_deref(), Some("moved to the afternoon"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceOverride<TZ: TimeZone = Tz> {
+    /// The new start of the instance.
+    pub(crate) start: DateTime<TZ>,
+    /// The new duration of the instance, or `None` to keep the duration of the set.
+    pub(crate) duration: Option<EventDuration>,
+    /// Data attached to the instance, which is not interpreted.
+    pub(crate) payload: Option<String>,
+    /// Which instances are modified.
+    pub(crate) range: RecurrenceRange,
+}
+
+impl<TZ: TimeZone> RecurrenceOverride<TZ> {
+    /// Creates an override moving a single instance to `start`.
+    #[must_use]
+    pub fn new(start: impl IntoDateTime<TZ>) -> Self {
+        Self {
+            start: start.into_datetime(),
+            duration: None,
+            payload: None,
+            range: RecurrenceRange::default(),
+        }
+    }
+
+    /// Sets the new duration of the instance, see [`RRuleSet::duration`](crate::RRuleSet::duration).
+    ///
+    /// A negative duration is replaced by zero.
+    #[must_use]
+    pub fn duration(mut self, duration: impl Into<EventDuration>) -> Self {
+        self.duration = Some(duration.into());
+        self
+    }
+
+    /// Sets the payload of the instance.
+    #[must_use]
+    pub fn payload(mut self, payload: impl Into<String>) -> Self {
+        self.payload = Some(payload.into());
+        self
+    }
+
+    /// Sets which instances are modified.
+    #[must_use]
+    pub fn range(mut self, range: RecurrenceRange) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Returns the new start of the instance.
+    #[must_use]
+    pub fn get_start(&self) -> &DateTime<TZ> {
+        &self.start
+    }
+
+    /// Returns the new duration of the instance, if set.
+    #[must_use]
+    pub fn get_duration(&self) -> Option<EventDuration> {
+        self.duration
+    }
+
+    /// Returns the payload of the instance, if set.
+    #[must_use]
+    pub fn get_payload(&self) -> Option<&str> {
+        self.payload.as_deref()
+    }
+
+    /// Returns which instances are modified.
+    #[must_use]
+    pub fn get_range(&self) -> RecurrenceRange {
+        self.range
+    }
+}
+
+/// Returns the occurrence of the instance starting at `recurrence_id`, after applying the
+/// override of the instance, or else the latest `THISANDFUTURE` override before it.
+///
+/// Returns `None` if the end of the occurrence can not be represented.
+pub(crate) fn override_occurrence<TZ: TimeZone>(
+    overrides: &RecurrenceOverrides<TZ>,
+    recurrence_id: DateTime<TZ>,
+    duration: EventDuration,
+) -> Option<Occurrence<TZ>> {
+    let mut start = recurrence_id.clone();
+    let mut duration = duration;
+    let mut payload = None;
+
+    let future = overrides
+        .range(..=&recurrence_id)
+        .rev()
+        .find(|(_, instance)| instance.range == RecurrenceRange::ThisAndFuture);
+    if let Some((id, instance)) = future {
+        start = start.checked_add_signed(instance.start.clone() - id.clone())?;
+        duration = instance.duration.unwrap_or(duration);
+        payload.clone_from(&instance.payload);
+    }
+    if let Some(instance) = overrides
+        .get(&recurrence_id)
+        .filter(|instance| instance.range == RecurrenceRange::ThisInstance)
+    {
+        start = instance.start.clone();
+        duration = instance.duration.unwrap_or(duration);
+        payload = instance.payload.clone().or(payload);
+    }
+
+    Occurrence::new(recurrence_id, start, duration, payload)
+}
+
+/// Returns the largest amount of time an override moves an instance back, or zero.
+pub(crate) fn max_backward_shift<TZ: TimeZone>(overrides: &RecurrenceOverrides<TZ>) -> Duration {
+    overrides
+        .iter()
+        .map(|(id, instance)| id.clone() - instance.start.clone())
+        .fold(Duration::zero(), Duration::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parses_recurrence_range() {
+        assert_eq!(
+            "THISANDFUTURE".parse::<RecurrenceRange>(),
+            Ok(RecurrenceRange::ThisAndFuture)
+        );
+        assert_eq!(
+            "thisinstance".parse::<RecurrenceRange>(),
+            Ok(RecurrenceRange::ThisInstance)
+        );
+        assert_eq!(
+            "THISANDPRIOR".parse::<RecurrenceRange>(),
+            Err(ParseError::InvalidRecurrenceRange("THISANDPRIOR".into()))
+        );
+        assert_eq!(RecurrenceRange::ThisAndFuture.to_string(), "THISANDFUTURE");
+    }
+
+    #[test]
+    fn applies_latest_override() {
+        let date = |day| Tz::UTC.with_ymd_and_hms(2021, 1, day, 9, 0, 0).unwrap();
+        let mut overrides = RecurrenceOverrides::new();
+        overrides.insert(
+            date(2),
+            RecurrenceOverride::new(date(2) + Duration::hours(1))
+                .duration(Duration::hours(2))
+                .payload("later")
+                .range(RecurrenceRange::ThisAndFuture),
+        );
+        overrides.insert(
+            date(4),
+            RecurrenceOverride::new(date(4) - Duration::hours(3)),
+        );
+
+        let occurrence =
+            |day| override_occurrence(&overrides, date(day), Duration::hours(1).into());
+        let first = occurrence(1).unwrap();
+        assert_eq!(
+            (first.start, first.end),
+            (date(1), date(1) + Duration::hours(1))
+        );
+        assert_eq!(first.payload, None);
+
+        let third = occurrence(3).unwrap();
+        assert_eq!(third.recurrence_id, date(3));
+        assert_eq!(third.start, date(3) + Duration::hours(1));
+        assert_eq!(third.end, date(3) + Duration::hours(3));
+        assert_eq!(third.payload.as_deref(), Some("later"));
+
+        // The override of the instance wins, but keeps the duration and payload of the range
+        let fourth = occurrence(4).unwrap();
+        assert_eq!(fourth.start, date(4) - Duration::hours(3));
+        assert_eq!(fourth.end, date(4) - Duration::hours(1));
+        assert_eq!(fourth.payload.as_deref(), Some("later"));
+
+        assert_eq!(max_backward_shift(&overrides), Duration::hours(3));
+    }
+}
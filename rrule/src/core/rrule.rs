@@ -3,16 +3,18 @@ use crate::core::get_hour;
 use crate::core::get_minute;
 use crate::core::get_month;
 use crate::core::get_second;
-use crate::iter::RRuleIter;
+use crate::iter::rrule_rev_iter::RRuleRevIter;
+use crate::iter::{IterLimits, Limiter, RRuleIter};
 use crate::parser::str_to_weekday;
 use crate::parser::ContentLineCaptures;
 use crate::parser::ParseError;
 use crate::validator::validate_rrule;
-use crate::validator::ValidationError;
+use crate::IntoDateTime;
 use crate::Tz;
-use crate::{RRuleError, RRuleSet, Unvalidated, Validated};
+use crate::{FieldError, RRuleError, RRuleSet, Unvalidated, Validated};
+use crate::{RScale, Skip};
 use chrono::DateTime;
-use chrono::{Datelike, Month, Weekday};
+use chrono::{Datelike, Month, TimeZone, Weekday};
 #[cfg(feature = "serde")]
 use serde_with::{serde_as, DeserializeFromStr, SerializeDisplay};
 use std::cmp::Ordering;
@@ -274,6 +276,15 @@ pub struct RRule<Stage = Validated> {
     /// Can be a value from -366 to 366.
     /// Note: Only used when `by-easter` feature flag is set. Otherwise, it is ignored.
     pub(crate) by_easter: Option<i16>,
+    /// The calendar system of the recurrence, as defined by [RFC 7529](https://datatracker.ietf.org/doc/html/rfc7529).
+    /// The Gregorian calendar is used when it is not set.
+    pub(crate) rscale: Option<RScale>,
+    /// How dates which do not exist in a year or month are handled.
+    /// Unlike RFC 7529, it can be used without `rscale` as well.
+    pub(crate) skip: Option<Skip>,
+    /// The leap months to apply the recurrence to, e.g. `5` for the Hebrew month `5L`.
+    /// Can be a value from 1 to 12.
+    pub(crate) by_leap_month: Vec<u8>,
     /// A phantom data to have the stage (unvalidated or validated).
     #[cfg_attr(feature = "serde", serde_as(as = "ignore"))]
     pub(crate) stage: PhantomData<Stage>,
@@ -299,6 +310,9 @@ impl Default for RRule<Unvalidated> {
             by_minute: Vec::new(),
             by_second: Vec::new(),
             by_easter: None,
+            rscale: None,
+            skip: None,
+            by_leap_month: Vec::new(),
             stage: PhantomData,
         }
     }
@@ -338,8 +352,8 @@ impl RRule<Unvalidated> {
     /// If given, this must be a datetime instance specifying the
     /// upper-bound limit of the recurrence.
     #[must_use]
-    pub fn until(mut self, until: DateTime<Tz>) -> Self {
-        self.until = Some(until);
+    pub fn until(mut self, until: impl IntoDateTime<Tz>) -> Self {
+        self.until = Some(until.into_datetime());
         self
     }
 
@@ -367,9 +381,7 @@ impl RRule<Unvalidated> {
     pub fn by_month(mut self, by_month: &[Month]) -> Self {
         self.by_month = by_month
             .iter()
-            .map(|month| {
-                u8::try_from(month.number_from_month()).expect("1-12 is within range of u8")
-            })
+            .filter_map(|month| u8::try_from(month.number_from_month()).ok())
             .collect();
         self
     }
@@ -442,6 +454,30 @@ impl RRule<Unvalidated> {
         self
     }
 
+    /// The calendar system of the recurrence, see [`RScale`].
+    #[must_use]
+    pub fn rscale(mut self, rscale: RScale) -> Self {
+        self.rscale = Some(rscale);
+        self
+    }
+
+    /// How dates which do not exist in a year or month are handled, see [`Skip`].
+    /// For example, with [`Skip::Backward`] a `by_month_day` of `31` is the last day of
+    /// the months with less than 31 days.
+    #[must_use]
+    pub fn skip(mut self, skip: Skip) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    /// When given, these variables will define the leap months to apply the recurrence to,
+    /// e.g. `5` for the Hebrew month `5L`. Requires a non-Gregorian [`RRule::rscale`].
+    #[must_use]
+    pub fn by_leap_month(mut self, by_leap_month: Vec<u8>) -> Self {
+        self.by_leap_month = by_leap_month;
+        self
+    }
+
     /// Fills in some additional fields in order to make iter work correctly.
     pub(crate) fn finalize_parsed_rrule(mut self, dt_start: &DateTime<Tz>) -> Self {
         // TEMP: move negative months to another list
@@ -472,17 +508,35 @@ impl RRule<Unvalidated> {
             || !self.by_weekday.is_empty()
             || by_easter_is_some)
         {
+            // With a non-Gregorian calendar, the month and day of `dt_start` in that calendar
+            let calendar_date = self
+                .calendar()
+                .map(|rscale| (rscale, rscale.calendar_date(dt_start.date_naive())));
+            let day = match calendar_date {
+                Some((_, date)) => i8::try_from(date.day).expect("day is between 1 and 30"),
+                None => get_day(dt_start),
+            };
             match self.freq {
                 Frequency::Yearly => {
-                    if self.by_month.is_empty() {
-                        let month = get_month(dt_start);
-                        self.by_month = vec![month];
+                    if self.by_month.is_empty() && self.by_leap_month.is_empty() {
+                        match calendar_date {
+                            Some((rscale, _)) => {
+                                let (month, leap) = rscale.month_code(dt_start.date_naive());
+                                if leap {
+                                    self.by_leap_month = vec![month];
+                                } else {
+                                    self.by_month = vec![month];
+                                }
+                            }
+                            None => {
+                                let month = get_month(dt_start);
+                                self.by_month = vec![month];
+                            }
+                        }
                     }
-                    let day = get_day(dt_start);
                     self.by_month_day = vec![day];
                 }
                 Frequency::Monthly => {
-                    let day = get_day(dt_start);
                     self.by_month_day = vec![day];
                 }
                 Frequency::Weekly => {
@@ -523,6 +577,9 @@ impl RRule<Unvalidated> {
         self.by_month.sort_unstable();
         self.by_month.dedup();
 
+        self.by_leap_month.sort_unstable();
+        self.by_leap_month.dedup();
+
         self.by_month_day.sort_unstable();
         self.by_month_day.dedup();
 
@@ -549,36 +606,65 @@ impl RRule<Unvalidated> {
     /// # Errors
     ///
     /// If the properties aren't valid, it will return [`RRuleError`].
-    pub fn validate(self, dt_start: DateTime<Tz>) -> Result<RRule<Validated>, RRuleError> {
+    pub fn validate(self, dt_start: impl IntoDateTime<Tz>) -> Result<RRule<Validated>, RRuleError> {
+        let dt_start = dt_start.into_datetime();
         let rrule = self.finalize_parsed_rrule(&dt_start);
 
         // Validate required checks (defined by RFC 5545)
         validate_rrule::validate_rrule_forced(&rrule, &dt_start)?;
+        validate_rrule::validate_timeset(&rrule)?;
 
-        // Check if it is possible to generate a timeset
-        match rrule.freq {
-            Frequency::Hourly => {
-                if rrule.by_minute.is_empty() && rrule.by_second.is_empty() {
-                    return Err(ValidationError::UnableToGenerateTimeset.into());
-                }
-            }
-            Frequency::Minutely => {
-                if rrule.by_second.is_empty() {
-                    return Err(ValidationError::UnableToGenerateTimeset.into());
-                }
-            }
-            Frequency::Secondly => {}
-            _ => {
-                if rrule.by_hour.is_empty()
-                    && rrule.by_minute.is_empty()
-                    && rrule.by_second.is_empty()
-                {
-                    return Err(ValidationError::UnableToGenerateTimeset.into());
-                }
-            }
+        Ok(rrule.into_validated())
+    }
+
+    /// Validates the [`RRule`] with the given `dt_start`, like [`RRule::validate`], but returns
+    /// the errors of all the rule parts instead of only the first one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FieldError`] for every rule part which isn't valid, like `BYMONTHDAY`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{Frequency, RRule, Tz};
+    ///
+    /// let dt_start = Tz::UTC.with_ymd_and_hms(2023, 1, 1, 9, 0, 0).unwrap();
+    /// let errors = RRule::new(Frequency::Weekly)
+    ///     .by_set_pos(vec![0])
+    ///     .by_hour(vec![24])
+    ///     .validate_all(dt_start)
+    ///     .unwrap_err();
+    /// let fields = errors.iter().map(|error| error.get_field()).collect::<Vec<_>>();
+    /// assert_eq!(fields, ["BYSETPOS", "BYHOUR"]);
+    /// ```
+    pub fn validate_all(
+        self,
+        dt_start: impl IntoDateTime<Tz>,
+    ) -> Result<RRule<Validated>, Vec<FieldError>> {
+        let dt_start = dt_start.into_datetime();
+        let rrule = self.finalize_parsed_rrule(&dt_start);
+
+        let mut errors = validate_rrule::validate_rrule_all(&rrule, &dt_start)
+            .into_iter()
+            .map(|(part, error)| FieldError::new(part, error))
+            .collect::<Vec<_>>();
+        if let Err(error) = validate_rrule::validate_timeset(&rrule) {
+            errors.push(FieldError::new("FREQ", error));
         }
 
-        Ok(RRule {
+        if errors.is_empty() {
+            Ok(rrule.into_validated())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Changes the stage of an rrule which passed the validation.
+    fn into_validated(self) -> RRule<Validated> {
+        let rrule = self;
+        RRule {
             freq: rrule.freq,
             interval: rrule.interval,
             count: rrule.count,
@@ -595,8 +681,11 @@ impl RRule<Unvalidated> {
             by_minute: rrule.by_minute,
             by_second: rrule.by_second,
             by_easter: rrule.by_easter,
+            rscale: rrule.rscale,
+            skip: rrule.skip,
+            by_leap_month: rrule.by_leap_month,
             stage: PhantomData,
-        })
+        }
     }
 
     /// Validates the [`RRule`] with the given `dt_start` and creates an [`RRuleSet`] struct.
@@ -604,7 +693,8 @@ impl RRule<Unvalidated> {
     /// # Errors
     ///
     /// Returns [`RRuleError::ValidationError`] in case the rrule is invalid.
-    pub fn build(self, dt_start: DateTime<Tz>) -> Result<RRuleSet, RRuleError> {
+    pub fn build(self, dt_start: impl IntoDateTime<Tz>) -> Result<RRuleSet, RRuleError> {
+        let dt_start = dt_start.into_datetime();
         let rrule = self.validate(dt_start)?;
         let rrule_set = RRuleSet::new(dt_start).rrule(rrule);
         Ok(rrule_set)
@@ -612,8 +702,38 @@ impl RRule<Unvalidated> {
 }
 
 impl RRule {
-    pub(crate) fn iter_with_ctx(&self, dt_start: DateTime<Tz>, limited: bool) -> RRuleIter {
-        RRuleIter::new(self, &dt_start, limited)
+    /// Returns true if `dt` is a recurrence of the rrule starting at `dt_start`.
+    ///
+    /// Only the periods around `dt` are generated, without iterating from `dt_start`,
+    /// except for rrules with a `COUNT`. The loop iterations are limited as with
+    /// [`IterLimits::default`], and `false` is returned when the limit is reached.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{Frequency, NWeekday, RRule, Tz, Weekday};
+    ///
+    /// let dt_start = Tz::UTC.with_ymd_and_hms(2001, 1, 1, 9, 0, 0).unwrap();
+    /// let rrule = RRule::new(Frequency::Weekly)
+    ///     .by_weekday(vec![NWeekday::Every(Weekday::Mon)])
+    ///     .validate(dt_start)
+    ///     .unwrap();
+    ///
+    /// assert!(rrule.contains(&dt_start, &Tz::UTC.with_ymd_and_hms(2024, 6, 3, 9, 0, 0).unwrap()));
+    /// assert!(!rrule.contains(&dt_start, &Tz::UTC.with_ymd_and_hms(2024, 6, 4, 9, 0, 0).unwrap()));
+    /// ```
+    #[must_use]
+    pub fn contains<TZ: TimeZone>(&self, dt_start: &DateTime<TZ>, dt: &DateTime<TZ>) -> bool {
+        RRuleRevIter::contains(self, dt_start, dt, &Limiter::new(&IterLimits::default()))
+    }
+
+    pub(crate) fn iter_with_ctx<TZ: TimeZone>(
+        &self,
+        dt_start: DateTime<TZ>,
+        limiter: Limiter,
+    ) -> RRuleIter<TZ> {
+        RRuleIter::new(self, &dt_start, limiter)
     }
 }
 
@@ -634,7 +754,11 @@ impl<S> Display for RRule<S> {
     /// So if you want a valid string, it's smarter to always use `rrule.validate(ds_start)?.to_string()`.
     #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut res = Vec::with_capacity(15);
+        let mut res = Vec::with_capacity(17);
+        if let Some(rscale) = &self.rscale {
+            res.push(format!("RSCALE={}", rscale));
+        }
+
         res.push(format!("FREQ={}", &self.freq));
 
         if let Some(until) = &self.until {
@@ -671,12 +795,24 @@ impl<S> Display for RRule<S> {
             ));
         }
 
-        if !self.by_month.is_empty() {
+        if !self.by_month.is_empty() || !self.by_leap_month.is_empty() {
+            // Leap months are listed after the month with the same number, e.g. `5,5L,6`
+            let mut months = self
+                .by_month
+                .iter()
+                .map(|month| (*month, false))
+                .chain(self.by_leap_month.iter().map(|month| (*month, true)))
+                .collect::<Vec<_>>();
+            months.sort_unstable();
             res.push(format!(
                 "BYMONTH={}",
-                self.by_month
-                    .iter()
-                    .map(ToString::to_string)
+                months
+                    .into_iter()
+                    .map(|(month, leap)| if leap {
+                        format!("{}L", month)
+                    } else {
+                        month.to_string()
+                    })
                     .collect::<Vec<_>>()
                     .join(",")
             ));
@@ -764,6 +900,10 @@ impl<S> Display for RRule<S> {
             res.push(format!("BYEASTER={}", by_easter));
         }
 
+        if let Some(skip) = &self.skip {
+            res.push(format!("SKIP={}", skip));
+        }
+
         write!(f, "{}", res.join(";"))
     }
 }
@@ -859,4 +999,27 @@ impl<S> RRule<S> {
     pub fn get_by_easter(&self) -> Option<&i16> {
         self.by_easter.as_ref()
     }
+
+    /// Get the `rscale` of the recurrence.
+    #[must_use]
+    pub fn get_rscale(&self) -> Option<RScale> {
+        self.rscale
+    }
+
+    /// Get the `skip` of the recurrence.
+    #[must_use]
+    pub fn get_skip(&self) -> Option<Skip> {
+        self.skip
+    }
+
+    /// Get the `by_leap_month` of the recurrence.
+    #[must_use]
+    pub fn get_by_leap_month(&self) -> &[u8] {
+        &self.by_leap_month
+    }
+
+    /// Returns the calendar system of the recurrence, if it is not the Gregorian calendar.
+    pub(crate) fn calendar(&self) -> Option<RScale> {
+        self.rscale.filter(|rscale| *rscale != RScale::Gregorian)
+    }
 }
@@ -1,68 +1,179 @@
-use crate::core::datetime::datetime_to_ical_format;
-use crate::core::utils::collect_with_error;
+use crate::core::convert::IntoDateTime;
+use crate::core::datetime::{datetime_to_ical_format, duration_to_ical_format};
+use crate::core::utils::{collect_with_error, fold_content_line, is_in_range};
+use crate::core::{override_occurrence, EventDuration, RecurrenceOverrides};
+use crate::iter::counter_date::DateTimeIter;
+use crate::iter::rrule_iter::WasLimited;
+use crate::iter::rrule_rev_iter::RRuleRevIter;
+use crate::iter::Limiter;
 use crate::parser::{ContentLine, Grammar};
-use crate::{ParseError, RRule, RRuleError, Tz};
-use chrono::DateTime;
+use crate::{
+    BetweenIter, FieldError, IterLimits, OccurrenceIter, ParseError, RRule, RRuleError,
+    RRuleSetIter, RRuleSetRevIter, RecurrenceOverride, RecurrenceRange, Tz,
+};
+use chrono::{DateTime, Duration, TimeZone};
 #[cfg(feature = "serde")]
 use serde_with::{serde_as, DeserializeFromStr, SerializeDisplay};
+use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::ops::Range;
 use std::str::FromStr;
 
+/// The number of earlier instances of each rule [`RRuleSet::split_at`] tries as the start of its
+/// second set, before falling back to `DTSTART`.
+const SPLIT_START_CANDIDATES: usize = 1000;
+
+/// A rule split by [`RRuleSet::split_rule`].
+struct SplitRule {
+    /// The rule of the first set, if it has instances before the split.
+    first: Option<RRule>,
+    /// The rule of the second set, if it has instances at or after the split.
+    second: Option<RRule>,
+    /// The instances at or after the split which the rule of the second set doesn't generate.
+    moved: Vec<DateTime<Tz>>,
+    /// The instances before the split which the rule of the second set generates.
+    skipped: Vec<DateTime<Tz>>,
+}
+
+/// The rules of a set split by [`RRuleSet::split_at`], see [`SplitRule`].
+#[derive(Default)]
+struct SplitRules {
+    first: Vec<RRule>,
+    second: Vec<RRule>,
+    moved: Vec<DateTime<Tz>>,
+    skipped: Vec<DateTime<Tz>>,
+}
+
 /// A validated Recurrence Rule that can be used to create an iterator.
+///
+/// The recurrences are generated in the timezone of `dt_start`, which can be any
+/// [`chrono::TimeZone`] (e.g. `chrono::Utc` or `chrono_tz::Tz`) and defaults to [`Tz`].
+/// Parsing and printing the iCalendar format is only available for [`Tz`].
+///
+/// # Usage
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use rrule::{Frequency, RRule, RRuleSet, Tz};
+///
+/// let dt_start = Utc.with_ymd_and_hms(2021, 1, 1, 9, 0, 0).unwrap();
+/// let rrule = RRule::new(Frequency::Daily)
+///     .count(2)
+///     .validate(dt_start.with_timezone(&Tz::UTC))
+///     .unwrap();
+///
+/// let dates: Vec<_> = RRuleSet::new(dt_start).rrule(rrule).into_iter().collect();
+/// assert_eq!(dates, vec![dt_start, Utc.with_ymd_and_hms(2021, 1, 2, 9, 0, 0).unwrap()]);
+/// ```
 #[cfg_attr(feature = "serde", serde_as)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(DeserializeFromStr, SerializeDisplay))]
-pub struct RRuleSet {
+pub struct RRuleSet<TZ: TimeZone = Tz> {
     /// List of rrules.
     pub(crate) rrule: Vec<RRule>,
     /// List of rdates.
-    pub(crate) rdate: Vec<DateTime<Tz>>,
+    pub(crate) rdate: Vec<DateTime<TZ>>,
     /// List of exules.
     pub(crate) exrule: Vec<RRule>,
     /// List of exdates.
-    pub(crate) exdate: Vec<DateTime<Tz>>,
+    pub(crate) exdate: Vec<DateTime<TZ>>,
     /// The start datetime of the recurring event.
-    pub(crate) dt_start: DateTime<Tz>,
+    pub(crate) dt_start: DateTime<TZ>,
+    /// The duration of every recurrence, given by `DURATION` or derived from `DTEND`.
+    pub(crate) duration: Option<EventDuration>,
+    /// Modified instances, keyed by their original start (`RECURRENCE-ID`).
+    pub(crate) overrides: RecurrenceOverrides<TZ>,
     /// If set, all returned recurrences must be before this date.
-    pub(crate) before: Option<DateTime<Tz>>,
+    pub(crate) before: Option<DateTime<TZ>>,
     /// If set, all returned recurrences must be after this date.
-    pub(crate) after: Option<DateTime<Tz>>,
-    /// If validation limits are enabled
-    pub(crate) limited: bool,
+    pub(crate) after: Option<DateTime<TZ>>,
+    /// Limits of the iterators over the set.
+    pub(crate) limits: IterLimits,
 }
 
 /// The return result of `RRuleSet::all`.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct RRuleResult {
+pub struct RRuleResult<TZ: TimeZone = Tz> {
     /// List of recurrences.
-    pub dates: Vec<DateTime<Tz>>,
+    pub dates: Vec<DateTime<TZ>>,
     /// It is being true if the list of dates is limited.
     /// To indicate that it can potentially contain more dates.
     pub limited: bool,
 }
 
-impl RRuleSet {
+/// A recurrence of an [`RRuleSet`] together with its end, see [`RRuleSet::occurrences`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Occurrence<TZ: TimeZone = Tz> {
+    /// The original start of the occurrence, which identifies it like a `RECURRENCE-ID`.
+    pub recurrence_id: DateTime<TZ>,
+    /// The start of the occurrence (inclusive), which differs from `recurrence_id` when the
+    /// occurrence was moved by a [`RecurrenceOverride`].
+    pub start: DateTime<TZ>,
+    /// The end of the occurrence (exclusive), which is `start` plus the duration of the set,
+    /// or of the [`RecurrenceOverride`] of the occurrence.
+    pub end: DateTime<TZ>,
+    /// The payload of the [`RecurrenceOverride`] of the occurrence, if any.
+    pub payload: Option<String>,
+}
+
+impl<TZ: TimeZone> Occurrence<TZ> {
+    /// Creates an occurrence lasting `duration`, or `None` if its end can not be represented.
+    pub(crate) fn new(
+        recurrence_id: DateTime<TZ>,
+        start: DateTime<TZ>,
+        duration: EventDuration,
+        payload: Option<String>,
+    ) -> Option<Self> {
+        let end = duration.add_to(&start)?;
+        Some(Self {
+            recurrence_id,
+            start,
+            end,
+            payload,
+        })
+    }
+
+    /// Returns true if the occurrence overlaps with `range`.
+    ///
+    /// An occurrence without duration overlaps if its start is in `range`.
+    #[must_use]
+    pub fn overlaps(&self, range: &Range<DateTime<TZ>>) -> bool {
+        self.start < range.end && (self.end > range.start || self.start >= range.start)
+    }
+}
+
+impl<TZ: TimeZone> RRuleSet<TZ> {
     /// Creates an empty [`RRuleSet`], starting from `ds_start`.
     #[must_use]
-    pub fn new(dt_start: DateTime<Tz>) -> Self {
+    pub fn new(dt_start: impl IntoDateTime<TZ>) -> Self {
         Self {
-            dt_start,
+            dt_start: dt_start.into_datetime(),
+            duration: None,
+            overrides: RecurrenceOverrides::new(),
             rrule: vec![],
             rdate: vec![],
             exrule: vec![],
             exdate: vec![],
             before: None,
             after: None,
-            limited: false,
+            limits: IterLimits::default(),
         }
     }
 
-    /// Enable validation limits.
+    /// Enable validation limits, see [`IterLimits::default`].
     ///
-    /// This is only needed if you are going to use the Iterator api directly.
+    /// They are enabled by default, so this is only needed to restore them after
+    /// [`RRuleSet::iter_limits`].
     #[must_use]
     pub fn limit(mut self) -> Self {
-        self.limited = true;
+        self.limits = IterLimits::default();
+        self
+    }
+
+    /// Sets the limits of the iterators over the set, and of [`RRuleSet::all_within_limits`].
+    #[must_use]
+    pub fn iter_limits(mut self, limits: IterLimits) -> Self {
+        self.limits = limits;
         self
     }
 
@@ -70,8 +181,8 @@ impl RRuleSet {
     ///
     /// This value will not be used if you use the `Iterator` API directly.
     #[must_use]
-    pub fn before(mut self, dt: DateTime<Tz>) -> Self {
-        self.before = Some(dt);
+    pub fn before(mut self, dt: impl IntoDateTime<TZ>) -> Self {
+        self.before = Some(dt.into_datetime());
         self
     }
 
@@ -79,8 +190,37 @@ impl RRuleSet {
     ///
     /// This value will not be used if you use the `Iterator` API directly.
     #[must_use]
-    pub fn after(mut self, dt: DateTime<Tz>) -> Self {
-        self.after = Some(dt);
+    pub fn after(mut self, dt: impl IntoDateTime<TZ>) -> Self {
+        self.after = Some(dt.into_datetime());
+        self
+    }
+
+    /// Sets the duration of every recurrence, like the `DURATION` property does.
+    ///
+    /// A [`chrono::Duration`] is exact, see [`EventDuration`] for a duration of nominal days.
+    /// A negative duration is replaced by zero.
+    #[must_use]
+    pub fn duration(mut self, duration: impl Into<EventDuration>) -> Self {
+        self.duration = Some(duration.into());
+        self
+    }
+
+    /// Modifies the instance starting at `recurrence_id`, or with
+    /// [`RecurrenceRange::ThisAndFuture`] that instance and all the later ones.
+    ///
+    /// Overrides are applied by [`RRuleSet::occurrences`] and [`RRuleSet::overlapping`],
+    /// the other iterators yield the original dates of the set. An override which does not
+    /// match an instance of the set is ignored, so a [`RecurrenceRange::ThisAndFuture`] one doesn't
+    /// move the later instances either. Overrides are not part of the iCalendar string
+    /// of the set, as they are separate components with a `RECURRENCE-ID`.
+    #[must_use]
+    pub fn recurrence_override(
+        mut self,
+        recurrence_id: impl IntoDateTime<TZ>,
+        instance: RecurrenceOverride<TZ>,
+    ) -> Self {
+        self.overrides
+            .insert(recurrence_id.into_datetime(), instance);
         self
     }
 
@@ -101,15 +241,15 @@ impl RRuleSet {
 
     /// Adds a new rdate to the set.
     #[must_use]
-    pub fn rdate(mut self, rdate: DateTime<Tz>) -> Self {
-        self.rdate.push(rdate);
+    pub fn rdate(mut self, rdate: impl IntoDateTime<TZ>) -> Self {
+        self.rdate.push(rdate.into_datetime());
         self
     }
 
     /// Adds a new exdate to the set.
     #[must_use]
-    pub fn exdate(mut self, exdate: DateTime<Tz>) -> Self {
-        self.exdate.push(exdate);
+    pub fn exdate(mut self, exdate: impl IntoDateTime<TZ>) -> Self {
+        self.exdate.push(exdate.into_datetime());
         self
     }
 
@@ -130,14 +270,14 @@ impl RRuleSet {
 
     /// Sets the rdates of the set.
     #[must_use]
-    pub fn set_rdates(mut self, rdates: Vec<DateTime<Tz>>) -> Self {
+    pub fn set_rdates(mut self, rdates: Vec<DateTime<TZ>>) -> Self {
         self.rdate = rdates;
         self
     }
 
     /// Set the exdates of the set.
     #[must_use]
-    pub fn set_exdates(mut self, exdates: Vec<DateTime<Tz>>) -> Self {
+    pub fn set_exdates(mut self, exdates: Vec<DateTime<TZ>>) -> Self {
         self.exdate = exdates;
         self
     }
@@ -156,26 +296,47 @@ impl RRuleSet {
 
     /// Returns the rdates of the set.
     #[must_use]
-    pub fn get_rdate(&self) -> &Vec<DateTime<Tz>> {
+    pub fn get_rdate(&self) -> &Vec<DateTime<TZ>> {
         &self.rdate
     }
 
     /// Returns the exdates of the set.
     #[must_use]
-    pub fn get_exdate(&self) -> &Vec<DateTime<Tz>> {
+    pub fn get_exdate(&self) -> &Vec<DateTime<TZ>> {
         &self.exdate
     }
 
     /// Returns the start datetime of the recurring event.
     #[must_use]
-    pub fn get_dt_start(&self) -> &DateTime<Tz> {
+    pub fn get_dt_start(&self) -> &DateTime<TZ> {
         &self.dt_start
     }
 
+    /// Returns the duration of every recurrence, if set.
+    #[must_use]
+    pub fn get_duration(&self) -> Option<EventDuration> {
+        self.duration
+    }
+
+    /// Returns the limits of the iterators over the set.
+    #[must_use]
+    pub fn get_iter_limits(&self) -> IterLimits {
+        self.limits
+    }
+
+    /// Returns the modified instances, keyed by their original start (`RECURRENCE-ID`).
+    #[must_use]
+    pub fn get_recurrence_overrides(&self) -> &BTreeMap<DateTime<TZ>, RecurrenceOverride<TZ>> {
+        &self.overrides
+    }
+
     /// Returns all the recurrences of the rrule.
     ///
     /// Limit must be set in order to prevent infinite loops.
-    /// The max limit is `65535`. If you need more please use `into_iter` directly.
+    /// The max limit is `65535`. If you need more please use [`RRuleSet::all_within_limits`].
+    ///
+    /// The loop iterations are limited as with [`RRuleSet::limit`], unless the
+    /// [`IterLimits`] of the set already limit them.
     ///
     /// # Usage
     ///
@@ -190,17 +351,419 @@ impl RRuleSet {
     /// assert_eq!(result.limited, true);
     /// ```
     #[must_use]
-    pub fn all(mut self, limit: u16) -> RRuleResult {
-        self.limited = true;
+    pub fn all(self, limit: u16) -> RRuleResult<TZ> {
+        let limits = IterLimits {
+            max_results: Some(limit.into()),
+            max_loop_iterations: self
+                .limits
+                .max_loop_iterations
+                .or(IterLimits::default().max_loop_iterations),
+            ..self.limits
+        };
+        self.iter_limits(limits).all_within_limits()
+    }
+
+    /// Returns all the recurrences of the rrule, within the [`IterLimits`] of the set.
+    ///
+    /// Unlike [`RRuleSet::all`], the number of recurrences is only limited by
+    /// [`IterLimits::max_results`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{IterLimits, RRuleSet};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T000000Z\nRRULE:FREQ=SECONDLY".parse().unwrap();
+    ///
+    /// let result = rrule_set
+    ///     .iter_limits(IterLimits::default().max_results(100_000))
+    ///     .all_within_limits();
+    /// assert_eq!(result.dates.len(), 100_000);
+    /// assert_eq!(result.limited, true);
+    /// ```
+    #[must_use]
+    pub fn all_within_limits(self) -> RRuleResult<TZ> {
         collect_with_error(
-            self.into_iter(),
+            self.iter_after(),
             &self.after,
             &self.before,
             true,
-            Some(limit),
+            self.limits.max_results,
         )
     }
 
+    /// Returns an iterator over the recurrences starting at `dt`, see [`RRuleSetIter::seek`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20010101T090000Z\nRRULE:FREQ=MINUTELY"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let dt = Tz::UTC.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+    /// let dates: Vec<_> = rrule_set.iter_from(dt).take(2).collect();
+    /// assert_eq!(dates[0], dt);
+    /// assert_eq!(dates[1].to_rfc3339(), "2024-06-01T12:01:00+00:00");
+    /// ```
+    pub fn iter_from(&self, dt: impl IntoDateTime<TZ>) -> RRuleSetIter<TZ> {
+        let mut iter = self.into_iter();
+        iter.seek(dt);
+        iter
+    }
+
+    /// Returns an iterator over the recurrences between `start` and `end`, including them
+    /// if `inclusive` is true.
+    ///
+    /// Unlike [`RRuleSet::all`], the recurrences are not collected and there is no limit on
+    /// their number. The rrules jump straight to the period containing `start`, see
+    /// [`RRuleSetIter::seek`], and the iterator stops at the first recurrence after `end`.
+    /// Use [`BetweenIter::was_limited`] to check if it was stopped by the iterator limits instead.
+    /// Like [`RRuleSet::iter_from`], the `before` and `after` dates of the set are not used.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20010101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let start = Tz::UTC.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap();
+    /// let end = Tz::UTC.with_ymd_and_hms(2024, 6, 4, 9, 0, 0).unwrap();
+    /// let mut iter = rrule_set.limit().between(start, end, false);
+    ///
+    /// let dates: Vec<_> = iter.by_ref().collect();
+    /// assert_eq!(dates[0].to_rfc3339(), "2024-06-02T09:00:00+00:00");
+    /// assert_eq!(dates[1].to_rfc3339(), "2024-06-03T09:00:00+00:00");
+    /// assert_eq!(dates.len(), 2);
+    /// assert!(!iter.was_limited());
+    /// ```
+    pub fn between(
+        &self,
+        start: impl IntoDateTime<TZ>,
+        end: impl IntoDateTime<TZ>,
+        inclusive: bool,
+    ) -> BetweenIter<TZ> {
+        BetweenIter::new(
+            self.into_iter(),
+            start.into_datetime(),
+            end.into_datetime(),
+            inclusive,
+        )
+    }
+
+    /// Returns the first recurrence after `dt`, or at `dt` if `inclusive` is true.
+    ///
+    /// The rrules jump straight to the period containing `dt`, see [`RRuleSetIter::seek`].
+    /// Like [`RRuleSet::iter_from`], the `before` and `after` dates of the set are not used.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20010101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let dt = Tz::UTC.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap();
+    /// assert_eq!(rrule_set.next_after(dt, true), Some(dt));
+    /// assert_eq!(
+    ///     rrule_set.next_after(dt, false).unwrap().to_rfc3339(),
+    ///     "2024-06-02T09:00:00+00:00"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn next_after(&self, dt: impl IntoDateTime<TZ>, inclusive: bool) -> Option<DateTime<TZ>> {
+        let dt = dt.into_datetime();
+        self.iter_from(dt.clone())
+            .find(|date| inclusive || *date != dt)
+    }
+
+    /// Returns the last recurrence before `dt`, or at `dt` if `inclusive` is true.
+    ///
+    /// The rrules are walked back from the period containing `dt`, see [`RRuleSet::iter_rev`],
+    /// so only rrules with a `COUNT` are iterated from the start.
+    /// Like [`RRuleSet::iter_from`], the `before` and `after` dates of the set are not used.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20010101T090000Z\nRRULE:FREQ=DAILY".parse().unwrap();
+    ///
+    /// let dt = Tz::UTC.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap();
+    /// assert_eq!(rrule_set.prev_before(dt, true), Some(dt));
+    /// assert_eq!(
+    ///     rrule_set.prev_before(dt, false).unwrap().to_rfc3339(),
+    ///     "2024-05-31T09:00:00+00:00"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn prev_before(&self, dt: impl IntoDateTime<TZ>, inclusive: bool) -> Option<DateTime<TZ>> {
+        let dt = dt.into_datetime();
+        RRuleSetRevIter::bounded(self, Some(&dt), None)
+            .ok()?
+            .find(|date| inclusive || *date != dt)
+    }
+
+    /// Returns an iterator over the recurrences in reverse order, starting at the last one.
+    ///
+    /// Unlike iterating over `&RRuleSet`, the `before` and `after` dates of the set are used
+    /// to bound the iteration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError::IterError`] if the set has no last recurrence, which is the case
+    /// when an rrule has no `COUNT` or `UNTIL` and the set has no `before` date.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY;UNTIL=20210105T090000Z"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let dates: Vec<_> = rrule_set.iter_rev().unwrap().take(2).collect();
+    /// assert_eq!(dates[0].to_rfc3339(), "2021-01-05T09:00:00+00:00");
+    /// assert_eq!(dates[1].to_rfc3339(), "2021-01-04T09:00:00+00:00");
+    /// ```
+    pub fn iter_rev(&self) -> Result<RRuleSetRevIter<TZ>, RRuleError> {
+        RRuleSetRevIter::new(self)
+    }
+
+    /// Returns true if `dt` is a recurrence of the set.
+    ///
+    /// The rrules and exrules only generate the periods around `dt`, see [`RRule::contains`].
+    /// Like [`RRuleSet::iter_from`], the `before` and `after` dates of the set are not used.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20010101T090000Z\nRRULE:FREQ=DAILY\nEXDATE:20240601T090000Z"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// assert!(rrule_set.contains(Tz::UTC.with_ymd_and_hms(2024, 5, 31, 9, 0, 0).unwrap()));
+    /// assert!(!rrule_set.contains(Tz::UTC.with_ymd_and_hms(2024, 6, 1, 9, 0, 0).unwrap()));
+    /// ```
+    pub fn contains(&self, dt: impl IntoDateTime<TZ>) -> bool {
+        let dt = dt.into_datetime();
+        let limiter = Limiter::new(&self.limits);
+        let rrule_contains =
+            |rrule: &RRule| RRuleRevIter::contains(rrule, &self.dt_start, &dt, &limiter);
+
+        if self
+            .exdate
+            .iter()
+            .any(|exdate| exdate.timestamp() == dt.timestamp())
+            || self.exrule.iter().any(rrule_contains)
+        {
+            return false;
+        }
+        self.rdate.contains(&dt) || self.rrule.iter().any(rrule_contains)
+    }
+
+    /// Returns true if the set has a last recurrence, which is the case when every rrule has
+    /// a `COUNT` or `UNTIL`, or the set has a `before` date.
+    ///
+    /// This only looks at the rules, the recurrences are not generated.
+    #[must_use]
+    pub fn is_finite(&self) -> bool {
+        self.before.is_some()
+            || self
+                .rrule
+                .iter()
+                .all(|rrule| rrule.count.is_some() || rrule.until.is_some())
+    }
+
+    /// Returns the last recurrence of the set, or `None` if the set is not finite (see
+    /// [`RRuleSet::is_finite`]) or has no recurrences.
+    ///
+    /// Like [`RRuleSet::all`], the `before` and `after` dates of the set are used.
+    /// The rrules with an `UNTIL` are walked back from it, see [`RRuleSet::iter_rev`],
+    /// so only rrules with a `COUNT` are iterated from the start.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20010101T090000Z\nRRULE:FREQ=HOURLY;UNTIL=20240601T123000Z"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(rrule_set.last().unwrap().to_rfc3339(), "2024-06-01T12:00:00+00:00");
+    /// ```
+    #[must_use]
+    pub fn last(&self) -> Option<DateTime<TZ>> {
+        self.iter_rev().ok()?.next()
+    }
+
+    /// Returns the number of recurrences of the set, or `None` if there are more than `limit`.
+    ///
+    /// Like [`RRuleSet::all`], the `before` and `after` dates of the set are used.
+    /// A set which is not finite (see [`RRuleSet::is_finite`]) returns `None` without
+    /// generating any recurrence, otherwise the recurrences are counted without being stored.
+    /// `None` is also returned when the iteration was stopped by the validation limits,
+    /// see [`RRuleSet::limit`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=MINUTELY;COUNT=100000"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(rrule_set.count_occurrences(1_000_000), Some(100_000));
+    /// assert_eq!(rrule_set.count_occurrences(10), None);
+    /// ```
+    #[must_use]
+    pub fn count_occurrences(&self, limit: u64) -> Option<u64> {
+        if !self.is_finite() {
+            return None;
+        }
+
+        let mut count = 0;
+        let mut iter = self.iter_after();
+        for date in iter.by_ref() {
+            if matches!(&self.before, Some(before) if date > *before) {
+                break;
+            }
+            if is_in_range(&date, &self.after, &self.before, true) {
+                count += 1;
+                if count > limit {
+                    return None;
+                }
+            }
+        }
+
+        (!iter.was_limited()).then_some(count)
+    }
+
+    /// Returns an iterator over the recurrences together with their end.
+    ///
+    /// Recurrences end after the duration of the set, or at their start if it has none.
+    /// The [`RecurrenceOverride`]s of the set are applied, and moved occurrences are yielded
+    /// in order of their new start.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nDURATION:PT1H30M\nRRULE:FREQ=DAILY"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let occurrence = rrule_set.occurrences().next().unwrap();
+    /// assert_eq!(occurrence.start.to_rfc3339(), "2021-01-01T09:00:00+00:00");
+    /// assert_eq!(occurrence.end.to_rfc3339(), "2021-01-01T10:30:00+00:00");
+    /// ```
+    pub fn occurrences(&self) -> OccurrenceIter<TZ> {
+        OccurrenceIter::new(
+            self.into_iter(),
+            self.duration.unwrap_or_default(),
+            self.applied_overrides(),
+        )
+    }
+
+    /// Returns the overrides to apply, without the [`RecurrenceRange::ThisAndFuture`] ones
+    /// whose `RECURRENCE-ID` is not an instance of the set, as they would move the later
+    /// instances otherwise.
+    fn applied_overrides(&self) -> RecurrenceOverrides<TZ> {
+        self.overrides
+            .iter()
+            .filter(|(recurrence_id, instance)| {
+                instance.range == RecurrenceRange::ThisInstance
+                    || self.contains((*recurrence_id).clone())
+            })
+            .map(|(recurrence_id, instance)| (recurrence_id.clone(), instance.clone()))
+            .collect()
+    }
+
+    /// Returns all the occurrences overlapping with `range`, including the ones which started
+    /// before it and have not ended yet.
+    ///
+    /// Like [`RRuleSet::iter_from`], this does not use the `before` and `after` dates of the set.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nDURATION:PT2H\nRRULE:FREQ=DAILY"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let start = Tz::UTC.with_ymd_and_hms(2021, 1, 3, 10, 0, 0).unwrap();
+    /// let end = Tz::UTC.with_ymd_and_hms(2021, 1, 4, 10, 0, 0).unwrap();
+    /// let occurrences = rrule_set.overlapping(start..end);
+    /// assert_eq!(occurrences.len(), 2);
+    /// assert_eq!(occurrences[0].start.to_rfc3339(), "2021-01-03T09:00:00+00:00");
+    /// assert_eq!(occurrences[1].start.to_rfc3339(), "2021-01-04T09:00:00+00:00");
+    /// ```
+    #[must_use]
+    pub fn overlapping(&self, range: Range<DateTime<TZ>>) -> Vec<Occurrence<TZ>> {
+        let duration = self.duration.unwrap_or_default();
+        let overrides = self.applied_overrides();
+        // Instances moved along with the later ones are found by widening the range
+        let mut max_duration = duration.max_exact();
+        let (mut min_shift, mut max_shift) = (Duration::zero(), Duration::zero());
+        for (recurrence_id, instance) in &overrides {
+            if instance.range == RecurrenceRange::ThisAndFuture {
+                let shift = instance.start.clone() - recurrence_id.clone();
+                min_shift = min_shift.min(shift);
+                max_shift = max_shift.max(shift);
+                max_duration = max_duration.max(instance.duration.unwrap_or(duration).max_exact());
+            }
+        }
+
+        // Occurrences starting at `range.start - max_duration` or at `range.end` only overlap
+        // when they end there, which `Occurrence::overlaps` decides.
+        let start = range.start.clone() - max_duration - max_shift;
+        let end = range.end.clone() - min_shift;
+        let mut recurrence_ids = collect_with_error(
+            self.iter_from(start.clone()),
+            &Some(start.clone()),
+            &Some(end.clone()),
+            true,
+            None,
+        )
+        .dates;
+        // Instances moved on their own can come from anywhere
+        recurrence_ids.extend(
+            overrides
+                .iter()
+                .filter(|(recurrence_id, instance)| {
+                    instance.range == RecurrenceRange::ThisInstance
+                        && !(&start..=&end).contains(recurrence_id)
+                        && self.iter_from((*recurrence_id).clone()).next().as_ref()
+                            == Some(*recurrence_id)
+                })
+                .map(|(recurrence_id, _)| recurrence_id.clone()),
+        );
+
+        let mut occurrences: Vec<_> = recurrence_ids
+            .into_iter()
+            .filter_map(|recurrence_id| override_occurrence(&overrides, recurrence_id, duration))
+            .filter(|occurrence| occurrence.overlaps(&range))
+            .collect();
+        occurrences.sort_by(|a, b| a.start.cmp(&b.start));
+        occurrences
+    }
+
     /// Returns all the recurrences of the rrule.
     ///
     /// # Note
@@ -208,53 +771,391 @@ impl RRuleSet {
     /// This method does not enforce any validation limits and might lead to
     /// very long iteration times. Please read the `SECURITY.md` for more information.
     #[must_use]
-    pub fn all_unchecked(self) -> Vec<DateTime<Tz>> {
-        collect_with_error(self.into_iter(), &self.after, &self.before, true, None).dates
+    pub fn all_unchecked(self) -> Vec<DateTime<TZ>> {
+        let rrule_set = self.iter_limits(IterLimits::unlimited());
+        collect_with_error(
+            rrule_set.iter_after(),
+            &rrule_set.after,
+            &rrule_set.before,
+            true,
+            None,
+        )
+        .dates
     }
 
-    fn set_from_content_lines(self, content_lines: Vec<ContentLine>) -> Result<Self, RRuleError> {
+    /// Returns an iterator which starts at `after`, if set.
+    fn iter_after(&self) -> RRuleSetIter<TZ> {
+        match &self.after {
+            Some(after) => self.iter_from(after),
+            None => self.into_iter(),
+        }
+    }
+}
+
+impl RRuleSet {
+    pub(crate) fn set_from_content_lines(
+        self,
+        content_lines: Vec<ContentLine>,
+    ) -> Result<Self, RRuleError> {
+        let (rrule_set, mut errors) = self.set_from_content_lines_lenient(content_lines);
+        if errors.is_empty() {
+            Ok(rrule_set)
+        } else {
+            Err(errors.swap_remove(0).error)
+        }
+    }
+
+    /// Sets the content lines like [`RRuleSet::set_from_content_lines`], but skips the rules
+    /// which are invalid and returns the errors of all of them.
+    fn set_from_content_lines_lenient(
+        self,
+        content_lines: Vec<ContentLine>,
+    ) -> (Self, Vec<FieldError>) {
         let dt_start = self.dt_start;
+        let mut errors = vec![];
 
-        content_lines.into_iter().try_fold(
-            self,
-            |rrule_set, content_line| match content_line {
-                ContentLine::RRule(rrule) => rrule
-                    .validate(dt_start)
-                    .map(|rrule| rrule_set.rrule(rrule)),
+        let rrule_set = content_lines
+            .into_iter()
+            .fold(self, |rrule_set, content_line| match content_line {
+                ContentLine::RRule(rrule) => match rrule.validate_all(dt_start) {
+                    Ok(rrule) => rrule_set.rrule(rrule),
+                    Err(rrule_errors) => {
+                        errors.extend(rrule_errors);
+                        rrule_set
+                    }
+                },
                 #[allow(unused_variables)]
                 ContentLine::ExRule(exrule) => {
                     #[cfg(feature = "exrule")]
                     {
-                        exrule
-                            .validate(dt_start)
-                            .map(|exrule| rrule_set.exrule(exrule))
+                        match exrule.validate_all(dt_start) {
+                            Ok(exrule) => rrule_set.exrule(exrule),
+                            Err(exrule_errors) => {
+                                errors.extend(exrule_errors);
+                                rrule_set
+                            }
+                        }
                     }
                     #[cfg(not(feature = "exrule"))]
                     {
                         log::warn!("Found EXRULE in input, but it will be ignored since the `exrule` feature is not enabled.");
-                        Ok(rrule_set)
+                        rrule_set
                     }
                 }
-                ContentLine::ExDate(exdates) => {
-                    Ok(exdates.into_iter().fold(rrule_set, Self::exdate))
-                }
-                ContentLine::RDate(rdates) => {
-                    Ok(rdates.into_iter().fold(rrule_set, Self::rdate))
-                }
+                ContentLine::ExDate(exdates) => exdates.into_iter().fold(rrule_set, Self::exdate),
+                ContentLine::RDate(rdates) => rdates.into_iter().fold(rrule_set, Self::rdate),
+            });
+
+        (rrule_set, errors)
+    }
+
+    /// Parses an [`RRuleSet`] like [`RRuleSet::from_str`], but returns the errors of all the
+    /// properties and rule parts of the input instead of only the first one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FieldError`] for every problem of the input, with the rule part (like
+    /// `BYMONTHDAY` or `UNTIL`) or the property (like `EXDATE`) it concerns.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let errors = RRuleSet::parse_lenient(
+    ///     "DTSTART:20230101T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=32;BYHOUR=25\nEXDATE:2023",
+    /// )
+    /// .unwrap_err();
+    /// let fields = errors.iter().map(|error| error.get_field()).collect::<Vec<_>>();
+    /// assert_eq!(fields, ["BYMONTHDAY", "BYHOUR", "EXDATE"]);
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<Self, Vec<FieldError>> {
+        let (
+            Grammar {
+                start,
+                duration,
+                content_lines,
             },
-        )
+            errors,
+        ) = Grammar::parse_lenient(s);
+        let mut errors = errors
+            .into_iter()
+            .map(|(field, located)| FieldError {
+                location: located.location.map(|location| *location),
+                ..FieldError::new(field, located.error)
+            })
+            .collect::<Vec<_>>();
+
+        let Some(start) = start else {
+            // The rules can't be validated without a start date
+            if !errors.iter().any(|error| error.field == "DTSTART") {
+                errors.push(FieldError::new("DTSTART", ParseError::MissingStartDate));
+            }
+            return Err(errors);
+        };
+
+        let mut rrule_set = Self::new(start.datetime);
+        if let Some(duration) = duration {
+            rrule_set = rrule_set.duration(duration);
+        }
+        let (rrule_set, rule_errors) = rrule_set.set_from_content_lines_lenient(content_lines);
+        errors.extend(rule_errors);
+
+        if errors.is_empty() {
+            Ok(rrule_set)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Splits the set at `dt`, like when editing "this and all following" instances of a series.
+    ///
+    /// The first set has the recurrences before `dt`: its rules end at their last instance
+    /// before `dt` with an `UNTIL`, which replaces their `COUNT`. The second set starts at the
+    /// first instance of its rules at or after `dt`, and its rules with a `COUNT` only count the
+    /// remaining instances. When the periods of a rule with an `INTERVAL` don't line up with
+    /// that instance, the second set starts at an earlier instance they line up with instead,
+    /// and the instances before `dt` are excluded from it with exdates. The instances a rule
+    /// can't generate from the start of the second set, like those moved past `dt` by
+    /// `SKIP=FORWARD`, are added to it as rdates (or exdates for exrules). Rules without any
+    /// instance left in a set are removed from it. The rdates, exdates and recurrence
+    /// overrides are divided between both sets.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY;COUNT=5"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let (first, second) = rrule_set.split_at(Tz::UTC.with_ymd_and_hms(2021, 1, 3, 9, 0, 0).unwrap());
+    /// assert_eq!(
+    ///     first.to_string(),
+    ///     "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY;UNTIL=20210102T090000Z;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+    /// );
+    /// assert_eq!(
+    ///     second.to_string(),
+    ///     "DTSTART:20210103T090000Z\nRRULE:FREQ=DAILY;COUNT=3;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn split_at(&self, dt: impl IntoDateTime<Tz>) -> (Self, Self) {
+        let dt = dt.into_datetime();
+        let start = self.split_start(&dt);
+
+        let split_rules = |rules: &[RRule]| {
+            let mut split = SplitRules::default();
+            for rule in rules {
+                let SplitRule {
+                    first,
+                    second,
+                    moved,
+                    skipped,
+                } = self.split_rule(rule, &dt, &start);
+                split.first.extend(first);
+                split.second.extend(second);
+                split.moved.extend(moved);
+                split.skipped.extend(skipped);
+            }
+            split
+        };
+        let add_dates = |dates: &mut Vec<DateTime<Tz>>, new_dates: Vec<DateTime<Tz>>| {
+            for date in new_dates {
+                if !dates.contains(&date) {
+                    dates.push(date);
+                }
+            }
+        };
+        let (first_rdates, mut second_rdates): (Vec<_>, Vec<_>) =
+            self.rdate.iter().copied().partition(|rdate| *rdate < dt);
+        let (first_exdates, mut second_exdates): (Vec<_>, Vec<_>) =
+            self.exdate.iter().copied().partition(|exdate| *exdate < dt);
+        let rrules = split_rules(&self.rrule);
+        let exrules = split_rules(&self.exrule);
+        add_dates(&mut second_rdates, rrules.moved);
+        add_dates(&mut second_exdates, rrules.skipped);
+        // The instances of the exrules before `dt` don't need to be excluded, as the second set
+        // has no other instance before `dt`
+        add_dates(&mut second_exdates, exrules.moved);
+
+        let first = Self {
+            rrule: rrules.first,
+            rdate: first_rdates,
+            exrule: exrules.first,
+            exdate: first_exdates,
+            overrides: self
+                .overrides
+                .range(..&dt)
+                .map(|(id, instance)| (*id, instance.clone()))
+                .collect(),
+            ..self.clone()
+        };
+        let second = Self {
+            rrule: rrules.second,
+            rdate: second_rdates,
+            exrule: exrules.second,
+            exdate: second_exdates,
+            dt_start: start,
+            overrides: self
+                .overrides
+                .range(&dt..)
+                .map(|(id, instance)| (*id, instance.clone()))
+                .collect(),
+            ..self.clone()
+        };
+        (first, second)
+    }
+
+    /// Returns the start of the second set of [`RRuleSet::split_at`].
+    ///
+    /// This is the first instance of the rrules at or after `dt`, unless the periods of a rule
+    /// with an `INTERVAL` counted from there would differ from the ones counted from
+    /// `dt_start`. The latest earlier instance they don't differ from is used then, which
+    /// falls back to `dt_start`.
+    fn split_start(&self, dt: &DateTime<Tz>) -> DateTime<Tz> {
+        let limiter = || Limiter::new(&self.limits);
+        let first_after = self
+            .rrule
+            .iter()
+            .filter_map(|rule| {
+                let mut iter = rule.iter_with_ctx(self.dt_start, limiter());
+                iter.seek(dt);
+                iter.next()
+            })
+            .min();
+        let Some(first_after) = first_after else {
+            return self
+                .rdate
+                .iter()
+                .filter(|rdate| *rdate >= dt)
+                .min()
+                .copied()
+                .unwrap_or_else(|| (*dt).max(self.dt_start));
+        };
+
+        let dt_start = self.dt_start.naive_local();
+        let is_aligned = |date: &DateTime<Tz>| {
+            self.rrule
+                .iter()
+                .chain(&self.exrule)
+                .all(|rule| DateTimeIter::is_aligned(rule, &dt_start, &date.naive_local()))
+        };
+        if is_aligned(&first_after) {
+            return first_after;
+        }
+        let mut earlier: Vec<_> = self
+            .rrule
+            .iter()
+            .filter_map(|rule| {
+                RRuleRevIter::new(rule, &self.dt_start, Some(first_after), &limiter())
+            })
+            .flat_map(|iter| iter.take(SPLIT_START_CANDIDATES))
+            .collect();
+        earlier.sort_unstable_by(|a, b| b.cmp(a));
+        earlier
+            .into_iter()
+            .find(is_aligned)
+            .unwrap_or(self.dt_start)
+    }
+
+    /// Splits a rule of the set at `dt`, where `start` is the start of the second set, see
+    /// [`RRuleSet::split_at`].
+    ///
+    fn split_rule(&self, rule: &RRule, dt: &DateTime<Tz>, start: &DateTime<Tz>) -> SplitRule {
+        let limiter = || Limiter::new(&self.limits);
+        let last_before = RRuleRevIter::new(rule, &self.dt_start, Some(*dt), &limiter())
+            .and_then(|mut iter| iter.find(|date| date < dt));
+
+        let mut tail = RRule {
+            count: None,
+            ..rule.clone()
+        }
+        .iter_with_ctx(*start, limiter())
+        .peekable();
+        let mut skipped = vec![];
+        while let Some(date) = tail.next_if(|date| date < dt) {
+            skipped.push(date);
+        }
+        let mut after = rule.iter_with_ctx(self.dt_start, limiter());
+        after.seek(dt);
+
+        let (has_after, count, moved) = if rule.count.is_some() {
+            // A rule with a `COUNT` has a limited number of instances, so the second rule can
+            // count the ones it generates out of them
+            let original: Vec<_> = rule.iter_with_ctx(self.dt_start, limiter()).collect();
+            let last = original.last();
+            let generated: Vec<_> = skipped
+                .iter()
+                .copied()
+                .chain(tail)
+                .take_while(|date| Some(date) <= last)
+                .collect();
+            skipped.retain(|date| generated.contains(date));
+            let moved = after
+                .filter(|date| generated.binary_search(date).is_err())
+                .collect();
+            let count = u32::try_from(generated.len()).expect("count is covered by u32");
+            (generated.len() > skipped.len(), Some(count), moved)
+        } else {
+            // The instances before the first one of the second rule, which were moved after
+            // `dt` from a period before `start`
+            let moved = match tail.peek() {
+                Some(first_after) => after.take_while(|date| date < first_after).collect(),
+                None if rule.until.is_some() => after.collect(),
+                None => vec![],
+            };
+            (tail.peek().is_some(), None, moved)
+        };
+
+        let second = has_after.then(|| RRule {
+            count,
+            ..rule.clone()
+        });
+        if second.is_none() {
+            skipped.clear();
+        }
+
+        let first = last_before.map(|last_before| {
+            if second.is_none() && moved.is_empty() {
+                return rule.clone();
+            }
+            // `UNTIL` needs to be in UTC, unless `DTSTART` is in local time
+            let until = if self.dt_start.timezone().is_local() {
+                last_before.with_timezone(&Tz::LOCAL)
+            } else {
+                last_before.with_timezone(&Tz::UTC)
+            };
+            RRule {
+                until: Some(until),
+                count: None,
+                ..rule.clone()
+            }
+        });
+        SplitRule {
+            first,
+            second,
+            moved,
+            skipped,
+        }
     }
 
     /// Set the [`RRuleSet`] properties from a string. If a DTSTART is found, it will be used as the start datetime.
     pub fn set_from_string(mut self, s: &str) -> Result<Self, RRuleError> {
         let Grammar {
             start,
+            duration,
             content_lines,
         } = Grammar::from_str(s)?;
 
         if let Some(dtstart) = start {
             self.dt_start = dtstart.datetime;
         }
+        if duration.is_some() {
+            self.duration = duration;
+        }
 
         self.set_from_content_lines(content_lines)
     }
@@ -271,32 +1172,50 @@ impl FromStr for RRuleSet {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let Grammar {
             start,
+            duration,
             content_lines,
         } = Grammar::from_str(s)?;
 
         let start = start.ok_or(ParseError::MissingStartDate)?;
 
-        Self::new(start.datetime).set_from_content_lines(content_lines)
+        let mut rrule_set = Self::new(start.datetime);
+        if let Some(duration) = duration {
+            rrule_set = rrule_set.duration(duration);
+        }
+        rrule_set.set_from_content_lines(content_lines)
     }
 }
 
 impl Display for RRuleSet {
     /// Prints a valid set of iCalendar properties which can be used to create a new [`RRuleSet`] later.
     /// You may use the generated string to create a new iCalendar component, like VEVENT.
+    ///
+    /// The properties are separated by `\n` by default. With the alternate flag (`{:#}`) they are
+    /// separated by `CRLF` instead, and lines longer than 75 octets are folded as required by
+    /// [RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545#section-3.1).
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=3".parse().unwrap();
+    /// assert_eq!(
+    ///     format!("{rrule_set:#}"),
+    ///     "DTSTART:20120201T093000Z\r\nRRULE:FREQ=DAILY;COUNT=3;BYHOUR=9;BYMINUTE=30;BYSECOND=0"
+    /// );
+    /// ```
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let start_datetime = format!("DTSTART{}", datetime_to_ical_format(&self.dt_start));
+        let mut lines = vec![format!(
+            "DTSTART{}",
+            datetime_to_ical_format(&self.dt_start)
+        )];
 
-        let mut rrules = self
-            .rrule
-            .iter()
-            .map(|rrule| format!("RRULE:{rrule}"))
-            .collect::<Vec<_>>()
-            .join("\n");
-        if !rrules.is_empty() {
-            rrules = format!("\n{rrules}");
+        if let Some(duration) = self.duration {
+            lines.push(format!("DURATION:{}", duration_to_ical_format(duration)));
         }
 
-        let mut rdates = self
+        lines.extend(self.rrule.iter().map(|rrule| format!("RRULE:{rrule}")));
+
+        let rdates = self
             .rdate
             .iter()
             .map(|dt| {
@@ -308,20 +1227,12 @@ impl Display for RRuleSet {
             .join(",");
         if !rdates.is_empty() {
             // TODO: check if original VALUE prop was DATE or PERIOD
-            rdates = format!("\nRDATE;VALUE=DATE-TIME:{rdates}");
+            lines.push(format!("RDATE;VALUE=DATE-TIME:{rdates}"));
         }
 
-        let mut exrules = self
-            .exrule
-            .iter()
-            .map(|exrule| format!("EXRULE:{exrule}"))
-            .collect::<Vec<_>>()
-            .join("\n");
-        if !exrules.is_empty() {
-            exrules = format!("\n{exrules}");
-        }
+        lines.extend(self.exrule.iter().map(|exrule| format!("EXRULE:{exrule}")));
 
-        let mut exdates = self
+        let exdates = self
             .exdate
             .iter()
             .map(|dt| {
@@ -333,10 +1244,18 @@ impl Display for RRuleSet {
             .join(",");
         if !exdates.is_empty() {
             // TODO: check if original VALUE prop was DATE or PERIOD
-            exdates = format!("\nEXDATE;VALUE=DATE-TIME:{exdates}");
+            lines.push(format!("EXDATE;VALUE=DATE-TIME:{exdates}"));
         }
 
-        write!(f, "{start_datetime}{rrules}{rdates}{exrules}{exdates}")
+        if f.alternate() {
+            let folded = lines
+                .iter()
+                .map(|line| fold_content_line(line))
+                .collect::<Vec<_>>();
+            write!(f, "{}", folded.join("\r\n"))
+        } else {
+            write!(f, "{}", lines.join("\n"))
+        }
     }
 }
 
@@ -396,6 +1315,24 @@ mod tests {
         assert_eq!(rruleset.to_string(), rruleset_str);
     }
 
+    #[test]
+    fn rruleset_folded_string_roundtrip() {
+        let exdates = (1..=9)
+            .map(|day| format!("2012020{day}T093000Z"))
+            .collect::<Vec<_>>()
+            .join(",");
+        let rruleset_str = format!("DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=30;BYHOUR=9;BYMINUTE=30;BYSECOND=0\nEXDATE;VALUE=DATE-TIME:{exdates}");
+        let rruleset = RRuleSet::from_str(&rruleset_str).unwrap();
+
+        let folded = format!("{rruleset:#}");
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+        assert!(folded.contains("\r\n "));
+
+        // Parsing the folded output gives back the same set
+        assert_eq!(RRuleSet::from_str(&folded).unwrap(), rruleset);
+        assert_eq!(rruleset.to_string(), rruleset_str);
+    }
+
     #[test]
     fn respect_local_timezone_in_exdates_rdates() {
         let rruleset_str = "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=3;BYHOUR=9;BYMINUTE=30;BYSECOND=0\nRDATE;VALUE=DATE-TIME:19970101T000000,19970120T000000\nEXRULE:FREQ=YEARLY;COUNT=8;BYMONTH=6,7;BYMONTHDAY=1;BYHOUR=9;BYMINUTE=30;BYSECOND=0\nEXDATE;VALUE=DATE-TIME:19970121T000000";
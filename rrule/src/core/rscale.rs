@@ -0,0 +1,427 @@
+//! Calendar systems for the `RSCALE` and `SKIP` parts of an `RRULE`, as defined by
+//! [RFC 7529](https://datatracker.ietf.org/doc/html/rfc7529).
+
+use crate::parser::ParseError;
+use chrono::{Datelike, NaiveDate};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The calendar system of an [`RRule`](crate::RRule), set by the `RSCALE` part.
+///
+/// With a non-Gregorian calendar, yearly and monthly periods are the years and months of that
+/// calendar, and `BYMONTH`, `BYMONTHDAY` and `BYYEARDAY` refer to its months and days.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::RRuleSet;
+///
+/// // Every 1 Nisan
+/// let rrule_set: RRuleSet = "DTSTART:20230323T090000Z\n\
+///     RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=7;BYMONTHDAY=1;COUNT=2"
+///     .parse()
+///     .unwrap();
+/// let dates = rrule_set.all(10).dates;
+/// assert_eq!(dates[0].to_rfc3339(), "2023-03-23T09:00:00+00:00");
+/// assert_eq!(dates[1].to_rfc3339(), "2024-04-09T09:00:00+00:00");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RScale {
+    /// The Gregorian calendar, which is also used without an `RSCALE`.
+    Gregorian,
+    /// The Hebrew calendar, with the leap month `5L` (Adar I) in 7 of every 19 years.
+    /// Months are numbered from Tishri (`1`) to Elul (`12`), Nisan being `7`.
+    Hebrew,
+    /// The tabular (civil) Islamic calendar, with 12 months of 29 or 30 days.
+    IslamicCivil,
+}
+
+impl Display for RScale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Gregorian => "GREGORIAN",
+            Self::Hebrew => "HEBREW",
+            Self::IslamicCivil => "ISLAMIC-CIVIL",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for RScale {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_uppercase()[..] {
+            "GREGORIAN" => Ok(Self::Gregorian),
+            "HEBREW" => Ok(Self::Hebrew),
+            "ISLAMIC-CIVIL" => Ok(Self::IslamicCivil),
+            _ => Err(ParseError::InvalidRScale(s.into())),
+        }
+    }
+}
+
+/// How a date which does not exist in a year or month is handled, set by the `SKIP` part.
+///
+/// For example the 30th day of a month with 29 days, the 29th of February in a year which is
+/// not a leap year, or the leap month `5L` of the Hebrew calendar in a year without leap month.
+///
+/// RFC 7529 only allows `SKIP` together with `RSCALE`, but it is accepted for the default
+/// Gregorian calendar as well.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::RRuleSet;
+///
+/// // The last day of every month
+/// let rrule_set: RRuleSet = "DTSTART:20240131T090000Z\n\
+///     RRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=BACKWARD;COUNT=3"
+///     .parse()
+///     .unwrap();
+/// let dates = rrule_set.all(10).dates;
+/// assert_eq!(dates[0].to_rfc3339(), "2024-01-31T09:00:00+00:00");
+/// assert_eq!(dates[1].to_rfc3339(), "2024-02-29T09:00:00+00:00");
+/// assert_eq!(dates[2].to_rfc3339(), "2024-03-31T09:00:00+00:00");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Skip {
+    /// The date is skipped.
+    #[default]
+    Omit,
+    /// The date moves to the previous valid day or month, e.g. the last day of the month.
+    Backward,
+    /// The date moves to the next valid day or month, e.g. the first day of the next month.
+    Forward,
+}
+
+impl Display for Skip {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Omit => "OMIT",
+            Self::Backward => "BACKWARD",
+            Self::Forward => "FORWARD",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Skip {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_uppercase()[..] {
+            "OMIT" => Ok(Self::Omit),
+            "BACKWARD" => Ok(Self::Backward),
+            "FORWARD" => Ok(Self::Forward),
+            _ => Err(ParseError::InvalidSkip(s.into())),
+        }
+    }
+}
+
+/// A month of a calendar year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CalendarMonth {
+    /// Month number as used by `BYMONTH`, the leap month `5L` has number `5`.
+    pub number: u8,
+    pub leap: bool,
+    pub len: u16,
+}
+
+/// A date in a calendar, with the month as index in the months of the year (starting at `1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CalendarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// Day number of `1970-01-01` counted from `0001-01-01` (day `1`) in the proleptic Gregorian
+/// calendar, the "fixed date" used by Calendrical Calculations.
+const UNIX_EPOCH_FIXED: i64 = 719_163;
+/// Fixed date of 1 Tishri of year 1 in the Hebrew calendar.
+const HEBREW_EPOCH_FIXED: i64 = -1_373_427;
+/// Fixed date of 1 Muharram of year 1 in the Islamic calendar.
+const ISLAMIC_EPOCH_FIXED: i64 = 227_015;
+
+impl RScale {
+    /// Returns the months of the year in order.
+    pub(crate) fn months(self, year: i32) -> Vec<CalendarMonth> {
+        let month = |number, leap, len| CalendarMonth { number, leap, len };
+        match self {
+            Self::Gregorian => (1..=12)
+                .map(|number| {
+                    let next = NaiveDate::from_ymd_opt(
+                        if number == 12 { year + 1 } else { year },
+                        u32::from(number % 12 + 1),
+                        1,
+                    )
+                    .expect("first day of a month is a valid date");
+                    let len = next.pred_opt().expect("date has a previous day").day();
+                    month(
+                        number,
+                        false,
+                        u16::try_from(len).expect("len is at most 31"),
+                    )
+                })
+                .collect(),
+            Self::Hebrew => {
+                let year_len = self.year_start(year + 1) - self.year_start(year);
+                let mut months = vec![
+                    month(1, false, 30),
+                    // Heshvan has 30 days and Kislev 29 days in complete and deficient years
+                    month(2, false, if year_len % 10 == 5 { 30 } else { 29 }),
+                    month(3, false, if year_len % 10 == 3 { 29 } else { 30 }),
+                    month(4, false, 29),
+                    month(5, false, 30),
+                ];
+                if is_hebrew_leap_year(year) {
+                    months.push(month(5, true, 30));
+                }
+                months.extend(
+                    (6..=12).map(|number| month(number, false, 30 - u16::from(number % 2 == 0))),
+                );
+                months
+            }
+            Self::IslamicCivil => (1..=12)
+                .map(|number| {
+                    let len = if number % 2 == 1 || (number == 12 && is_islamic_leap_year(year)) {
+                        30
+                    } else {
+                        29
+                    };
+                    month(number, false, len)
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the number of days since the Unix epoch of the first day of the year.
+    pub(crate) fn year_start(self, year: i32) -> i64 {
+        let year = i64::from(year);
+        let fixed = match self {
+            Self::Gregorian => {
+                let days =
+                    NaiveDate::from_ymd_opt(i32::try_from(year).expect("year is an i32"), 1, 1)
+                        .map(|date| date.num_days_from_ce())
+                        .expect("year is within the range of supported dates");
+                i64::from(days)
+            }
+            Self::Hebrew => {
+                HEBREW_EPOCH_FIXED + hebrew_elapsed_days(year) + hebrew_year_length_correction(year)
+            }
+            Self::IslamicCivil => {
+                (year - 1) * 354 + (3 + 11 * year).div_euclid(30) + ISLAMIC_EPOCH_FIXED
+            }
+        };
+        fixed - UNIX_EPOCH_FIXED
+    }
+
+    /// Returns the number of months before the first month of the year.
+    pub(crate) fn months_before_year(self, year: i32) -> i64 {
+        let year = i64::from(year);
+        match self {
+            Self::Gregorian | Self::IslamicCivil => (year - 1) * 12,
+            Self::Hebrew => (235 * year - 234).div_euclid(19),
+        }
+    }
+
+    /// Returns the year and the index of the month (starting at `1`) of a month counted by
+    /// [`RScale::months_before_year`].
+    pub(crate) fn year_and_month(self, months: i64) -> Option<(i32, u32)> {
+        let mut year = match self {
+            Self::Gregorian | Self::IslamicCivil => months.div_euclid(12) + 1,
+            Self::Hebrew => (19 * months).div_euclid(235) + 1,
+        };
+        let mut year_i32 = i32::try_from(year).ok()?;
+        while self.months_before_year(year_i32) > months {
+            year -= 1;
+            year_i32 = i32::try_from(year).ok()?;
+        }
+        while self.months_before_year(year_i32.checked_add(1)?) <= months {
+            year += 1;
+            year_i32 = i32::try_from(year).ok()?;
+        }
+        let month = u32::try_from(months - self.months_before_year(year_i32) + 1).ok()?;
+        Some((year_i32, month))
+    }
+
+    /// Returns the date in this calendar of a Gregorian date.
+    pub(crate) fn calendar_date(self, date: NaiveDate) -> CalendarDate {
+        let days = i64::from(date.num_days_from_ce()) - UNIX_EPOCH_FIXED;
+        let mut year = match self {
+            Self::Gregorian => date.year(),
+            Self::Hebrew => {
+                let fixed = days + UNIX_EPOCH_FIXED;
+                let approx = (fixed - HEBREW_EPOCH_FIXED) * 98_496 / 35_975_351 + 1;
+                i32::try_from(approx).expect("date is within the range of i32 years")
+            }
+            Self::IslamicCivil => {
+                let fixed = days + UNIX_EPOCH_FIXED;
+                let year = (30 * (fixed - ISLAMIC_EPOCH_FIXED) + 10_646).div_euclid(10_631);
+                i32::try_from(year).expect("date is within the range of i32 years")
+            }
+        };
+        while self.year_start(year) > days {
+            year -= 1;
+        }
+        while self.year_start(year + 1) <= days {
+            year += 1;
+        }
+
+        let mut day = days - self.year_start(year);
+        let mut month = 1;
+        for calendar_month in self.months(year) {
+            if day < i64::from(calendar_month.len) {
+                break;
+            }
+            day -= i64::from(calendar_month.len);
+            month += 1;
+        }
+        CalendarDate {
+            year,
+            month,
+            day: u32::try_from(day + 1).expect("day is within the month"),
+        }
+    }
+
+    /// Returns the month number and if it's a leap month, of the month of a Gregorian date.
+    pub(crate) fn month_code(self, date: NaiveDate) -> (u8, bool) {
+        let date = self.calendar_date(date);
+        let month = self.months(date.year)[date.month as usize - 1];
+        (month.number, month.leap)
+    }
+}
+
+fn is_hebrew_leap_year(year: i32) -> bool {
+    (7 * i64::from(year) + 1).rem_euclid(19) < 7
+}
+
+/// Number of days from the epoch to the molad of Tishri of the year, with the postponement
+/// when the molad falls on a Sunday, Wednesday or Friday.
+fn hebrew_elapsed_days(year: i64) -> i64 {
+    let months_elapsed = (235 * year - 234).div_euclid(19);
+    let parts_elapsed = 12_084 + 13_753 * months_elapsed;
+    let days = 29 * months_elapsed + parts_elapsed.div_euclid(25_920);
+    if (3 * (days + 1)).rem_euclid(7) < 3 {
+        days + 1
+    } else {
+        days
+    }
+}
+
+/// Postponement of the new year to prevent years of 356 or 382 days.
+fn hebrew_year_length_correction(year: i64) -> i64 {
+    let previous = hebrew_elapsed_days(year - 1);
+    let current = hebrew_elapsed_days(year);
+    let next = hebrew_elapsed_days(year + 1);
+    if next - current == 356 {
+        2
+    } else if current - previous == 382 {
+        1
+    } else {
+        0
+    }
+}
+
+fn is_islamic_leap_year(year: i32) -> bool {
+    (14 + 11 * i64::from(year)).rem_euclid(30) < 11
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn converts_gregorian_dates() {
+        let tests = [
+            // 1 Tishri 5784 (Rosh Hashanah)
+            (RScale::Hebrew, date(2023, 9, 16), (5784, 1, 1), (1, false)),
+            // 15 Nisan 5784 (Passover) in a leap year
+            (RScale::Hebrew, date(2024, 4, 23), (5784, 8, 15), (7, false)),
+            // 14 Adar I 5784
+            (RScale::Hebrew, date(2024, 2, 23), (5784, 6, 14), (5, true)),
+            // 29 Elul 5783
+            (
+                RScale::Hebrew,
+                date(2023, 9, 15),
+                (5783, 12, 29),
+                (12, false),
+            ),
+            // 1 Muharram 1445
+            (
+                RScale::IslamicCivil,
+                date(2023, 7, 19),
+                (1445, 1, 1),
+                (1, false),
+            ),
+            // 1 Ramadan 1445
+            (
+                RScale::IslamicCivil,
+                date(2024, 3, 11),
+                (1445, 9, 1),
+                (9, false),
+            ),
+            (
+                RScale::Gregorian,
+                date(2024, 2, 29),
+                (2024, 2, 29),
+                (2, false),
+            ),
+        ];
+        for (rscale, gregorian, (year, month, day), code) in tests {
+            let calendar_date = rscale.calendar_date(gregorian);
+            assert_eq!(
+                calendar_date,
+                CalendarDate { year, month, day },
+                "{rscale} {gregorian}"
+            );
+            assert_eq!(rscale.month_code(gregorian), code);
+        }
+    }
+
+    #[test]
+    fn computes_year_lengths() {
+        let year_len = |rscale: RScale, year| {
+            let months = rscale.months(year);
+            let len: i64 = months.iter().map(|month| i64::from(month.len)).sum();
+            assert_eq!(len, rscale.year_start(year + 1) - rscale.year_start(year));
+            (months.len(), len)
+        };
+        assert_eq!(year_len(RScale::Hebrew, 5783), (12, 355));
+        assert_eq!(year_len(RScale::Hebrew, 5784), (13, 383));
+        assert_eq!(year_len(RScale::Hebrew, 5785), (12, 355));
+        assert_eq!(year_len(RScale::IslamicCivil, 1446), (12, 354));
+        assert_eq!(year_len(RScale::IslamicCivil, 1447), (12, 355));
+        assert_eq!(year_len(RScale::Gregorian, 2024), (12, 366));
+
+        for year in 5700..5800 {
+            let (months, len) = year_len(RScale::Hebrew, year);
+            assert!([353, 354, 355, 383, 384, 385].contains(&len), "{year}");
+            assert_eq!(months == 13, len > 380);
+        }
+    }
+
+    #[test]
+    fn counts_months() {
+        for rscale in [RScale::Gregorian, RScale::Hebrew, RScale::IslamicCivil] {
+            for year in [-5, 1, 1445, 2024, 5784] {
+                let months = rscale.months_before_year(year);
+                assert_eq!(rscale.year_and_month(months), Some((year, 1)));
+                let len = i64::try_from(rscale.months(year).len()).unwrap();
+                assert_eq!(
+                    rscale.year_and_month(months + len - 1),
+                    Some((year, u32::try_from(len).unwrap()))
+                );
+                assert_eq!(
+                    rscale.months_before_year(year + 1),
+                    months + len,
+                    "{rscale} {year}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Conversions between the `time` crate and [`chrono::DateTime`], which allow
+//! [`RRuleSet`](crate::RRuleSet) and [`RRule`](crate::RRule) to take `time` values.
+
+use super::convert::{FromDateTime, IntoDateTime};
+use crate::Tz;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Offset, TimeZone, Timelike, Utc};
+use time::{Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+impl IntoDateTime<Tz> for OffsetDateTime {
+    /// Converts into a [`Tz::UTC`] datetime if the offset is zero, and into a [`Tz::Fixed`]
+    /// datetime with the same offset otherwise.
+    ///
+    /// Dates outside of the range supported by `chrono` are saturated.
+    fn into_datetime(self) -> DateTime<Tz> {
+        let seconds = self.offset().whole_seconds();
+        let tz = if seconds == 0 {
+            Tz::UTC
+        } else {
+            chrono::FixedOffset::east_opt(seconds).map_or(Tz::UTC, Tz::Fixed)
+        };
+        let nanos = self.unix_timestamp_nanos();
+        let utc = i64::try_from(nanos.div_euclid(1_000_000_000))
+            .ok()
+            .and_then(|seconds| {
+                let nanosecond = u32::try_from(nanos.rem_euclid(1_000_000_000)).ok()?;
+                DateTime::from_timestamp(seconds, nanosecond)
+            })
+            .unwrap_or(if nanos < 0 {
+                DateTime::<Utc>::MIN_UTC
+            } else {
+                DateTime::<Utc>::MAX_UTC
+            });
+        utc.with_timezone(&tz)
+    }
+}
+
+impl IntoDateTime<Tz> for PrimitiveDateTime {
+    /// Converts into a [`Tz::LOCAL`] datetime, like a floating `DTSTART` without a `TZID`.
+    ///
+    /// An ambiguous local time resolves to the earlier of the two, and a local time which
+    /// does not exist is shifted by the offset in use around it.
+    /// Dates outside of the range supported by `chrono` are saturated.
+    fn into_datetime(self) -> DateTime<Tz> {
+        let naive = NaiveDate::from_ymd_opt(self.year(), u32::from(u8::from(self.month())), 1)
+            .and_then(|date| date.with_day(u32::from(self.day())))
+            .and_then(|date| {
+                date.and_hms_nano_opt(
+                    u32::from(self.hour()),
+                    u32::from(self.minute()),
+                    u32::from(self.second()),
+                    self.nanosecond(),
+                )
+            })
+            .unwrap_or(if self.year() < 0 {
+                NaiveDateTime::MIN
+            } else {
+                NaiveDateTime::MAX
+            });
+
+        Tz::LOCAL
+            .from_local_datetime(&naive)
+            .earliest()
+            .unwrap_or_else(|| {
+                let offset = Tz::LOCAL.offset_from_utc_datetime(&naive).fix();
+                Tz::LOCAL.from_utc_datetime(&(naive - offset))
+            })
+    }
+}
+
+impl<TZ: TimeZone> FromDateTime<TZ> for OffsetDateTime {
+    /// Converts into an [`OffsetDateTime`] with the offset of the datetime.
+    fn from_datetime(dt: DateTime<TZ>) -> Option<Self> {
+        let offset = UtcOffset::from_whole_seconds(dt.offset().fix().local_minus_utc()).ok()?;
+        let nanos =
+            i128::from(dt.timestamp()) * 1_000_000_000 + i128::from(dt.timestamp_subsec_nanos());
+        Self::from_unix_timestamp_nanos(nanos)
+            .ok()?
+            .checked_to_offset(offset)
+    }
+}
+
+impl<TZ: TimeZone> FromDateTime<TZ> for PrimitiveDateTime {
+    /// Converts into a [`PrimitiveDateTime`] with the local date and time of the datetime.
+    fn from_datetime(dt: DateTime<TZ>) -> Option<Self> {
+        let local = dt.naive_local();
+        let month = Month::try_from(u8::try_from(local.month()).ok()?).ok()?;
+        let date =
+            time::Date::from_calendar_date(local.year(), month, u8::try_from(local.day()).ok()?)
+                .ok()?;
+        date.with_hms_nano(
+            u8::try_from(local.hour()).ok()?,
+            u8::try_from(local.minute()).ok()?,
+            u8::try_from(local.second()).ok()?,
+            local.nanosecond(),
+        )
+        .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frequency, RRule, RRuleSet};
+    use time::macros::{datetime, offset};
+
+    #[test]
+    fn converts_offset_date_time() {
+        let tests = [
+            (datetime!(2023-03-20 09:00 UTC), "2023-03-20T09:00:00+00:00"),
+            (
+                datetime!(2023-03-20 09:00:00.5 +05:30),
+                "2023-03-20T09:00:00.500+05:30",
+            ),
+            (
+                datetime!(1969-12-31 23:59:59.25 -03:00),
+                "1969-12-31T23:59:59.250-03:00",
+            ),
+        ];
+        for (input, expected) in tests {
+            let dt: DateTime<Tz> = input.into_datetime();
+            assert_eq!(dt.to_rfc3339(), expected);
+            assert_eq!(OffsetDateTime::from_datetime(dt), Some(input));
+        }
+
+        let dt: DateTime<Tz> = datetime!(2023-03-20 09:00 UTC).into_datetime();
+        assert_eq!(dt.timezone(), Tz::UTC);
+        let dt: DateTime<Tz> = datetime!(2023-03-20 09:00 -08:00).into_datetime();
+        assert_eq!(
+            dt.timezone(),
+            Tz::Fixed(chrono::FixedOffset::west_opt(8 * 3600).unwrap())
+        );
+    }
+
+    #[test]
+    fn converts_primitive_date_time() {
+        let dt: DateTime<Tz> = datetime!(2023-03-20 09:00:00.5).into_datetime();
+        assert!(dt.timezone().is_local());
+        assert_eq!(dt.naive_local().to_string(), "2023-03-20 09:00:00.500");
+        assert_eq!(
+            PrimitiveDateTime::from_datetime(dt),
+            Some(datetime!(2023-03-20 09:00:00.5))
+        );
+    }
+
+    #[test]
+    fn rrule_set_with_time_values() {
+        let dt_start = datetime!(2023-03-20 09:00 +01:00);
+        let rrule = RRule::new(Frequency::Weekly)
+            .until(datetime!(2023-04-03 08:00 UTC))
+            .validate(dt_start)
+            .unwrap();
+        let rrule_set = RRuleSet::new(dt_start)
+            .rrule(rrule)
+            .exdate(datetime!(2023-03-27 08:00 UTC))
+            .rdate(datetime!(2023-03-28 12:00 +01:00));
+
+        let dates: Vec<OffsetDateTime> = rrule_set.into_iter().convert().collect();
+        assert_eq!(
+            dates,
+            [
+                datetime!(2023-03-20 09:00 +01:00),
+                datetime!(2023-03-28 12:00 +01:00),
+                datetime!(2023-04-03 09:00 +01:00),
+            ]
+        );
+        assert_eq!(dates[0].offset(), offset!(+01:00));
+    }
+}
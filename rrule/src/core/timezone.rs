@@ -1,4 +1,6 @@
-use chrono::Local;
+use chrono::{FixedOffset, Local};
+
+use super::vtimezone::VTimezone;
 
 /// A wrapper around `chrono_tz::Tz` that is able to represent `Local` timezone also.
 ///
@@ -12,6 +14,8 @@ use chrono::Local;
 /// let berlin = Tz::Europe__Berlin;
 /// // From `chrono_tz::Tz`
 /// let berlin: Tz = chrono_tz::Tz::Europe__Berlin.into();
+/// // From `chrono::FixedOffset`
+/// let india: Tz = chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap().into();
 /// ```
 #[derive(Clone, Copy)]
 pub enum Tz {
@@ -19,15 +23,23 @@ pub enum Tz {
     Local(Local),
     /// Timezone represented by `chrono_tz::Tz`
     Tz(chrono_tz::Tz),
+    /// Timezone defined by a `VTIMEZONE` component
+    VTimezone(&'static VTimezone),
+    /// Timezone with a fixed offset from UTC
+    Fixed(FixedOffset),
 }
 
 impl Tz {
     /// Name of timezone
+    ///
+    /// This is `"Fixed"` for a [`Tz::Fixed`], use the `Display` implementation to get its offset.
     #[must_use]
     pub fn name(&self) -> &str {
         match self {
             Self::Local(_) => "Local",
             Self::Tz(tz) => tz.name(),
+            Self::VTimezone(tz) => tz.get_tzid(),
+            Self::Fixed(_) => "Fixed",
         }
     }
 
@@ -36,7 +48,7 @@ impl Tz {
     pub fn is_local(&self) -> bool {
         match self {
             Self::Local(_) => true,
-            Self::Tz(_) => false,
+            Self::Tz(_) | Self::VTimezone(_) | Self::Fixed(_) => false,
         }
     }
 
@@ -1,17 +1,21 @@
-use chrono::{FixedOffset, Local, Offset, TimeZone, Utc};
+use chrono::{FixedOffset, Local, NaiveTime, Offset, TimeZone, Utc};
 
-use super::Tz;
+use super::{vtimezone::VTimezone, Tz};
 
 impl PartialEq for Tz {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Local(_), Self::Local(_)) => true,
             (Self::Tz(l0), Self::Tz(r0)) => l0 == r0,
+            (Self::VTimezone(l0), Self::VTimezone(r0)) => std::ptr::eq(*l0, *r0) || l0 == r0,
+            (Self::Fixed(l0), Self::Fixed(r0)) => l0 == r0,
             _ => false,
         }
     }
 }
 
+impl Eq for Tz {}
+
 impl From<Local> for Tz {
     fn from(tz: Local) -> Self {
         Self::Local(tz)
@@ -30,11 +34,19 @@ impl From<chrono_tz::Tz> for Tz {
     }
 }
 
+impl From<FixedOffset> for Tz {
+    fn from(offset: FixedOffset) -> Self {
+        Self::Fixed(offset)
+    }
+}
+
 impl std::fmt::Debug for Tz {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Local(tz) => tz.fmt(f),
             Self::Tz(tz) => tz.fmt(f),
+            Self::VTimezone(tz) => tz.get_tzid().fmt(f),
+            Self::Fixed(offset) => offset.fmt(f),
         }
     }
 }
@@ -44,6 +56,8 @@ impl std::fmt::Display for Tz {
         match self {
             Self::Local(_tz) => write!(f, "Local"),
             Self::Tz(tz) => tz.fmt(f),
+            Self::VTimezone(tz) => write!(f, "{}", tz.get_tzid()),
+            Self::Fixed(offset) => offset.fmt(f),
         }
     }
 }
@@ -52,12 +66,14 @@ impl std::fmt::Display for Tz {
 pub enum RRuleOffset {
     Local(FixedOffset),
     Tz(<chrono_tz::Tz as TimeZone>::Offset),
+    VTimezone(&'static VTimezone, FixedOffset),
+    Fixed(FixedOffset),
 }
 
 impl std::fmt::Debug for RRuleOffset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Local(offset) => offset.fmt(f),
+            Self::Local(offset) | Self::VTimezone(_, offset) | Self::Fixed(offset) => offset.fmt(f),
             Self::Tz(offset) => offset.fmt(f),
         }
     }
@@ -66,7 +82,7 @@ impl std::fmt::Debug for RRuleOffset {
 impl std::fmt::Display for RRuleOffset {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Local(offset) => offset.fmt(f),
+            Self::Local(offset) | Self::VTimezone(_, offset) | Self::Fixed(offset) => offset.fmt(f),
             Self::Tz(offset) => offset.fmt(f),
         }
     }
@@ -75,7 +91,7 @@ impl std::fmt::Display for RRuleOffset {
 impl Offset for RRuleOffset {
     fn fix(&self) -> FixedOffset {
         match self {
-            Self::Local(tz) => tz.fix(),
+            Self::Local(tz) | Self::VTimezone(_, tz) | Self::Fixed(tz) => tz.fix(),
             Self::Tz(tz) => tz.fix(),
         }
     }
@@ -88,6 +104,8 @@ impl TimeZone for Tz {
         match offset {
             RRuleOffset::Local(offset) => Self::Local(Local::from_offset(offset)),
             RRuleOffset::Tz(offset) => Self::Tz(chrono_tz::Tz::from_offset(offset)),
+            RRuleOffset::VTimezone(tz, _) => Self::VTimezone(tz),
+            RRuleOffset::Fixed(offset) => Self::Fixed(*offset),
         }
     }
 
@@ -103,6 +121,10 @@ impl TimeZone for Tz {
             Self::Tz(tz) => tz
                 .from_local_date(local)
                 .map(|date| RRuleOffset::Tz(*date.offset())),
+            Self::VTimezone(tz) => tz
+                .offset_from_local(&local.and_time(NaiveTime::MIN))
+                .map(|offset| RRuleOffset::VTimezone(tz, offset)),
+            Self::Fixed(offset) => chrono::LocalResult::Single(RRuleOffset::Fixed(*offset)),
         }
     }
 
@@ -117,6 +139,10 @@ impl TimeZone for Tz {
             Self::Tz(tz) => tz
                 .from_local_datetime(local)
                 .map(|date| RRuleOffset::Tz(*date.offset())),
+            Self::VTimezone(tz) => tz
+                .offset_from_local(local)
+                .map(|offset| RRuleOffset::VTimezone(tz, offset)),
+            Self::Fixed(offset) => chrono::LocalResult::Single(RRuleOffset::Fixed(*offset)),
         }
     }
 
@@ -125,6 +151,10 @@ impl TimeZone for Tz {
         match self {
             Self::Local(tz) => RRuleOffset::Local(*tz.from_utc_date(utc).offset()),
             Self::Tz(tz) => RRuleOffset::Tz(*tz.from_utc_date(utc).offset()),
+            Self::VTimezone(tz) => {
+                RRuleOffset::VTimezone(tz, tz.offset_from_utc(&utc.and_time(NaiveTime::MIN)))
+            }
+            Self::Fixed(offset) => RRuleOffset::Fixed(*offset),
         }
     }
 
@@ -132,6 +162,8 @@ impl TimeZone for Tz {
         match self {
             Self::Local(tz) => RRuleOffset::Local(*tz.from_utc_datetime(utc).offset()),
             Self::Tz(tz) => RRuleOffset::Tz(*tz.from_utc_datetime(utc).offset()),
+            Self::VTimezone(tz) => RRuleOffset::VTimezone(tz, tz.offset_from_utc(utc)),
+            Self::Fixed(offset) => RRuleOffset::Fixed(*offset),
         }
     }
 }
@@ -1,5 +1,6 @@
+use crate::iter::rrule_iter::WasLimited;
 use crate::RRuleResult;
-use crate::{iter::rrule_iter::WasLimited, Tz};
+use chrono::TimeZone;
 use std::ops::{
     Bound::{Excluded, Unbounded},
     RangeBounds,
@@ -9,26 +10,27 @@ use std::ops::{
 ///
 /// In the case where the iterator ended with errors, the error will be included,
 /// otherwise the second value of the return tuple will be `None`.
-pub(super) fn collect_with_error<T>(
+pub(super) fn collect_with_error<T, TZ: TimeZone>(
     mut iterator: T,
-    start: &Option<chrono::DateTime<Tz>>,
-    end: &Option<chrono::DateTime<Tz>>,
+    start: &Option<chrono::DateTime<TZ>>,
+    end: &Option<chrono::DateTime<TZ>>,
     inclusive: bool,
-    limit: Option<u16>,
-) -> RRuleResult
+    limit: Option<usize>,
+) -> RRuleResult<TZ>
 where
-    T: Iterator<Item = chrono::DateTime<Tz>> + WasLimited,
+    T: Iterator<Item = chrono::DateTime<TZ>> + WasLimited,
 {
     let mut list = vec![];
     let mut was_limited = false;
     // This loop should always end because `.next()` has build in limits
     // Once a limit is tripped it will break in the `None` case.
-    while limit.is_none() || matches!(limit, Some(limit) if usize::from(limit) > list.len()) {
+    while limit.is_none() || matches!(limit, Some(limit) if limit > list.len()) {
         if let Some(value) = iterator.next() {
+            let reached_the_end = has_reached_the_end(&value, end, inclusive);
             if is_in_range(&value, start, end, inclusive) {
                 list.push(value);
             }
-            if has_reached_the_end(&value, end, inclusive) {
+            if reached_the_end {
                 // Date is after end date, so can stop iterating
                 break;
             }
@@ -38,7 +40,7 @@ where
         }
     }
 
-    was_limited = was_limited || matches!(limit, Some(limit) if usize::from(limit) == list.len());
+    was_limited = was_limited || matches!(limit, Some(limit) if limit == list.len());
 
     RRuleResult {
         dates: list,
@@ -47,9 +49,9 @@ where
 }
 
 /// Checks if `date` is after `end`.
-fn has_reached_the_end(
-    date: &chrono::DateTime<Tz>,
-    end: &Option<chrono::DateTime<Tz>>,
+fn has_reached_the_end<TZ: TimeZone>(
+    date: &chrono::DateTime<TZ>,
+    end: &Option<chrono::DateTime<TZ>>,
     inclusive: bool,
 ) -> bool {
     if inclusive {
@@ -66,10 +68,10 @@ fn has_reached_the_end(
 }
 
 /// Helper function to determine if a date is within a given range.
-pub(super) fn is_in_range(
-    date: &chrono::DateTime<Tz>,
-    start: &Option<chrono::DateTime<Tz>>,
-    end: &Option<chrono::DateTime<Tz>>,
+pub(super) fn is_in_range<TZ: TimeZone>(
+    date: &chrono::DateTime<TZ>,
+    start: &Option<chrono::DateTime<TZ>>,
+    end: &Option<chrono::DateTime<TZ>>,
     inclusive: bool,
 ) -> bool {
     // Should it include or not include the start and/or end date?
@@ -90,6 +92,31 @@ pub(super) fn is_in_range(
     }
 }
 
+/// The maximum length of a content line in octets, excluding the line break.
+const MAX_CONTENT_LINE_OCTETS: usize = 75;
+
+/// Folds a content line into multiple physical lines of at most 75 octets, as described in
+/// [RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545#section-3.1).
+///
+/// Every continuation line starts with a single space, and lines are never split in the
+/// middle of a multi-octet UTF-8 character.
+pub(super) fn fold_content_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_CONTENT_LINE_OCTETS * 3);
+    let mut octets = 0;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_CONTENT_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the length of the line
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+
+    folded
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::Tz;
@@ -340,4 +367,25 @@ mod tests {
             inclusive,
         ));
     }
+
+    #[test]
+    fn folds_long_content_lines() {
+        assert_eq!(fold_content_line("RRULE:FREQ=DAILY"), "RRULE:FREQ=DAILY");
+
+        let line = format!("EXDATE:{}", "20120202T130000Z,".repeat(10));
+        let folded = fold_content_line(&line);
+        let physical_lines = folded.split("\r\n").collect::<Vec<_>>();
+        assert_eq!(physical_lines.len(), 3);
+        assert!(physical_lines.iter().all(|l| l.len() <= 75));
+        assert_eq!(physical_lines[0].len(), 75);
+        assert!(physical_lines[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+
+        // Multi-octet characters are never split
+        let line = format!("X:{}", "é".repeat(40));
+        let folded = fold_content_line(&line);
+        let physical_lines = folded.split("\r\n").collect::<Vec<_>>();
+        assert_eq!(physical_lines[0].len(), 74);
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
 }
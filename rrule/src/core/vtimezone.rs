@@ -0,0 +1,521 @@
+use crate::parser::component::Component;
+use crate::parser::{
+    datestring_to_date, parse_utc_offset, ContentLineCaptures, ParseError, PropertyName,
+};
+use crate::{RRule, RRuleError, RRuleSet, Tz, Unvalidated};
+use chrono::{Datelike, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// The type of a `VTIMEZONE` observance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObservanceKind {
+    /// A `STANDARD` observance.
+    Standard,
+    /// A `DAYLIGHT` observance.
+    Daylight,
+}
+
+/// A `STANDARD` or `DAYLIGHT` sub-component of a `VTIMEZONE`.
+///
+/// The onsets of an observance are generated with an [`RRuleSet`] over the local times
+/// found in its `DTSTART`, `RRULE` and `RDATE` properties.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Observance {
+    pub(crate) kind: ObservanceKind,
+    /// The `TZNAME` of the observance, e.g. `CEST`.
+    pub(crate) name: Option<String>,
+    /// The UTC offset in use before the onset of this observance.
+    pub(crate) offset_from: FixedOffset,
+    /// The UTC offset in use from the onset of this observance.
+    pub(crate) offset_to: FixedOffset,
+    /// The onsets of the observance, as local times (in `offset_from`) expressed in UTC.
+    pub(crate) onsets: RRuleSet,
+}
+
+impl Observance {
+    /// Returns whether it is a `STANDARD` or `DAYLIGHT` observance.
+    #[must_use]
+    pub fn get_kind(&self) -> ObservanceKind {
+        self.kind
+    }
+
+    /// Returns the `TZNAME` of the observance.
+    #[must_use]
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the UTC offset in use before the onset of this observance.
+    #[must_use]
+    pub fn get_offset_from(&self) -> FixedOffset {
+        self.offset_from
+    }
+
+    /// Returns the UTC offset in use from the onset of this observance.
+    #[must_use]
+    pub fn get_offset_to(&self) -> FixedOffset {
+        self.offset_to
+    }
+
+    /// Returns the local time of the UTC time `utc`, in the offset in use before the onsets.
+    fn to_local(&self, utc: NaiveDateTime) -> chrono::DateTime<Tz> {
+        let offset_from = Duration::seconds(i64::from(self.offset_from.local_minus_utc()));
+        utc.checked_add_signed(offset_from)
+            .unwrap_or(utc)
+            .and_utc()
+            .with_timezone(&Tz::UTC)
+    }
+
+    /// Returns the UTC time of an onset, which is a local time in the offset in use before it.
+    fn to_utc(&self, onset: &chrono::DateTime<Tz>) -> NaiveDateTime {
+        onset.naive_utc() - Duration::seconds(i64::from(self.offset_from.local_minus_utc()))
+    }
+
+    /// Returns the last onset of this observance before `start` and the onsets within
+    /// `start..end`, in UTC.
+    ///
+    /// The onsets are looked up from a year before `start`, which usually finds the last onset
+    /// as well, so the observance only needs to be walked back if it doesn't recur yearly.
+    fn onsets_around(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> (Option<NaiveDateTime>, Vec<NaiveDateTime>) {
+        let lookback = start
+            .checked_sub_signed(Duration::days(366))
+            .unwrap_or(start);
+        let mut last_before_start = None;
+        let mut onsets = vec![];
+        let around = self
+            .onsets
+            .iter_from(self.to_local(lookback))
+            .map(|onset| self.to_utc(&onset))
+            .take_while(|onset| *onset < end);
+        for onset in around {
+            if onset < start {
+                last_before_start = Some(onset);
+            } else {
+                onsets.push(onset);
+            }
+        }
+        if last_before_start.is_none() {
+            last_before_start = self
+                .onsets
+                .prev_before(self.to_local(lookback), false)
+                .map(|onset| self.to_utc(&onset));
+        }
+        (last_before_start, onsets)
+    }
+
+    fn from_component(component: &Component, kind: ObservanceKind) -> Result<Self, RRuleError> {
+        let get_property = |name: &str| {
+            component
+                .properties
+                .iter()
+                .find(|property| property.name.eq_ignore_ascii_case(name))
+        };
+        let get_offset = |name: &str| {
+            get_property(name)
+                .ok_or_else(|| ParseError::MissingProperty(name.into()))
+                .and_then(|property| parse_utc_offset(&property.value))
+        };
+
+        let offset_from = get_offset("TZOFFSETFROM")?;
+        let offset_to = get_offset("TZOFFSETTO")?;
+        let name = get_property("TZNAME").map(|property| property.value.clone());
+
+        // The local times of the observance are handled as if they were UTC times.
+        let dt_start = get_property("DTSTART")
+            .ok_or_else(|| ParseError::MissingProperty("DTSTART".into()))
+            .and_then(|property| datestring_to_date(&property.value, Some(Tz::UTC), "DTSTART"))?;
+        let mut onsets = RRuleSet::new(dt_start).limit();
+
+        for property in &component.properties {
+            if property.name.eq_ignore_ascii_case("RRULE") {
+                let mut rrule = RRule::<Unvalidated>::try_from(ContentLineCaptures {
+                    property_name: PropertyName::RRule,
+                    parameters: None,
+                    value: &property.value,
+                })?;
+                // `UNTIL` is specified in UTC, so it needs to be moved to local time as well.
+                rrule.until = rrule.until.map(|until| {
+                    let local = until.naive_utc()
+                        + Duration::seconds(i64::from(offset_from.local_minus_utc()));
+                    local.and_utc().with_timezone(&Tz::UTC)
+                });
+                onsets = onsets.rrule(rrule.validate(dt_start)?);
+            } else if property.name.eq_ignore_ascii_case("RDATE") {
+                for value in property.value.split(',').filter(|value| !value.is_empty()) {
+                    onsets = onsets.rdate(datestring_to_date(value, Some(Tz::UTC), "RDATE")?);
+                }
+            }
+        }
+        if onsets.get_rrule().is_empty() {
+            onsets = onsets.rdate(dt_start);
+        }
+
+        Ok(Self {
+            kind,
+            name,
+            offset_from,
+            offset_to,
+            onsets,
+        })
+    }
+}
+
+/// The offset changes of a [`VTimezone`] within a single year.
+struct YearTransitions {
+    /// The offset in use at the start of the year.
+    initial: FixedOffset,
+    /// The onsets in UTC within the year, sorted, with the offset used from then on.
+    changes: Vec<(NaiveDateTime, FixedOffset)>,
+}
+
+impl YearTransitions {
+    /// Returns the offset in use at the given UTC time, which must be within the year.
+    fn offset_at(&self, utc: &NaiveDateTime) -> FixedOffset {
+        self.changes
+            .iter()
+            .rev()
+            .find(|(onset, _)| onset <= utc)
+            .map_or(self.initial, |(_, offset)| *offset)
+    }
+}
+
+/// A timezone defined by a `VTIMEZONE` component, as commonly found in calendars exported by
+/// Outlook and Exchange, where the `TZID` is not an IANA name.
+///
+/// A [`VTimezone`] can be turned into a [`Tz`], which makes it usable as any other timezone.
+///
+/// # Usage
+///
+/// ```
+/// use chrono::TimeZone;
+/// use rrule::{Tz, VTimezone};
+///
+/// let vtimezone: VTimezone = "BEGIN:VTIMEZONE
+/// TZID:W. Europe Standard Time
+/// BEGIN:STANDARD
+/// DTSTART:16010101T030000
+/// TZOFFSETFROM:+0200
+/// TZOFFSETTO:+0100
+/// RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10
+/// END:STANDARD
+/// BEGIN:DAYLIGHT
+/// DTSTART:16010101T020000
+/// TZOFFSETFROM:+0100
+/// TZOFFSETTO:+0200
+/// RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3
+/// END:DAYLIGHT
+/// END:VTIMEZONE"
+///     .parse()
+///     .unwrap();
+///
+/// let tz = Tz::from(vtimezone);
+/// assert_eq!(tz.name(), "W. Europe Standard Time");
+/// let summer = tz.with_ymd_and_hms(2023, 7, 1, 12, 0, 0).unwrap();
+/// assert_eq!(summer.to_rfc3339(), "2023-07-01T12:00:00+02:00");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VTimezone {
+    pub(crate) tzid: String,
+    pub(crate) observances: Vec<Observance>,
+}
+
+impl VTimezone {
+    /// Returns the `TZID` of the timezone.
+    #[must_use]
+    pub fn get_tzid(&self) -> &str {
+        &self.tzid
+    }
+
+    /// Returns the `STANDARD` and `DAYLIGHT` observances of the timezone.
+    #[must_use]
+    pub fn get_observances(&self) -> &Vec<Observance> {
+        &self.observances
+    }
+
+    /// Returns a `'static` reference to an equal timezone, which is what allows [`Tz`] to stay
+    /// `Copy`.
+    ///
+    /// Every distinct timezone is allocated once and kept for the lifetime of the program, so
+    /// parsing the same `VTIMEZONE` again reuses it instead of allocating a new one.
+    pub(crate) fn intern(self) -> &'static Self {
+        static REGISTRY: OnceLock<Mutex<Vec<&'static VTimezone>>> = OnceLock::new();
+
+        let mut registry = REGISTRY
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(existing) = registry.iter().find(|existing| ***existing == self) {
+            return existing;
+        }
+        let interned: &'static Self = Box::leak(Box::new(self));
+        registry.push(interned);
+        interned
+    }
+
+    pub(crate) fn from_component(component: &Component) -> Result<Self, RRuleError> {
+        let tzid = component
+            .properties
+            .iter()
+            .find(|property| property.name.eq_ignore_ascii_case("TZID"))
+            .map(|property| property.value.clone())
+            .ok_or_else(|| ParseError::MissingProperty("TZID".into()))?;
+
+        let mut observances = vec![];
+        for sub_component in &component.components {
+            let kind = match sub_component.name.as_str() {
+                "STANDARD" => ObservanceKind::Standard,
+                "DAYLIGHT" => ObservanceKind::Daylight,
+                _ => continue,
+            };
+            observances.push(Observance::from_component(sub_component, kind)?);
+        }
+        if observances.is_empty() {
+            return Err(ParseError::MissingComponent("STANDARD".into()).into());
+        }
+
+        Ok(Self { tzid, observances })
+    }
+
+    /// Computes the offset changes within `year`. The onsets are looked up around the year,
+    /// without iterating over the observances from their `DTSTART`.
+    fn transitions(&self, year: i32) -> YearTransitions {
+        let year_bound = |year| {
+            NaiveDate::from_ymd_opt(year, 1, 1)
+                .unwrap_or(NaiveDate::MAX)
+                .and_time(chrono::NaiveTime::MIN)
+        };
+        let (start, end) = (year_bound(year), year_bound(year + 1));
+
+        let mut last_before_start: Option<(NaiveDateTime, &Observance)> = None;
+        let mut changes = vec![];
+        for observance in &self.observances {
+            let (last_onset, onsets) = observance.onsets_around(start, end);
+            if let Some(onset) = last_onset {
+                if last_before_start.map_or(true, |(last, _)| last < onset) {
+                    last_before_start = Some((onset, observance));
+                }
+            }
+            changes.extend(onsets.into_iter().map(|onset| (onset, observance)));
+        }
+        changes.sort_by_key(|(onset, _)| *onset);
+
+        let initial = match (last_before_start, changes.first()) {
+            (Some((_, observance)), _) => observance.offset_to,
+            (None, Some((_, observance))) => observance.offset_from,
+            (None, None) => self.observances[0].offset_from,
+        };
+
+        YearTransitions {
+            initial,
+            changes: changes
+                .into_iter()
+                .map(|(onset, observance)| (onset, observance.offset_to))
+                .collect(),
+        }
+    }
+
+    /// Returns the offset in use at the given UTC time.
+    pub(crate) fn offset_from_utc(&self, utc: &NaiveDateTime) -> FixedOffset {
+        self.transitions(utc.year()).offset_at(utc)
+    }
+
+    /// Returns the possible offsets of the given local time.
+    pub(crate) fn offset_from_local(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        let mut candidates = self
+            .observances
+            .iter()
+            .flat_map(|observance| [observance.offset_from, observance.offset_to])
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|offset| -offset.local_minus_utc());
+        candidates.dedup();
+
+        // An offset is valid if the UTC time it leads to actually uses that offset.
+        // The UTC times are in the same year as the local time, except around new year.
+        let transitions = self.transitions(local.year());
+        let valid = candidates
+            .into_iter()
+            .filter(|offset| {
+                let utc = *local - Duration::seconds(i64::from(offset.local_minus_utc()));
+                let offset_at_utc = if utc.year() == local.year() {
+                    transitions.offset_at(&utc)
+                } else {
+                    self.offset_from_utc(&utc)
+                };
+                offset_at_utc == *offset
+            })
+            .collect::<Vec<_>>();
+
+        match valid[..] {
+            [] => LocalResult::None,
+            [offset] => LocalResult::Single(offset),
+            [earliest, .., latest] => LocalResult::Ambiguous(earliest, latest),
+        }
+    }
+}
+
+impl FromStr for VTimezone {
+    type Err = RRuleError;
+
+    /// Parses the first `VTIMEZONE` component found in the input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RRuleError`], if no valid `VTIMEZONE` component was found.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let root = Component::parse_root(s).map_err(|located| located.error)?;
+        let component = root
+            .find_components("VTIMEZONE")
+            .into_iter()
+            .next()
+            .ok_or_else(|| ParseError::MissingComponent("VTIMEZONE".into()))?;
+
+        Self::from_component(component)
+    }
+}
+
+impl From<VTimezone> for Tz {
+    fn from(tz: VTimezone) -> Self {
+        Self::VTimezone(tz.intern())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const W_EUROPE: &str = "BEGIN:VTIMEZONE
+TZID:W. Europe Standard Time
+BEGIN:STANDARD
+DTSTART:16010101T030000
+TZOFFSETFROM:+0200
+TZOFFSETTO:+0100
+TZNAME:CET
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:16010101T020000
+TZOFFSETFROM:+0100
+TZOFFSETTO:+0200
+TZNAME:CEST
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3
+END:DAYLIGHT
+END:VTIMEZONE";
+
+    #[test]
+    fn parses_vtimezone() {
+        let vtimezone = VTimezone::from_str(W_EUROPE).unwrap();
+        assert_eq!(vtimezone.get_tzid(), "W. Europe Standard Time");
+
+        let observances = vtimezone.get_observances();
+        assert_eq!(observances.len(), 2);
+        assert_eq!(observances[0].get_kind(), ObservanceKind::Standard);
+        assert_eq!(observances[0].get_name(), Some("CET"));
+        assert_eq!(
+            observances[1].get_offset_to(),
+            FixedOffset::east_opt(7200).unwrap()
+        );
+    }
+
+    #[test]
+    fn matches_iana_timezone_offsets() {
+        let tz = Tz::from(VTimezone::from_str(W_EUROPE).unwrap());
+        let berlin = Tz::Europe__Berlin;
+
+        // Hourly around the transitions of 2023
+        let mut utc = Tz::UTC.with_ymd_and_hms(2023, 3, 25, 0, 0, 0).unwrap();
+        while utc < Tz::UTC.with_ymd_and_hms(2023, 10, 30, 0, 0, 0).unwrap() {
+            assert_eq!(
+                utc.with_timezone(&tz).naive_local(),
+                utc.with_timezone(&berlin).naive_local(),
+                "{utc}"
+            );
+            utc += Duration::hours(1);
+        }
+    }
+
+    #[test]
+    fn handles_gaps_and_overlaps_in_local_time() {
+        let tz = Tz::from(VTimezone::from_str(W_EUROPE).unwrap());
+
+        // 2023-03-26 02:30 doesn't exist
+        assert_eq!(
+            tz.with_ymd_and_hms(2023, 3, 26, 2, 30, 0),
+            LocalResult::None
+        );
+        // 2023-10-29 02:30 happens twice
+        let overlap = tz.with_ymd_and_hms(2023, 10, 29, 2, 30, 0);
+        let (first, second) = (overlap.earliest().unwrap(), overlap.latest().unwrap());
+        assert_eq!(first.to_rfc3339(), "2023-10-29T02:30:00+02:00");
+        assert_eq!(second.to_rfc3339(), "2023-10-29T02:30:00+01:00");
+    }
+
+    #[test]
+    fn interns_equal_timezones() {
+        let tz1 = Tz::from(VTimezone::from_str(W_EUROPE).unwrap());
+        let tz2 = Tz::from(VTimezone::from_str(W_EUROPE).unwrap());
+        assert_eq!(tz1, tz2);
+        assert_ne!(tz1, Tz::Europe__Berlin);
+        let (Tz::VTimezone(vtz1), Tz::VTimezone(vtz2)) = (tz1, tz2) else {
+            unreachable!("Expected VTIMEZONE timezones");
+        };
+        assert!(std::ptr::eq(vtz1, vtz2));
+    }
+
+    #[test]
+    fn computes_offsets_of_distant_years() {
+        let vtimezone = VTimezone::from_str(W_EUROPE).unwrap();
+        let summer_time = FixedOffset::east_opt(7200).unwrap();
+        let winter_time = FixedOffset::east_opt(3600).unwrap();
+        for year in [1601, 1900, 2023, 2500, 9999] {
+            let utc = |month| {
+                NaiveDate::from_ymd_opt(year, month, 1)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap()
+            };
+            assert_eq!(vtimezone.offset_from_utc(&utc(7)), summer_time, "{year}");
+            assert_eq!(vtimezone.offset_from_utc(&utc(1)), winter_time, "{year}");
+        }
+    }
+
+    #[test]
+    fn supports_observance_without_rrule() {
+        let tz = Tz::from(
+            VTimezone::from_str(
+                "BEGIN:VTIMEZONE
+TZID:Custom
+BEGIN:STANDARD
+DTSTART:19700101T000000
+TZOFFSETFROM:+0530
+TZOFFSETTO:+0530
+END:STANDARD
+END:VTIMEZONE",
+            )
+            .unwrap(),
+        );
+        let dt = tz.with_ymd_and_hms(2023, 6, 1, 9, 0, 0).unwrap();
+        assert_eq!(dt.to_rfc3339(), "2023-06-01T09:00:00+05:30");
+    }
+
+    #[test]
+    fn rejects_invalid_vtimezones() {
+        assert_eq!(
+            VTimezone::from_str("BEGIN:VTIMEZONE\nBEGIN:STANDARD\nEND:STANDARD\nEND:VTIMEZONE"),
+            Err(ParseError::MissingProperty("TZID".into()).into())
+        );
+        assert_eq!(
+            VTimezone::from_str("BEGIN:VTIMEZONE\nTZID:Custom\nEND:VTIMEZONE"),
+            Err(ParseError::MissingComponent("STANDARD".into()).into())
+        );
+        assert_eq!(
+            VTimezone::from_str("BEGIN:VCALENDAR\nEND:VCALENDAR"),
+            Err(ParseError::MissingComponent("VTIMEZONE".into()).into())
+        );
+    }
+}
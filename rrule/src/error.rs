@@ -2,7 +2,10 @@
 
 use thiserror::Error;
 
-pub use crate::{parser::ParseError, validator::ValidationError};
+pub use crate::{
+    parser::{Diagnostic, Location, ParseError},
+    validator::ValidationError,
+};
 
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 /// The error type for the rrule crate.
@@ -24,3 +27,41 @@ impl RRuleError {
         Self::IterError(msg.as_ref().to_owned())
     }
 }
+
+/// An error of [`crate::RRule::validate_all`] or [`crate::RRuleSet::parse_lenient`], with the
+/// field it concerns, like a rule part (`BYMONTHDAY`, `UNTIL`) or a property (`EXDATE`).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{field}: {error}")]
+pub struct FieldError {
+    pub(crate) field: String,
+    pub(crate) error: RRuleError,
+    pub(crate) location: Option<Location>,
+}
+
+impl FieldError {
+    pub(crate) fn new(field: impl Into<String>, error: impl Into<RRuleError>) -> Self {
+        Self {
+            field: field.into(),
+            error: error.into(),
+            location: None,
+        }
+    }
+
+    /// Returns the name of the field, like `BYMONTHDAY` or `EXDATE`.
+    #[must_use]
+    pub fn get_field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the error of the field.
+    #[must_use]
+    pub fn get_error(&self) -> &RRuleError {
+        &self.error
+    }
+
+    /// Returns where the error was found in the input, if it is known.
+    #[must_use]
+    pub fn get_location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
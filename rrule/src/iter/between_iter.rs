@@ -0,0 +1,64 @@
+use chrono::{DateTime, TimeZone};
+
+use super::rrule_iter::WasLimited;
+use crate::{RRuleSetIter, Tz};
+
+#[derive(Debug, Clone)]
+/// Iterator over the recurrences of an [`crate::RRuleSet`] between two dates, see
+/// [`crate::RRuleSet::between`].
+pub struct BetweenIter<TZ: TimeZone = Tz> {
+    iter: RRuleSetIter<TZ>,
+    start: DateTime<TZ>,
+    end: DateTime<TZ>,
+    /// If recurrences at `start` and `end` are yielded.
+    inclusive: bool,
+    /// If a recurrence after `end` has been reached.
+    finished: bool,
+}
+
+impl<TZ: TimeZone> BetweenIter<TZ> {
+    pub(crate) fn new(
+        mut iter: RRuleSetIter<TZ>,
+        start: DateTime<TZ>,
+        end: DateTime<TZ>,
+        inclusive: bool,
+    ) -> Self {
+        iter.seek(start.clone());
+        Self {
+            iter,
+            start,
+            end,
+            inclusive,
+            finished: false,
+        }
+    }
+
+    /// Returns true if the iterator was stopped by the iterator limits before reaching
+    /// the end of the range, see [`crate::RRuleSet::limit`].
+    ///
+    /// This should be checked once the iterator returned `None`.
+    #[must_use]
+    pub fn was_limited(&self) -> bool {
+        self.iter.was_limited()
+    }
+}
+
+impl<TZ: TimeZone> Iterator for BetweenIter<TZ> {
+    type Item = DateTime<TZ>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        for date in self.iter.by_ref() {
+            if date > self.end || (!self.inclusive && date == self.end) {
+                break;
+            }
+            if self.inclusive || date != self.start {
+                return Some(date);
+            }
+        }
+        self.finished = true;
+        None
+    }
+}
@@ -1,4 +1,7 @@
+use super::utils::date_from_ordinal;
 use crate::validator::{ValidationError, YEAR_RANGE};
+use crate::RScale;
+use chrono::Datelike;
 
 pub(crate) fn check_year_range(year: i32) -> Result<(), ValidationError> {
     if YEAR_RANGE.contains(&year) {
@@ -12,3 +15,8 @@ pub(crate) fn check_year_range(year: i32) -> Result<(), ValidationError> {
         })
     }
 }
+
+/// Checks that the Gregorian year in which a year of the calendar starts is in range.
+pub(crate) fn check_calendar_year_range(rscale: RScale, year: i32) -> Result<(), ValidationError> {
+    check_year_range(date_from_ordinal(rscale.year_start(year)).year())
+}
@@ -1,8 +1,10 @@
 use std::collections::HashSet;
 
-use chrono::{Datelike, TimeZone, Timelike, Utc, Weekday};
+use chrono::{
+    Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc, Weekday,
+};
 
-use crate::{Frequency, RRule, RRuleError, Tz};
+use crate::{Frequency, RRule, RRuleError, RScale};
 
 use super::{
     checks,
@@ -15,7 +17,7 @@ const MINUTES_IN_A_DAY: u32 = 60 * 24;
 const SECONDS_IN_A_DAY: u32 = 60 * 60 * 24;
 
 /// A simple date time type used during iteration.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub(crate) struct DateTimeIter {
     pub year: i32,
     pub month: u32,
@@ -42,6 +44,13 @@ impl DateTimeIter {
             by_second,
             ..
         } = rrule;
+        if let Some(rscale) = rrule.calendar() {
+            match rrule.freq {
+                Frequency::Yearly => return self.increment_calendar_yearly(rscale, *interval),
+                Frequency::Monthly => return self.increment_calendar_monthly(rscale, *interval),
+                _ => {}
+            }
+        }
         match rrule.freq {
             Frequency::Yearly => self.increment_yearly(*interval),
             Frequency::Monthly => self.increment_monthly(*interval),
@@ -82,6 +91,33 @@ impl DateTimeIter {
         Ok(())
     }
 
+    fn increment_calendar_yearly(
+        &mut self,
+        rscale: RScale,
+        interval: u16,
+    ) -> Result<(), RRuleError> {
+        self.year += i32::from(interval);
+        checks::check_calendar_year_range(rscale, self.year)?;
+        Ok(())
+    }
+
+    fn increment_calendar_monthly(
+        &mut self,
+        rscale: RScale,
+        interval: u16,
+    ) -> Result<(), RRuleError> {
+        let months = calendar_month_index(rscale, self) + i64::from(interval);
+        let (year, month) = rscale.year_and_month(months).ok_or_else(|| {
+            RRuleError::new_iter_err(
+                "Encountered a too high new month. Please decrease the rrule interval.",
+            )
+        })?;
+        self.year = year;
+        self.month = month;
+        checks::check_calendar_year_range(rscale, self.year)?;
+        Ok(())
+    }
+
     fn get_weekday(&self) -> u32 {
         let month_range_mask = if is_leap_year(self.year) {
             &MASKS.month_366_range
@@ -181,7 +217,7 @@ impl DateTimeIter {
                 Some("please decrease `INTERVAL`"),
             )?;
             let new_hours = u8::try_from(self.hour % 24).expect("range 0-23 is covered by u8");
-            if by_hour.is_empty() || by_hour.iter().any(|bh| *bh == new_hours) {
+            if by_hour.is_empty() || by_hour.contains(&new_hours) {
                 break;
             }
             if prev_hours.contains(&new_hours) {
@@ -305,8 +341,168 @@ impl DateTimeIter {
     }
 }
 
-impl From<&chrono::DateTime<Tz>> for DateTimeIter {
-    fn from(dt: &chrono::DateTime<Tz>) -> Self {
+impl DateTimeIter {
+    /// Returns the index of the last period of the [`RRule`] which starts at or before `date`,
+    /// where period `0` is the one of `dt_start`.
+    ///
+    /// The `n`th period (a year, month, week, day, hour, minute or second depending on the
+    /// frequency) is always `n * interval` periods after the period of `dt_start`.
+    pub fn period_index(rrule: &RRule, dt_start: &NaiveDateTime, date: &NaiveDateTime) -> i64 {
+        let interval = i64::from(rrule.interval);
+        if let Some(rscale) = rrule.calendar() {
+            let start = Self::from_calendar(rscale, dt_start);
+            let date = Self::from_calendar(rscale, date);
+            match rrule.freq {
+                Frequency::Yearly => {
+                    return i64::from(date.year - start.year).div_euclid(interval);
+                }
+                Frequency::Monthly => {
+                    return (calendar_month_index(rscale, &date)
+                        - calendar_month_index(rscale, &start))
+                    .div_euclid(interval);
+                }
+                _ => {}
+            }
+        }
+        match rrule.freq {
+            Frequency::Yearly => i64::from(date.year() - dt_start.year()).div_euclid(interval),
+            Frequency::Monthly => (month_index(date) - month_index(dt_start)).div_euclid(interval),
+            Frequency::Weekly => (date.date() - week_start(rrule, dt_start))
+                .num_days()
+                .div_euclid(7 * interval),
+            freq => {
+                let unit = seconds_in_period(freq);
+                let seconds_from_midnight = i64::from(dt_start.num_seconds_from_midnight());
+                let first_period_start = dt_start.date().and_time(NaiveTime::MIN)
+                    + Duration::seconds(seconds_from_midnight - seconds_from_midnight % unit);
+                (*date - first_period_start)
+                    .num_seconds()
+                    .div_euclid(unit * interval)
+            }
+        }
+    }
+
+    /// Returns whether the periods of the [`RRule`] counted from `date` are the same as the
+    /// ones counted from `dt_start`, which is only not the case with an `INTERVAL`.
+    pub fn is_aligned(rrule: &RRule, dt_start: &NaiveDateTime, date: &NaiveDateTime) -> bool {
+        if rrule.interval <= 1 {
+            return true;
+        }
+        let unit_rrule = RRule {
+            interval: 1,
+            ..rrule.clone()
+        };
+        Self::period_index(&unit_rrule, dt_start, date).rem_euclid(i64::from(rrule.interval)) == 0
+    }
+
+    /// Returns the counter date of the given period, as [`DateTimeIter::increment`] would
+    /// have incremented it to from `dt_start`.
+    ///
+    /// Returns `None` if the period is outside of the supported years.
+    pub fn from_period(rrule: &RRule, dt_start: &NaiveDateTime, period: i64) -> Option<Self> {
+        let offset = period.checked_mul(i64::from(rrule.interval))?;
+        if let Some(rscale) = rrule.calendar() {
+            let mut counter_date = Self::from_calendar(rscale, dt_start);
+            match rrule.freq {
+                Frequency::Yearly => {
+                    counter_date.year = i32::try_from(offset)
+                        .ok()
+                        .and_then(|offset| counter_date.year.checked_add(offset))?;
+                }
+                Frequency::Monthly => {
+                    let months = calendar_month_index(rscale, &counter_date).checked_add(offset)?;
+                    (counter_date.year, counter_date.month) = rscale.year_and_month(months)?;
+                }
+                _ => {}
+            }
+            if rrule.freq <= Frequency::Monthly {
+                checks::check_calendar_year_range(rscale, counter_date.year).ok()?;
+                return Some(counter_date);
+            }
+        }
+        let date = match rrule.freq {
+            Frequency::Yearly | Frequency::Monthly => {
+                let months = if rrule.freq == Frequency::Yearly {
+                    offset.checked_mul(12)?
+                } else {
+                    offset
+                };
+                let month = month_index(dt_start).checked_add(months)?;
+                let counter_date = Self {
+                    year: i32::try_from(month.div_euclid(12)).ok()?,
+                    month: u32::try_from(month.rem_euclid(12) + 1).ok()?,
+                    day: dt_start.day(),
+                    hour: dt_start.hour(),
+                    minute: dt_start.minute(),
+                    second: dt_start.second(),
+                };
+                checks::check_year_range(counter_date.year).ok()?;
+                return Some(counter_date);
+            }
+            Frequency::Weekly if period == 0 => *dt_start,
+            Frequency::Weekly => week_start(rrule, dt_start)
+                .checked_add_signed(Duration::try_days(offset.checked_mul(7)?)?)?
+                .and_time(dt_start.time()),
+            freq => dt_start.checked_add_signed(Duration::try_seconds(
+                offset.checked_mul(seconds_in_period(freq))?,
+            )?)?,
+        };
+        checks::check_year_range(date.year()).ok()?;
+        Some(Self {
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+            hour: date.hour(),
+            minute: date.minute(),
+            second: date.second(),
+        })
+    }
+}
+
+impl DateTimeIter {
+    /// Returns the counter date of `date` in a non-Gregorian calendar, which is used by yearly
+    /// and monthly rrules with that calendar. The month is the index in the months of the year.
+    pub fn from_calendar(rscale: RScale, date: &NaiveDateTime) -> Self {
+        let calendar_date = rscale.calendar_date(date.date());
+        Self {
+            year: calendar_date.year,
+            month: calendar_date.month,
+            day: calendar_date.day,
+            hour: date.hour(),
+            minute: date.minute(),
+            second: date.second(),
+        }
+    }
+}
+
+/// Number of months since the start of the calendar.
+fn calendar_month_index(rscale: RScale, counter_date: &DateTimeIter) -> i64 {
+    rscale.months_before_year(counter_date.year) + i64::from(counter_date.month) - 1
+}
+
+/// Returns the first day of the week containing `dt_start`.
+fn week_start(rrule: &RRule, dt_start: &NaiveDateTime) -> NaiveDate {
+    let week_start = rrule.week_start.num_days_from_monday();
+    let days = (dt_start.weekday().num_days_from_monday() + 7 - week_start) % 7;
+    dt_start.date() - Duration::days(i64::from(days))
+}
+
+/// Number of months since the start of year 0.
+fn month_index(date: &NaiveDateTime) -> i64 {
+    i64::from(date.year()) * 12 + i64::from(date.month0())
+}
+
+fn seconds_in_period(freq: Frequency) -> i64 {
+    match freq {
+        Frequency::Hourly => 60 * 60,
+        Frequency::Minutely => 60,
+        Frequency::Secondly => 1,
+        _ => 24 * 60 * 60,
+    }
+}
+
+impl<TZ: TimeZone> From<&chrono::DateTime<TZ>> for DateTimeIter {
+    fn from(dt: &chrono::DateTime<TZ>) -> Self {
         Self {
             year: dt.year(),
             month: dt.month(),
@@ -750,4 +946,54 @@ mod tests {
             assert_eq!(counter_date, expected_output);
         }
     }
+
+    #[test]
+    fn computes_periods_like_increment() {
+        let dt_start = UTC.with_ymd_and_hms(2020, 1, 31, 22, 45, 30).unwrap();
+        let freqs = [
+            (Frequency::Yearly, 3),
+            (Frequency::Monthly, 5),
+            (Frequency::Weekly, 2),
+            (Frequency::Daily, 9),
+            (Frequency::Hourly, 7),
+            (Frequency::Minutely, 45),
+            (Frequency::Secondly, 50),
+        ];
+        for (freq, interval) in freqs {
+            let rrule = RRule {
+                freq,
+                interval,
+                week_start: Weekday::Wed,
+                ..Default::default()
+            }
+            .validate(dt_start)
+            .unwrap();
+            let start = dt_start.naive_local();
+
+            let mut counter_date = DateTimeIter::from(&dt_start);
+            for period in 0..100 {
+                let expected = DateTimeIter::from_period(&rrule, &start, period).unwrap();
+                // The month of the counter date is not used for yearly frequencies
+                if freq == Frequency::Yearly {
+                    assert_eq!(counter_date.year, expected.year);
+                } else {
+                    assert_eq!(counter_date, expected, "{freq} period {period}");
+                }
+
+                // The day of the counter date is not used for yearly and monthly frequencies
+                let day = if freq > Frequency::Monthly {
+                    expected.day
+                } else {
+                    1
+                };
+                let date = NaiveDate::from_ymd_opt(expected.year, expected.month, day)
+                    .unwrap()
+                    .and_hms_opt(expected.hour, expected.minute, expected.second)
+                    .unwrap();
+                assert_eq!(DateTimeIter::period_index(&rrule, &start, &date), period);
+
+                counter_date.increment(&rrule, false).unwrap();
+            }
+        }
+    }
 }
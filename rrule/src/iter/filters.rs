@@ -21,13 +21,30 @@ pub(crate) fn is_filtered(ii: &IterInfo, current_day: usize) -> bool {
         .any(|filter| filter(ii, current_day, rrule))
 }
 
+/// Filters of the days moved to another day by `SKIP`, which don't have the month and month day
+/// of the `BYMONTH` and `BYMONTHDAY` rules they were created by.
+const SKIPPED_DAY_FILTERS: [RRuleFilter; 5] = [
+    &is_filtered_by_week_number,
+    &is_filtered_by_weekday,
+    &is_filtered_by_neg_weekday,
+    &is_filtered_by_easter,
+    &is_filtered_by_year_day,
+];
+
+pub(crate) fn is_skipped_day_filtered(ii: &IterInfo, current_day: usize) -> bool {
+    let rrule = ii.rrule();
+    SKIPPED_DAY_FILTERS
+        .into_iter()
+        .any(|filter| filter(ii, current_day, rrule))
+}
+
 fn is_filtered_by_month(ii: &IterInfo, current_day: usize, rrule: &RRule) -> bool {
-    if rrule.by_month.is_empty() {
+    if rrule.by_month.is_empty() && rrule.by_leap_month.is_empty() {
         return false;
     }
 
     let current_month = ii.month_mask()[current_day];
-    !rrule.by_month.contains(&current_month)
+    !ii.selected_months().contains(&current_month)
 }
 
 fn is_filtered_by_week_number(ii: &IterInfo, current_day: usize, rrule: &RRule) -> bool {
@@ -64,7 +81,8 @@ fn is_filtered_by_neg_weekday(ii: &IterInfo, current_day: usize, _rrule: &RRule)
             return false;
         }
 
-        let current_neg_weekday = neg_weekday_mask[current_day];
+        // Days moved by `SKIP` can be in the next year, which is not part of the mask
+        let current_neg_weekday = neg_weekday_mask.get(current_day).copied().unwrap_or(0);
         current_neg_weekday == 0
     } else {
         false
@@ -1,10 +1,11 @@
 use super::counter_date::DateTimeIter;
 #[cfg(feature = "by-easter")]
 use super::easter::easter;
+use super::utils::days_since_unix_epoch;
 use super::{monthinfo::MonthInfo, yearinfo::YearInfo};
 use crate::core::get_month;
-use crate::{Frequency, NWeekday, RRule, Tz};
-use chrono::{Datelike, NaiveTime, TimeZone};
+use crate::{Frequency, NWeekday, RRule, Skip};
+use chrono::{Datelike, NaiveDate, NaiveTime, TimeZone};
 
 #[derive(Debug, Clone)]
 pub(crate) struct IterInfo {
@@ -15,9 +16,15 @@ pub(crate) struct IterInfo {
 }
 
 impl IterInfo {
-    pub fn new(rrule: &RRule, dt_start: &chrono::DateTime<Tz>) -> Self {
-        let year = dt_start.year();
-        let month = get_month(dt_start);
+    pub fn new<TZ: TimeZone>(rrule: &RRule, dt_start: &chrono::DateTime<TZ>) -> Self {
+        let (year, month) = match rrule.calendar() {
+            Some(rscale) => {
+                let date = rscale.calendar_date(dt_start.date_naive());
+                let month = u8::try_from(date.month).expect("range 1-13 is covered by u8");
+                (date.year, month)
+            }
+            None => (dt_start.year(), get_month(dt_start)),
+        };
 
         let year_info = YearInfo::new(year, rrule);
         let mut ii = Self {
@@ -61,8 +68,22 @@ impl IterInfo {
     }
 
     pub fn rebuild(&mut self, counter_date: &DateTimeIter) {
-        let month = u8::try_from(counter_date.month).expect("range 1-12 is covered by u8");
-        self.rebuild_inner(counter_date.year, month, false);
+        let (year, month) = match self.rrule.calendar() {
+            // Only yearly and monthly counter dates are in the calendar of the rrule
+            Some(rscale) if self.rrule.freq >= Frequency::Weekly => {
+                let date = NaiveDate::from_ymd_opt(
+                    counter_date.year,
+                    counter_date.month,
+                    counter_date.day,
+                )
+                .expect("counter date is a valid date");
+                let date = rscale.calendar_date(date);
+                (date.year, date.month)
+            }
+            _ => (counter_date.year, counter_date.month),
+        };
+        let month = u8::try_from(month).expect("range 1-13 is covered by u8");
+        self.rebuild_inner(year, month, false);
     }
 
     pub fn year_len(&self) -> u16 {
@@ -74,7 +95,7 @@ impl IterInfo {
     }
 
     pub fn month_range(&self) -> &[u16] {
-        self.year_info.month_range
+        &self.year_info.month_range
     }
 
     pub fn easter_mask(&self) -> Option<&Vec<i32>> {
@@ -82,11 +103,15 @@ impl IterInfo {
     }
 
     pub fn weekday_mask(&self) -> &[u32] {
-        self.year_info.weekday_mask
+        &self.year_info.weekday_mask
     }
 
     pub fn month_mask(&self) -> &[u8] {
-        self.year_info.month_mask
+        &self.year_info.month_mask
+    }
+
+    pub fn selected_months(&self) -> &[u8] {
+        &self.year_info.selected_months
     }
 
     pub fn week_no_mask(&self) -> Option<&Vec<u8>> {
@@ -102,11 +127,11 @@ impl IterInfo {
     }
 
     pub fn month_day_mask(&self) -> &[i8] {
-        self.year_info.month_day_mask
+        &self.year_info.month_day_mask
     }
 
     pub fn neg_month_day_mask(&self) -> &[i8] {
-        self.year_info.neg_month_day_mask
+        &self.year_info.neg_month_day_mask
     }
 
     pub fn year_dayset(&self) -> Vec<usize> {
@@ -125,13 +150,7 @@ impl IterInfo {
     pub fn weekday_set(&self, year: i32, month: u32, day: u32) -> Vec<usize> {
         let set_len = usize::from(self.year_len() + 7);
 
-        let mut date_ordinal = usize::try_from(
-            chrono::Utc
-                .with_ymd_and_hms(year, month, day, 0, 0, 0)
-                .unwrap()
-                .ordinal0(),
-        )
-        .expect("target arch should have at least 32 bits");
+        let mut date_ordinal = self.day_of_year(year, month, day);
 
         let mut set = vec![];
 
@@ -151,13 +170,18 @@ impl IterInfo {
         set
     }
 
-    pub fn day_dayset(year: i32, month: u32, day: u32) -> Vec<usize> {
-        let date_ordinal = chrono::Utc
+    pub fn day_dayset(&self, year: i32, month: u32, day: u32) -> Vec<usize> {
+        vec![self.day_of_year(year, month, day)]
+    }
+
+    /// Returns the index of the (Gregorian) date in the days of the current year.
+    fn day_of_year(&self, year: i32, month: u32, day: u32) -> usize {
+        let date = chrono::Utc
             .with_ymd_and_hms(year, month, day, 0, 0, 0)
-            .unwrap()
-            .ordinal0();
+            .unwrap();
+        let date_ordinal = days_since_unix_epoch(&date) - self.year_ordinal();
 
-        vec![usize::try_from(date_ordinal).expect("target arch should have at least 32 bits")]
+        usize::try_from(date_ordinal).expect("date is in the current year")
     }
 
     pub fn hour_timeset(&self, hour: u8) -> Vec<NaiveTime> {
@@ -193,15 +217,71 @@ impl IterInfo {
             Frequency::Yearly => self.year_dayset(),
             Frequency::Monthly => self.month_dayset(month),
             Frequency::Weekly => self.weekday_set(year, month, day),
-            _ => Self::day_dayset(year, month, day),
+            _ => self.day_dayset(year, month, day),
         };
 
         // Filter out days according to the RRule filters.
         dayset.retain(|day| !super::filters::is_filtered(self, *day));
 
+        if matches!(freq, Frequency::Yearly | Frequency::Monthly)
+            && matches!(self.rrule.skip, Some(Skip::Backward | Skip::Forward))
+        {
+            dayset.extend(self.skipped_days(freq, month));
+            dayset.sort_unstable();
+            dayset.dedup();
+        }
+
         dayset
     }
 
+    /// Returns the days of `BYMONTHDAY` which don't exist in the months of the period,
+    /// moved to the previous or next valid day according to `SKIP`.
+    fn skipped_days(&self, freq: Frequency, month: u32) -> Vec<usize> {
+        let month_range = self.month_range();
+        let months = if freq == Frequency::Yearly {
+            1..month_range.len()
+        } else {
+            let month = usize::try_from(month).expect("target arch should have at least 32 bits");
+            month..month + 1
+        };
+        let by_month_is_set =
+            !self.rrule.by_month.is_empty() || !self.rrule.by_leap_month.is_empty();
+        let forward = self.rrule.skip == Some(Skip::Forward);
+
+        let mut days = vec![];
+        for month in months {
+            let is_selected =
+                u8::try_from(month).is_ok_and(|month| self.selected_months().contains(&month));
+            if by_month_is_set && !is_selected {
+                continue;
+            }
+
+            let start = usize::from(month_range[month - 1]);
+            let end = usize::from(month_range[month]);
+            let month_len = end - start;
+            for by_month_day in &self.rrule.by_month_day {
+                if usize::from(by_month_day.unsigned_abs()) > month_len {
+                    // The first day of the next month, or the last day of the month
+                    days.push(if forward { end } else { end - 1 });
+                }
+            }
+            for by_n_month_day in &self.rrule.by_n_month_day {
+                if usize::from(by_n_month_day.unsigned_abs()) > month_len {
+                    // The first day of the month, or the last day of the previous month
+                    // (which is skipped if it is in the previous year)
+                    days.extend(if forward {
+                        Some(start)
+                    } else {
+                        start.checked_sub(1)
+                    });
+                }
+            }
+        }
+
+        days.retain(|day| !super::filters::is_skipped_day_filtered(self, *day));
+        days
+    }
+
     /// Gets a timeset without checking if the hour, minute and second are valid, according
     /// to the `RRule`.
     ///
@@ -0,0 +1,157 @@
+use std::time::{Duration, Instant};
+
+/// Default maximum number of loop iterations without finding a recurrence.
+///
+/// If after this number of iterations no recurrence is found,
+/// we can assume none will be found.
+const DEFAULT_MAX_LOOP_ITERATIONS: u32 = 100_000;
+
+/// Limits which stop the iteration over an [`crate::RRuleSet`] to prevent infinite loops
+/// or very long iteration times, see [`crate::RRuleSet::iter_limits`].
+///
+/// When a limit is reached the iteration ends, and [`crate::RRuleResult::limited`] is set.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::{IterLimits, RRuleSet};
+/// use std::time::Duration;
+///
+/// let rrule_set: RRuleSet = "DTSTART:20210101T000000Z\nRRULE:FREQ=MINUTELY;UNTIL=20220101T000000Z"
+///     .parse()
+///     .unwrap();
+///
+/// // A year of minutes is more than `RRuleSet::all` can return
+/// let limits = IterLimits::default()
+///     .max_results(1_000_000)
+///     .max_duration(Duration::from_secs(60));
+/// let result = rrule_set.iter_limits(limits).all_within_limits();
+/// assert_eq!(result.dates.len(), 365 * 24 * 60 + 1);
+/// assert!(!result.limited);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IterLimits {
+    /// Maximum number of recurrences returned by [`crate::RRuleSet::all_within_limits`].
+    pub(crate) max_results: Option<usize>,
+    /// Maximum number of loop iterations without finding a recurrence.
+    pub(crate) max_loop_iterations: Option<u32>,
+    /// Maximum wall-clock time an iterator can run for, from its creation.
+    pub(crate) max_duration: Option<Duration>,
+}
+
+impl Default for IterLimits {
+    /// Returns the validation limits, which only limit the loop iterations to `100_000`.
+    ///
+    /// These are the limits of a new [`crate::RRuleSet`].
+    fn default() -> Self {
+        Self {
+            max_results: None,
+            max_loop_iterations: Some(DEFAULT_MAX_LOOP_ITERATIONS),
+            max_duration: None,
+        }
+    }
+}
+
+impl IterLimits {
+    /// Returns limits which never stop the iteration.
+    ///
+    /// An [`crate::RRuleSet`] uses [`IterLimits::default`] unless these are set with
+    /// [`crate::RRuleSet::iter_limits`], which might lead to very long iteration times.
+    /// Please read the `SECURITY.md` for more information.
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_results: None,
+            max_loop_iterations: None,
+            max_duration: None,
+        }
+    }
+
+    /// Sets the maximum number of recurrences returned by
+    /// [`crate::RRuleSet::all_within_limits`].
+    #[must_use]
+    pub fn max_results(mut self, max_results: usize) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Sets the maximum number of loop iterations without finding a recurrence.
+    #[must_use]
+    pub fn max_loop_iterations(mut self, max_loop_iterations: u32) -> Self {
+        self.max_loop_iterations = Some(max_loop_iterations);
+        self
+    }
+
+    /// Sets the maximum wall-clock time an iterator can run for, from its creation.
+    #[must_use]
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Returns the maximum number of recurrences returned by
+    /// [`crate::RRuleSet::all_within_limits`], if set.
+    #[must_use]
+    pub fn get_max_results(&self) -> Option<usize> {
+        self.max_results
+    }
+
+    /// Returns the maximum number of loop iterations without finding a recurrence, if set.
+    #[must_use]
+    pub fn get_max_loop_iterations(&self) -> Option<u32> {
+        self.max_loop_iterations
+    }
+
+    /// Returns the maximum wall-clock time an iterator can run for, if set.
+    #[must_use]
+    pub fn get_max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+}
+
+/// Enforces the loop and wall-clock [`IterLimits`] of an iterator.
+#[derive(Debug, Clone)]
+pub(crate) struct Limiter {
+    max_loop_iterations: Option<u32>,
+    /// Time after which the iteration stops.
+    deadline: Option<Instant>,
+}
+
+impl Limiter {
+    /// Creates a limiter whose deadline starts now.
+    pub(crate) fn new(limits: &IterLimits) -> Self {
+        Self {
+            max_loop_iterations: limits.max_loop_iterations,
+            // A deadline too far in the future is the same as none
+            deadline: limits
+                .max_duration
+                .and_then(|duration| Instant::now().checked_add(duration)),
+        }
+    }
+
+    /// Returns true if the iteration should stop after `loop_counter` loop iterations
+    /// without finding a recurrence.
+    pub(crate) fn is_exceeded(&self, loop_counter: u32) -> bool {
+        if matches!(self.max_loop_iterations, Some(max) if loop_counter >= max) {
+            log::warn!(
+                "Reached max loop counter (`{}`). \
+                See 'validator limits' in docs for more info.",
+                loop_counter
+            );
+            return true;
+        }
+        self.is_past_deadline()
+    }
+
+    /// Returns true if the iteration should stop because it ran for too long.
+    pub(crate) fn is_past_deadline(&self) -> bool {
+        let is_past_deadline =
+            matches!(self.deadline, Some(deadline) if Instant::now() >= deadline);
+        if is_past_deadline {
+            log::warn!(
+                "Reached max iteration duration. See 'validator limits' in docs for more info."
+            );
+        }
+        is_past_deadline
+    }
+}
@@ -1,25 +1,31 @@
 #![allow(clippy::module_name_repetitions)]
 
+mod between_iter;
 mod checks;
-mod counter_date;
+pub(crate) mod counter_date;
 mod easter;
 pub(crate) mod filters;
 pub(crate) mod iterinfo;
+mod limits;
 mod masks;
 mod monthinfo;
+mod occurrence_iter;
 mod operation_errors;
 mod pos_list;
 pub(crate) mod rrule_iter;
+pub(crate) mod rrule_rev_iter;
 mod rruleset_iter;
+mod rruleset_rev_iter;
 mod utils;
 mod yearinfo;
 
+pub use between_iter::BetweenIter;
 use iterinfo::IterInfo;
+pub use limits::IterLimits;
+pub(crate) use limits::Limiter;
+pub use occurrence_iter::OccurrenceIter;
 use pos_list::build_pos_list;
 pub(crate) use rrule_iter::RRuleIter;
-pub use rruleset_iter::RRuleSetIter;
-
-/// Prevent loops when searching for the next event in the iterator.
-/// If after X number of iterations it still has not found an event,
-/// we can assume it will not find an event.
-static MAX_ITER_LOOP: u32 = 100_000;
+pub use rruleset_iter::{RRuleSetConvertIter, RRuleSetIter};
+pub use rruleset_rev_iter::RRuleSetRevIter;
+pub(crate) use utils::add_time_to_date;
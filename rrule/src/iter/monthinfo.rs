@@ -23,16 +23,17 @@ impl MonthInfo {
             year_len,
             month_range,
             weekday_mask,
+            selected_months,
             ..
         } = year_info;
 
         // Build up `ranges`
         let mut ranges = vec![];
         if rrule.freq == Frequency::Yearly {
-            if rrule.by_month.is_empty() {
+            if rrule.by_month.is_empty() && rrule.by_leap_month.is_empty() {
                 ranges.push((0, u32::from(*year_len) - 1));
             } else {
-                for month in &rrule.by_month {
+                for month in selected_months {
                     let month = usize::from(*month);
                     let first = u32::from(month_range[month - 1]);
                     let last = u32::from(month_range[month]);
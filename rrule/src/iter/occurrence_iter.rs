@@ -0,0 +1,81 @@
+use chrono::{DateTime, Duration, TimeZone};
+
+use crate::core::{
+    max_backward_shift, override_occurrence, EventDuration, Occurrence, RecurrenceOverrides,
+};
+use crate::{RRuleSetIter, Tz};
+
+#[derive(Debug, Clone)]
+/// Iterator over all the occurrences in an [`crate::RRuleSet`], see
+/// [`crate::RRuleSet::occurrences`].
+pub struct OccurrenceIter<TZ: TimeZone = Tz> {
+    iter: RRuleSetIter<TZ>,
+    duration: EventDuration,
+    overrides: RecurrenceOverrides<TZ>,
+    /// Largest amount of time an override moves an occurrence back.
+    max_backward_shift: Duration,
+    /// Occurrences not yet yielded, sorted by decreasing start.
+    buffer: Vec<Occurrence<TZ>>,
+    /// Last recurrence yielded by `iter`.
+    last_recurrence_id: Option<DateTime<TZ>>,
+    /// If `iter` is exhausted.
+    finished: bool,
+}
+
+impl<TZ: TimeZone> OccurrenceIter<TZ> {
+    pub(crate) fn new(
+        iter: RRuleSetIter<TZ>,
+        duration: EventDuration,
+        overrides: RecurrenceOverrides<TZ>,
+    ) -> Self {
+        Self {
+            iter,
+            duration,
+            max_backward_shift: max_backward_shift(&overrides),
+            overrides,
+            buffer: vec![],
+            last_recurrence_id: None,
+            finished: false,
+        }
+    }
+
+    /// Returns true if no later recurrence can be moved before `occurrence`.
+    fn is_next(&self, occurrence: &Occurrence<TZ>) -> bool {
+        self.finished
+            || matches!(
+                &self.last_recurrence_id,
+                Some(last) if occurrence.start <= last.clone() - self.max_backward_shift
+            )
+    }
+}
+
+impl<TZ: TimeZone> Iterator for OccurrenceIter<TZ> {
+    type Item = Occurrence<TZ>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(occurrence) = self.buffer.last() {
+                if self.is_next(occurrence) {
+                    return self.buffer.pop();
+                }
+            } else if self.finished {
+                return None;
+            }
+
+            // Stops at the first occurrence whose end can not be represented
+            let occurrence = self.iter.next().and_then(|recurrence_id| {
+                self.last_recurrence_id = Some(recurrence_id.clone());
+                override_occurrence(&self.overrides, recurrence_id, self.duration)
+            });
+            match occurrence {
+                Some(occurrence) => {
+                    let index = self
+                        .buffer
+                        .partition_point(|buffered| buffered.start > occurrence.start);
+                    self.buffer.insert(index, occurrence);
+                }
+                None => self.finished = true,
+            }
+        }
+    }
+}
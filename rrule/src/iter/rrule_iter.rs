@@ -1,34 +1,37 @@
 use super::counter_date::DateTimeIter;
 use super::utils::add_time_to_date;
-use super::{build_pos_list, utils::date_from_ordinal, IterInfo, MAX_ITER_LOOP};
+use super::{build_pos_list, utils::date_from_ordinal, IterInfo, Limiter};
 use crate::core::{get_hour, get_minute, get_second};
-use crate::{Frequency, RRule, Tz};
-use chrono::NaiveTime;
+use crate::{Frequency, RRule};
+use chrono::{NaiveTime, TimeZone};
 use std::collections::VecDeque;
 
 #[derive(Debug, Clone)]
-pub(crate) struct RRuleIter {
+pub(crate) struct RRuleIter<TZ: TimeZone> {
     /// Date the iterator is currently at.
     pub(crate) counter_date: DateTimeIter,
     pub(crate) ii: IterInfo,
     pub(crate) timeset: Vec<NaiveTime>,
-    pub(crate) dt_start: chrono::DateTime<Tz>,
+    pub(crate) dt_start: chrono::DateTime<TZ>,
     /// Buffer of datetimes is not yet yielded
-    pub(crate) buffer: VecDeque<chrono::DateTime<Tz>>,
+    pub(crate) buffer: VecDeque<chrono::DateTime<TZ>>,
     /// Indicate of iterator should not return more items.
     /// Once set `true` is will always return `None`.
     pub(crate) finished: bool,
     /// Number of events that should still be generated before the end.
     /// Counter always goes down after each iteration.
     pub(crate) count: Option<u32>,
-    /// If the iterator should be using iterator limits.
-    pub(crate) limited: bool,
+    /// Iterator limits to enforce.
+    pub(crate) limiter: Limiter,
     /// If the iterator has been stopped by the iterator limits.
     pub(crate) was_limited: bool,
+    /// Last date added to the buffer, only kept when `SKIP` can move a date to
+    /// another period.
+    pub(crate) last_date: Option<chrono::DateTime<TZ>>,
 }
 
-impl RRuleIter {
-    pub(crate) fn new(rrule: &RRule, dt_start: &chrono::DateTime<Tz>, limited: bool) -> Self {
+impl<TZ: TimeZone> RRuleIter<TZ> {
+    pub(crate) fn new(rrule: &RRule, dt_start: &chrono::DateTime<TZ>, limiter: Limiter) -> Self {
         let ii = IterInfo::new(rrule, dt_start);
 
         let hour = get_hour(dt_start);
@@ -36,28 +39,94 @@ impl RRuleIter {
         let second = get_second(dt_start);
         let timeset = ii.get_timeset(hour, minute, second);
         let count = ii.rrule().count;
+        let counter_date = match rrule.calendar() {
+            Some(rscale) if rrule.freq <= Frequency::Monthly => {
+                DateTimeIter::from_calendar(rscale, &dt_start.naive_local())
+            }
+            _ => dt_start.into(),
+        };
 
         Self {
-            counter_date: dt_start.into(),
+            counter_date,
             ii,
             timeset,
-            dt_start: *dt_start,
+            dt_start: dt_start.clone(),
             buffer: VecDeque::new(),
             finished: false,
             count,
-            limited,
+            limiter,
             was_limited: false,
+            last_date: None,
+        }
+    }
+
+    /// Skips the dates before `date`, so the next date is the first one at or after `date`.
+    ///
+    /// The counter date jumps straight to the period containing `date`, unless the rrule
+    /// has a `COUNT`, in which case the dates before it have to be counted one by one.
+    pub(crate) fn seek(&mut self, date: &chrono::DateTime<TZ>) {
+        let rrule = self.ii.rrule();
+        if rrule.count.is_none() && rrule.interval > 0 && !self.finished {
+            let dt_start = self.dt_start.naive_local();
+            // The previous period is included as well, as a date of it can be moved to the
+            // period of `date` when its local time does not exist.
+            let period = DateTimeIter::period_index(rrule, &dt_start, &date.naive_local()) - 1;
+            match DateTimeIter::from_period(rrule, &dt_start, period) {
+                Some(counter_date) if counter_date > self.counter_date => {
+                    if matches!(
+                        rrule.freq,
+                        Frequency::Hourly | Frequency::Minutely | Frequency::Secondly
+                    ) {
+                        let hour =
+                            u8::try_from(counter_date.hour).expect("range 0-23 is covered by u8");
+                        let minute =
+                            u8::try_from(counter_date.minute).expect("range 0-59 is covered by u8");
+                        let second =
+                            u8::try_from(counter_date.second).expect("range 0-59 is covered by u8");
+                        // The period might not match the `BYXXX` rules, unlike the periods
+                        // the counter date is incremented to.
+                        self.timeset = self.ii.get_timeset(hour, minute, second);
+                    }
+                    self.buffer.clear();
+                    self.ii.rebuild(&counter_date);
+                    self.counter_date = counter_date;
+                }
+                Some(_) => {}
+                // The period is after the supported years
+                None if period > 0 => {
+                    self.buffer.clear();
+                    self.finished = true;
+                }
+                None => {}
+            }
+        }
+
+        loop {
+            if self.buffer.is_empty() {
+                if self.finished {
+                    return;
+                }
+                self.finished = self.generate();
+                if self.buffer.is_empty() {
+                    self.finished = true;
+                    return;
+                }
+            }
+            if matches!(self.buffer.front(), Some(next) if next >= date) {
+                return;
+            }
+            self.buffer.pop_front();
         }
     }
 
     /// Attempts to add a date to the result. Returns `true` if we should
     /// terminate the iteration.
     fn try_add_datetime(
-        dt: chrono::DateTime<Tz>,
+        dt: chrono::DateTime<TZ>,
         rrule: &RRule,
         count: &mut Option<u32>,
-        buffer: &mut VecDeque<chrono::DateTime<Tz>>,
-        dt_start: &chrono::DateTime<Tz>,
+        buffer: &mut VecDeque<chrono::DateTime<TZ>>,
+        dt_start: &chrono::DateTime<TZ>,
     ) -> bool {
         if matches!(rrule.until, Some(until) if dt > until) {
             // We can break because `pos_list` is sorted and
@@ -101,18 +170,11 @@ impl RRuleIter {
         // Loop until there is at least 1 item in the buffer.
         while self.buffer.is_empty() {
             // Prevent infinite loops
-            if self.limited {
-                loop_counter += 1;
-                if loop_counter >= MAX_ITER_LOOP {
-                    self.finished = true;
-                    self.was_limited = true;
-                    log::warn!(
-                        "Reached max loop counter (`{}`). \
-                    See 'validator limits' in docs for more info.",
-                        MAX_ITER_LOOP
-                    );
-                    return true;
-                }
+            loop_counter += 1;
+            if self.limiter.is_exceeded(loop_counter) {
+                self.finished = true;
+                self.was_limited = true;
+                return true;
             }
             let rrule = self.ii.rrule();
 
@@ -124,50 +186,23 @@ impl RRuleIter {
             );
 
             let tz = self.dt_start.timezone();
-
-            if rrule.by_set_pos.is_empty() {
-                // Loop over `start..end`
-                for current_day in &dayset {
-                    let current_day = i64::try_from(*current_day).expect(
-                        "We control the dayset, and we know that it will always fit within an i64",
-                    );
-                    let year_ordinal = self.ii.year_ordinal();
-                    // Ordinal conversion uses UTC: if we apply local-TZ here, then
-                    // just below we'll end up double-applying.
-                    let date = date_from_ordinal(year_ordinal + current_day);
-                    for time in &self.timeset {
-                        let Some(dt) = add_time_to_date(tz, date, *time) else {
-                            continue;
-                        };
-                        if Self::try_add_datetime(
-                            dt,
-                            rrule,
-                            &mut self.count,
-                            &mut self.buffer,
-                            &self.dt_start,
-                        ) {
-                            return true;
-                        }
+            let skips_to_other_periods = rrule.skip.is_some();
+            for dt in period_dates(&self.ii, &dayset, &self.timeset, &tz) {
+                if skips_to_other_periods {
+                    // The date might have been moved to a date which was already added
+                    if matches!(&self.last_date, Some(last_date) if dt <= *last_date) {
+                        continue;
                     }
+                    self.last_date = Some(dt.clone());
                 }
-            } else {
-                let pos_list = build_pos_list(
-                    &rrule.by_set_pos,
-                    &dayset,
-                    &self.timeset,
-                    self.ii.year_ordinal(),
-                    self.dt_start.timezone(),
-                );
-                for dt in pos_list {
-                    if Self::try_add_datetime(
-                        dt,
-                        rrule,
-                        &mut self.count,
-                        &mut self.buffer,
-                        &self.dt_start,
-                    ) {
-                        return true;
-                    }
+                if Self::try_add_datetime(
+                    dt,
+                    rrule,
+                    &mut self.count,
+                    &mut self.buffer,
+                    &self.dt_start,
+                ) {
+                    return true;
                 }
             }
 
@@ -198,8 +233,8 @@ impl RRuleIter {
     }
 }
 
-impl Iterator for RRuleIter {
-    type Item = chrono::DateTime<Tz>;
+impl<TZ: TimeZone> Iterator for RRuleIter<TZ> {
+    type Item = chrono::DateTime<TZ>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if !self.buffer.is_empty() {
@@ -219,11 +254,40 @@ impl Iterator for RRuleIter {
     }
 }
 
+/// Returns the dates of the period of `ii` for the given `dayset` and `timeset`.
+pub(crate) fn period_dates<TZ: TimeZone>(
+    ii: &IterInfo,
+    dayset: &[usize],
+    timeset: &[NaiveTime],
+    tz: &TZ,
+) -> Vec<chrono::DateTime<TZ>> {
+    let rrule = ii.rrule();
+    if !rrule.by_set_pos.is_empty() {
+        return build_pos_list(&rrule.by_set_pos, dayset, timeset, ii.year_ordinal(), tz);
+    }
+
+    let mut dates = Vec::with_capacity(dayset.len() * timeset.len());
+    // Loop over `start..end`
+    for current_day in dayset {
+        let current_day = i64::try_from(*current_day)
+            .expect("We control the dayset, and we know that it will always fit within an i64");
+        // Ordinal conversion uses UTC: if we apply local-TZ here, then
+        // just below we'll end up double-applying.
+        let date = date_from_ordinal(ii.year_ordinal() + current_day);
+        dates.extend(
+            timeset
+                .iter()
+                .filter_map(|time| add_time_to_date(tz, date, *time)),
+        );
+    }
+    dates
+}
+
 pub(crate) trait WasLimited {
     fn was_limited(&self) -> bool;
 }
 
-impl WasLimited for RRuleIter {
+impl<TZ: TimeZone> WasLimited for RRuleIter<TZ> {
     fn was_limited(&self) -> bool {
         self.was_limited
     }
@@ -0,0 +1,261 @@
+use super::counter_date::DateTimeIter;
+use super::rrule_iter::{period_dates, WasLimited};
+use super::{IterInfo, Limiter};
+use crate::core::{get_hour, get_minute, get_second};
+use crate::validator::YEAR_RANGE;
+use crate::{Frequency, RRule};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone};
+
+/// Iterator over the dates of an [`RRule`] in reverse order, starting at the end of the rrule.
+///
+/// The periods of the rrule are walked back from the one containing the end, see
+/// [`DateTimeIter::period_index`], without iterating from the start.
+/// Rrules with a `COUNT` can not be iterated this way, as the number of previous recurrences
+/// is unknown, so they are collected in order and yielded in reverse.
+#[derive(Debug, Clone)]
+pub(crate) struct RRuleRevIter<TZ: TimeZone> {
+    ii: IterInfo,
+    /// Timeset of the rrule, only used for frequencies of daily and above.
+    timeset: Vec<NaiveTime>,
+    dt_start: DateTime<TZ>,
+    /// Last date which might be yielded (inclusive).
+    end: DateTime<TZ>,
+    /// Index of the next period to generate, `None` once all periods have been generated.
+    period: Option<i64>,
+    /// Dates of the generated periods not yet yielded, sorted in increasing order.
+    buffer: Vec<DateTime<TZ>>,
+    /// Iterator limits to enforce.
+    limiter: Limiter,
+    /// If the iterator has been stopped by the iterator limits.
+    was_limited: bool,
+    /// First date of the periods generated so far, only kept when `SKIP` can move a date to
+    /// another period.
+    first_date: Option<DateTime<TZ>>,
+}
+
+impl<TZ: TimeZone> RRuleRevIter<TZ> {
+    /// Creates an iterator over the dates of `rrule` which are not after `end`.
+    ///
+    /// Returns `None` if the rrule has no `COUNT` or `UNTIL` and `end` is `None`,
+    /// as the iterator would not have a last date to start from.
+    pub(crate) fn new(
+        rrule: &RRule,
+        dt_start: &DateTime<TZ>,
+        end: Option<DateTime<TZ>>,
+        limiter: &Limiter,
+    ) -> Option<Self> {
+        let ii = IterInfo::new(rrule, dt_start);
+        let timeset = ii.get_timeset(
+            get_hour(dt_start),
+            get_minute(dt_start),
+            get_second(dt_start),
+        );
+
+        if rrule.count.is_some() {
+            let mut iter = rrule.iter_with_ctx(dt_start.clone(), limiter.clone());
+            let buffer = iter
+                .by_ref()
+                .take_while(|date| end.as_ref().map_or(true, |end| date <= end))
+                .collect();
+            return Some(Self {
+                ii,
+                timeset,
+                dt_start: dt_start.clone(),
+                end: dt_start.clone(),
+                period: None,
+                buffer,
+                limiter: limiter.clone(),
+                was_limited: iter.was_limited(),
+                first_date: None,
+            });
+        }
+
+        let until = rrule
+            .until
+            .map(|until| until.with_timezone(&dt_start.timezone()));
+        let end = match (until, end) {
+            (Some(until), Some(end)) => until.min(end),
+            (until, end) => until.or(end)?,
+        };
+
+        let mut iter = Self {
+            ii,
+            timeset,
+            dt_start: dt_start.clone(),
+            end,
+            period: None,
+            buffer: vec![],
+            limiter: limiter.clone(),
+            was_limited: false,
+            first_date: None,
+        };
+        if rrule.interval > 0 && iter.end >= iter.dt_start {
+            let max_date = NaiveDate::from_ymd_opt(*YEAR_RANGE.end(), 12, 31)
+                .and_then(|date| date.and_hms_opt(23, 59, 59))
+                .expect("last day of the year range is a valid date");
+            // The next period is included as well, as a date can be moved to it
+            // when its local time does not exist.
+            let period = DateTimeIter::period_index(
+                rrule,
+                &iter.dt_start.naive_local(),
+                &iter.end.naive_local().min(max_date),
+            );
+            iter.period = Some(period + 1);
+        }
+        Some(iter)
+    }
+
+    /// Returns the next date without consuming it.
+    pub(crate) fn peek(&mut self) -> Option<&DateTime<TZ>> {
+        if self.buffer.is_empty() {
+            self.generate();
+        }
+        self.buffer.last()
+    }
+
+    /// Generates the dates of the periods before the current one, until at least one date
+    /// is found or all the periods have been generated.
+    fn generate(&mut self) {
+        let rrule = self.ii.rrule().clone();
+        let is_sub_daily = matches!(
+            rrule.freq,
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly
+        );
+
+        let mut loop_counter: u32 = 0;
+        while self.buffer.is_empty() {
+            let Some(period) = self.period.filter(|period| *period >= 0) else {
+                self.period = None;
+                return;
+            };
+            // Prevent infinite loops
+            loop_counter += 1;
+            if self.limiter.is_exceeded(loop_counter) {
+                self.period = None;
+                self.was_limited = true;
+                return;
+            }
+
+            let Some((counter_date, dayset, dates)) = self.dates_of_period(&rrule, period) else {
+                self.period = Some(period - 1);
+                continue;
+            };
+
+            let mut next_period = period - 1;
+            if dayset.is_empty() && is_sub_daily {
+                // Jump to the last period of the previous day
+                let day_start = NaiveDate::from_ymd_opt(
+                    counter_date.year,
+                    counter_date.month,
+                    counter_date.day,
+                )
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .expect("counter date is a valid date");
+                next_period = next_period.min(DateTimeIter::period_index(
+                    &rrule,
+                    &self.dt_start.naive_local(),
+                    &(day_start - Duration::seconds(1)),
+                ));
+            }
+            self.period = Some(next_period);
+
+            self.buffer = dates
+                .into_iter()
+                .filter(|date| *date >= self.dt_start && *date <= self.end)
+                .collect();
+            self.buffer.sort();
+
+            if rrule.skip.is_some() {
+                // A date might have been moved to a date of a later period
+                if let Some(first_date) = &self.first_date {
+                    self.buffer.retain(|date| date < first_date);
+                }
+                if let Some(date) = self.buffer.first() {
+                    self.first_date = Some(date.clone());
+                }
+            }
+        }
+    }
+}
+
+impl<TZ: TimeZone> RRuleRevIter<TZ> {
+    /// Returns true if `date` is a date of `rrule`.
+    ///
+    /// Only the periods around the one of `date` are generated, except for rrules with a
+    /// `COUNT`, whose dates are iterated up to `date`.
+    pub(crate) fn contains(
+        rrule: &RRule,
+        dt_start: &DateTime<TZ>,
+        date: &DateTime<TZ>,
+        limiter: &Limiter,
+    ) -> bool {
+        let Some(mut iter) = Self::new(rrule, dt_start, Some(date.clone()), limiter) else {
+            return false;
+        };
+        let Some(period) = iter.period else {
+            // All the dates up to `date` were collected already
+            return iter.buffer.contains(date);
+        };
+        if *date < iter.dt_start || *date > iter.end {
+            return false;
+        }
+
+        // A date can be moved to the next period when its local time does not exist,
+        // or when `SKIP=FORWARD` moves it to the next month.
+        (period - 2..=period)
+            .filter(|period| *period >= 0)
+            .any(|period| {
+                iter.dates_of_period(rrule, period)
+                    .is_some_and(|(_, _, dates)| dates.contains(date))
+            })
+    }
+
+    /// Returns the counter date, dayset and dates of the period with index `period`, or `None`
+    /// if the period is outside of the supported years.
+    fn dates_of_period(
+        &mut self,
+        rrule: &RRule,
+        period: i64,
+    ) -> Option<(DateTimeIter, Vec<usize>, Vec<DateTime<TZ>>)> {
+        let counter_date = DateTimeIter::from_period(rrule, &self.dt_start.naive_local(), period)?;
+        self.ii.rebuild(&counter_date);
+
+        let dayset = self.ii.get_dayset(
+            rrule.freq,
+            counter_date.year,
+            counter_date.month,
+            counter_date.day,
+        );
+        let timeset = if matches!(
+            rrule.freq,
+            Frequency::Hourly | Frequency::Minutely | Frequency::Secondly
+        ) {
+            let hour = u8::try_from(counter_date.hour).expect("range 0-23 is covered by u8");
+            let minute = u8::try_from(counter_date.minute).expect("range 0-59 is covered by u8");
+            let second = u8::try_from(counter_date.second).expect("range 0-59 is covered by u8");
+            self.ii.get_timeset(hour, minute, second)
+        } else {
+            self.timeset.clone()
+        };
+
+        let dates = period_dates(&self.ii, &dayset, &timeset, &self.dt_start.timezone());
+        Some((counter_date, dayset, dates))
+    }
+}
+
+impl<TZ: TimeZone> Iterator for RRuleRevIter<TZ> {
+    type Item = DateTime<TZ>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            self.generate();
+        }
+        self.buffer.pop()
+    }
+}
+
+impl<TZ: TimeZone> WasLimited for RRuleRevIter<TZ> {
+    fn was_limited(&self) -> bool {
+        self.was_limited
+    }
+}
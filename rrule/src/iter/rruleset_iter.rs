@@ -1,33 +1,79 @@
-use chrono::DateTime;
+use chrono::{DateTime, TimeZone};
 
 use super::rrule_iter::WasLimited;
-use super::{rrule_iter::RRuleIter, MAX_ITER_LOOP};
-use crate::RRuleSet;
+use super::{rrule_iter::RRuleIter, Limiter};
+use crate::{FromDateTime, IntoDateTime, RRuleSet};
 use crate::{RRuleError, Tz};
 use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::iter::{Iterator, MapWhile};
 use std::str::FromStr;
-use std::{collections::HashMap, iter::Iterator};
 
 #[derive(Debug, Clone)]
 /// Iterator over all the dates in an [`RRuleSet`].
-pub struct RRuleSetIter {
-    queue: HashMap<usize, DateTime<Tz>>,
-    limited: bool,
-    rrule_iters: Vec<RRuleIter>,
-    exrules: Vec<RRuleIter>,
+pub struct RRuleSetIter<TZ: TimeZone = Tz> {
+    queue: HashMap<usize, DateTime<TZ>>,
+    limiter: Limiter,
+    rrule_iters: Vec<RRuleIter<TZ>>,
+    exrules: Vec<RRuleIter<TZ>>,
     exdates: BTreeSet<i64>,
     /// Sorted additional dates in descending order
-    rdates: Vec<DateTime<Tz>>,
+    rdates: Vec<DateTime<TZ>>,
     was_limited: bool,
 }
 
-impl RRuleSetIter {
+/// Iterator over the recurrences of an [`RRuleSet`] converted into `T`,
+/// see [`RRuleSetIter::convert`].
+pub type RRuleSetConvertIter<TZ, T> = MapWhile<RRuleSetIter<TZ>, fn(DateTime<TZ>) -> Option<T>>;
+
+impl<TZ: TimeZone> RRuleSetIter<TZ> {
+    /// Converts the recurrences into another datetime type, e.g. `jiff::Zoned`.
+    ///
+    /// The iterator ends at the first recurrence which can not be represented by `T`.
+    pub fn convert<T: FromDateTime<TZ>>(self) -> RRuleSetConvertIter<TZ, T> {
+        self.map_while(T::from_datetime)
+    }
+
+    /// Skips the recurrences before `dt`, so the next recurrence is the first one at or after `dt`.
+    ///
+    /// The rrules jump straight to the period containing `dt` instead of iterating over all
+    /// the periods before it, except for rrules with a `COUNT`.
+    /// Recurrences which were skipped or already yielded are never yielded again.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use chrono::TimeZone;
+    /// use rrule::{RRuleSet, Tz};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20010101T090000Z\nRRULE:FREQ=MINUTELY;INTERVAL=15"
+    ///     .parse()
+    ///     .unwrap();
+    /// let mut iter = rrule_set.into_iter();
+    /// iter.seek(Tz::UTC.with_ymd_and_hms(2024, 6, 1, 12, 10, 0).unwrap());
+    ///
+    /// assert_eq!(iter.next().unwrap().to_rfc3339(), "2024-06-01T12:15:00+00:00");
+    /// ```
+    pub fn seek(&mut self, dt: impl IntoDateTime<TZ>) {
+        let dt = dt.into_datetime();
+        self.queue.retain(|_, date| *date >= dt);
+        for rrule_iter in &mut self.rrule_iters {
+            rrule_iter.seek(&dt);
+        }
+        for exrule in &mut self.exrules {
+            exrule.seek(&dt);
+        }
+        while matches!(self.rdates.last(), Some(rdate) if *rdate < dt) {
+            self.rdates.pop();
+        }
+    }
+
     fn generate_date(
-        dates: &mut Vec<DateTime<Tz>>,
-        exrules: &mut [RRuleIter],
+        dates: &mut Vec<DateTime<TZ>>,
+        exrules: &mut [RRuleIter<TZ>],
         exdates: &mut BTreeSet<i64>,
-        limited: bool,
-    ) -> (Option<DateTime<Tz>>, bool) {
+        limiter: &Limiter,
+    ) -> (Option<DateTime<TZ>>, bool) {
         if dates.is_empty() {
             return (None, false);
         }
@@ -39,16 +85,9 @@ impl RRuleSetIter {
                 return (None, false);
             }
             // Prevent infinite loops
-            if limited {
-                loop_counter += 1;
-                if loop_counter >= MAX_ITER_LOOP {
-                    log::warn!(
-                        "Reached max loop counter (`{}`). \
-                See 'validator limits' in docs for more info.",
-                        MAX_ITER_LOOP
-                    );
-                    return (None, true);
-                }
+            loop_counter += 1;
+            if limiter.is_exceeded(loop_counter) {
+                return (None, true);
             }
             date = dates.remove(dates.len() - 1);
         }
@@ -57,11 +96,11 @@ impl RRuleSetIter {
     }
 
     fn generate(
-        rrule_iter: &mut RRuleIter,
-        exrules: &mut [RRuleIter],
+        rrule_iter: &mut RRuleIter<TZ>,
+        exrules: &mut [RRuleIter<TZ>],
         exdates: &mut BTreeSet<i64>,
-        limited: bool,
-    ) -> (Option<DateTime<Tz>>, bool) {
+        limiter: &Limiter,
+    ) -> (Option<DateTime<TZ>>, bool) {
         let mut date = match rrule_iter.next() {
             Some(d) => d,
             None => return (None, false),
@@ -69,16 +108,9 @@ impl RRuleSetIter {
         let mut loop_counter: u32 = 0;
         while Self::is_date_excluded(&date, exrules, exdates) {
             // Prevent infinite loops
-            if limited {
-                loop_counter += 1;
-                if loop_counter >= MAX_ITER_LOOP {
-                    log::warn!(
-                        "Reached max loop counter (`{}`). \
-                    See 'validator limits' in docs for more info.",
-                        MAX_ITER_LOOP
-                    );
-                    return (None, true);
-                }
+            loop_counter += 1;
+            if limiter.is_exceeded(loop_counter) {
+                return (None, true);
             }
 
             date = match rrule_iter.next() {
@@ -91,8 +123,8 @@ impl RRuleSetIter {
     }
 
     fn is_date_excluded(
-        date: &DateTime<Tz>,
-        exrules: &mut [RRuleIter],
+        date: &DateTime<TZ>,
+        exrules: &mut [RRuleIter<TZ>],
         exdates: &mut BTreeSet<i64>,
     ) -> bool {
         for exrule in exrules {
@@ -108,16 +140,20 @@ impl RRuleSetIter {
     }
 }
 
-impl Iterator for RRuleSetIter {
-    type Item = DateTime<Tz>;
+impl<TZ: TimeZone> Iterator for RRuleSetIter<TZ> {
+    type Item = DateTime<TZ>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut next_date: Option<(usize, DateTime<Tz>)> = None;
+        let mut next_date: Option<(usize, DateTime<TZ>)> = None;
 
         // If there already was an error, return the error again.
         if self.was_limited {
             return None;
         }
+        if self.limiter.is_past_deadline() {
+            self.was_limited = true;
+            return None;
+        }
 
         for (i, rrule_iter) in self.rrule_iters.iter_mut().enumerate() {
             let rrule_queue = self.queue.remove(&i);
@@ -129,7 +165,7 @@ impl Iterator for RRuleSetIter {
                     rrule_iter,
                     &mut self.exrules,
                     &mut self.exdates,
-                    self.limited,
+                    &self.limiter,
                 );
 
                 if was_limited {
@@ -141,7 +177,7 @@ impl Iterator for RRuleSetIter {
             };
 
             if let Some(next_rrule_date) = next_rrule_date {
-                match next_date {
+                match next_date.take() {
                     None => next_date = Some((i, next_rrule_date)),
                     Some((idx, date)) => {
                         if date >= next_rrule_date {
@@ -153,6 +189,7 @@ impl Iterator for RRuleSetIter {
                         } else {
                             // Store for next iterations
                             self.queue.insert(i, next_rrule_date);
+                            next_date = Some((idx, date));
                         }
                     }
                 }
@@ -163,7 +200,7 @@ impl Iterator for RRuleSetIter {
             &mut self.rdates,
             &mut self.exrules,
             &mut self.exdates,
-            self.limited,
+            &self.limiter,
         );
         if was_limited {
             self.was_limited = true;
@@ -195,10 +232,10 @@ impl Iterator for RRuleSetIter {
     }
 }
 
-impl IntoIterator for &RRuleSet {
-    type Item = DateTime<Tz>;
+impl<TZ: TimeZone> IntoIterator for &RRuleSet<TZ> {
+    type Item = DateTime<TZ>;
 
-    type IntoIter = RRuleSetIter;
+    type IntoIter = RRuleSetIter<TZ>;
 
     fn into_iter(self) -> Self::IntoIter {
         // Sort in decreasing order
@@ -206,31 +243,37 @@ impl IntoIterator for &RRuleSet {
         rdates_sorted
             .sort_by(|d1, d2| d2.partial_cmp(d1).expect("Could not order dates correctly"));
 
-        let limited = self.limited;
+        let limiter = Limiter::new(&self.limits);
 
         RRuleSetIter {
             queue: HashMap::new(),
-            limited,
             rrule_iters: self
                 .rrule
                 .iter()
-                .map(|rrule| rrule.iter_with_ctx(self.dt_start, limited))
+                .map(|rrule| rrule.iter_with_ctx(self.dt_start.clone(), limiter.clone()))
                 .collect(),
             rdates: rdates_sorted,
             exrules: self
                 .exrule
                 .iter()
-                .map(|exrule| exrule.iter_with_ctx(self.dt_start, limited))
+                .map(|exrule| exrule.iter_with_ctx(self.dt_start.clone(), limiter.clone()))
                 .collect(),
             exdates: self.exdate.iter().map(DateTime::timestamp).collect(),
+            limiter,
             was_limited: false,
         }
     }
 }
 
-impl WasLimited for RRuleSetIter {
+impl<TZ: TimeZone> WasLimited for RRuleSetIter<TZ> {
     fn was_limited(&self) -> bool {
+        // An rrule stopped by the limits ends without limiting the set
         self.was_limited
+            || self
+                .rrule_iters
+                .iter()
+                .chain(&self.exrules)
+                .any(WasLimited::was_limited)
     }
 }
 
@@ -0,0 +1,167 @@
+use chrono::{DateTime, TimeZone};
+
+use super::rrule_iter::WasLimited;
+use super::{rrule_rev_iter::RRuleRevIter, Limiter};
+use crate::{RRuleError, RRuleSet, Tz};
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone)]
+/// Iterator over all the dates in an [`RRuleSet`] in reverse order, see [`RRuleSet::iter_rev`].
+pub struct RRuleSetRevIter<TZ: TimeZone = Tz> {
+    limiter: Limiter,
+    rrule_iters: Vec<RRuleRevIter<TZ>>,
+    exrules: Vec<RRuleRevIter<TZ>>,
+    exdates: BTreeSet<i64>,
+    /// Sorted additional dates in increasing order
+    rdates: Vec<DateTime<TZ>>,
+    after: Option<DateTime<TZ>>,
+    was_limited: bool,
+}
+
+impl<TZ: TimeZone> RRuleSetRevIter<TZ> {
+    pub(crate) fn new(rrule_set: &RRuleSet<TZ>) -> Result<Self, RRuleError> {
+        Self::bounded(
+            rrule_set,
+            rrule_set.before.as_ref(),
+            rrule_set.after.clone(),
+        )
+    }
+
+    /// Creates an iterator over the dates of `rrule_set` which are not after `before`
+    /// and not before `after`, instead of the `before` and `after` dates of the set.
+    pub(crate) fn bounded(
+        rrule_set: &RRuleSet<TZ>,
+        before: Option<&DateTime<TZ>>,
+        after: Option<DateTime<TZ>>,
+    ) -> Result<Self, RRuleError> {
+        let RRuleSet {
+            dt_start, limits, ..
+        } = rrule_set;
+        let limiter = Limiter::new(limits);
+
+        let mut rrule_iters = rrule_set
+            .rrule
+            .iter()
+            .map(|rrule| {
+                RRuleRevIter::new(rrule, dt_start, before.cloned(), &limiter).ok_or_else(|| {
+                    RRuleError::new_iter_err(
+                        "Iterating in reverse requires every `RRULE` to have a `COUNT` or `UNTIL`, \
+                        or the set to have a `before` date",
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut rdates: Vec<_> = rrule_set
+            .rdate
+            .iter()
+            .filter(|rdate| before.map_or(true, |before| *rdate <= before))
+            .cloned()
+            .collect();
+        rdates.sort();
+
+        // Exrules only need to go as far as the last date of the set
+        let end = rrule_iters
+            .iter_mut()
+            .filter_map(|rrule_iter| rrule_iter.peek().cloned())
+            .chain(rdates.last().cloned())
+            .max()
+            .unwrap_or_else(|| dt_start.clone());
+        let exrules = rrule_set
+            .exrule
+            .iter()
+            .filter_map(|exrule| RRuleRevIter::new(exrule, dt_start, Some(end.clone()), &limiter))
+            .collect();
+
+        Ok(Self {
+            limiter,
+            rrule_iters,
+            exrules,
+            exdates: rrule_set.exdate.iter().map(DateTime::timestamp).collect(),
+            rdates,
+            after,
+            was_limited: false,
+        })
+    }
+
+    /// Removes and returns the latest date of all the rrules and rdates.
+    fn next_date(&mut self) -> Option<DateTime<TZ>> {
+        let mut latest: Option<(Option<usize>, DateTime<TZ>)> =
+            self.rdates.last().map(|rdate| (None, rdate.clone()));
+        for (i, rrule_iter) in self.rrule_iters.iter_mut().enumerate() {
+            if let Some(date) = rrule_iter.peek() {
+                if latest.as_ref().map_or(true, |(_, latest)| date > latest) {
+                    latest = Some((Some(i), date.clone()));
+                }
+            }
+        }
+
+        match latest? {
+            (Some(i), _) => self.rrule_iters[i].next(),
+            (None, _) => self.rdates.pop(),
+        }
+    }
+
+    fn is_date_excluded(&mut self, date: &DateTime<TZ>) -> bool {
+        for exrule in &mut self.exrules {
+            while let Some(exdate) = exrule.peek() {
+                if exdate < date {
+                    break;
+                }
+                self.exdates.insert(exdate.timestamp());
+                exrule.next();
+            }
+        }
+
+        self.exdates.contains(&date.timestamp())
+    }
+}
+
+impl<TZ: TimeZone> Iterator for RRuleSetRevIter<TZ> {
+    type Item = DateTime<TZ>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // If there already was an error, return the error again.
+        if self.was_limited {
+            return None;
+        }
+        if self.limiter.is_past_deadline() {
+            self.was_limited = true;
+            return None;
+        }
+
+        let mut loop_counter: u32 = 0;
+        loop {
+            let date = self.next_date();
+            if self.rrule_iters.iter().any(WasLimited::was_limited) {
+                self.was_limited = true;
+                return None;
+            }
+            let date = date?;
+
+            if matches!(&self.after, Some(after) if date < *after) {
+                // All the next dates are before `after` as well
+                self.rrule_iters.clear();
+                self.rdates.clear();
+                return None;
+            }
+
+            if !self.is_date_excluded(&date) {
+                return Some(date);
+            }
+
+            // Prevent infinite loops
+            loop_counter += 1;
+            if self.limiter.is_exceeded(loop_counter) {
+                self.was_limited = true;
+                return None;
+            }
+        }
+    }
+}
+
+impl<TZ: TimeZone> WasLimited for RRuleSetRevIter<TZ> {
+    fn was_limited(&self) -> bool {
+        self.was_limited
+    }
+}
@@ -1,14 +1,14 @@
 use std::ops;
 
-use crate::core::{duration_from_midnight, Tz};
-use chrono::{NaiveDate, NaiveTime, Utc};
+use crate::core::duration_from_midnight;
+use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
 
 const DAY_SECS: i64 = 24 * 60 * 60;
 
 /// Converts number of days since unix epoch to a (naive) date.
 pub(crate) fn date_from_ordinal(ordinal: i64) -> NaiveDate {
     chrono::DateTime::<Utc>::from_timestamp(ordinal * DAY_SECS, 0)
-        .unwrap()
+        .expect("ordinal is within the range of supported dates")
         .date_naive()
 }
 
@@ -72,17 +72,20 @@ where
     }
 }
 
-pub(crate) fn add_time_to_date(
-    tz: Tz,
+pub(crate) fn add_time_to_date<TZ: TimeZone>(
+    tz: &TZ,
     date: NaiveDate,
     time: NaiveTime,
-) -> Option<chrono::DateTime<Tz>> {
-    if let Some(dt) = date.and_time(time).and_local_timezone(tz).single() {
+) -> Option<chrono::DateTime<TZ>> {
+    if let Some(dt) = date.and_time(time).and_local_timezone(tz.clone()).single() {
         return Some(dt);
     }
     // If the day is a daylight saving time, the above code might not work, and we
     // can try to get a valid datetime by adding the `time` as a duration instead.
-    let dt = date.and_hms_opt(0, 0, 0)?.and_local_timezone(tz).single()?;
+    let dt = date
+        .and_hms_opt(0, 0, 0)?
+        .and_local_timezone(tz.clone())
+        .single()?;
     let day_duration = duration_from_midnight(time);
     dt.checked_add_signed(day_duration)
 }
@@ -90,9 +93,10 @@ pub(crate) fn add_time_to_date(
 #[cfg(test)]
 mod test {
 
-    use chrono::{Duration, TimeZone};
+    use chrono::Duration;
 
     use super::*;
+    use crate::core::Tz;
 
     #[test]
     fn naive_date_from_ordinal() {
@@ -190,7 +194,7 @@ mod test {
         ];
 
         for (tz, date, time, expected_output) in tests {
-            let res = add_time_to_date(tz, date, time);
+            let res = add_time_to_date(&tz, date, time);
             assert_eq!(res, expected_output);
         }
     }
@@ -2,8 +2,10 @@ use super::{
     masks::MASKS,
     utils::{days_since_unix_epoch, get_year_len, pymod},
 };
-use crate::RRule;
+use crate::core::CalendarMonth;
+use crate::{RRule, RScale, Skip};
 use chrono::{Datelike, TimeZone, Utc};
+use std::borrow::Cow;
 
 #[derive(Debug)]
 pub(crate) struct BaseMasks {
@@ -36,25 +38,32 @@ fn base_year_masks(year_weekday: u16, year_len: u16) -> BaseMasks {
 
 #[derive(Debug, Clone)]
 pub(crate) struct YearInfo {
-    /// The year
+    /// The year, in the calendar of the rrule
     pub year: i32,
-    /// Number of days in the current year (365 or 366)
+    /// Number of days in the current year (365 or 366 in the Gregorian calendar)
     pub year_len: u16,
-    /// Number of days in the next year (365 or 366)
+    /// Number of days in the next year
     pub next_year_len: u16,
     /// Number of days since Unix epoch
     pub year_ordinal: i64,
-    pub month_mask: &'static [u8],
-    pub month_day_mask: &'static [i8],
-    pub neg_month_day_mask: &'static [i8],
-    pub month_range: &'static [u16],
-    pub weekday_mask: &'static [u32],
+    /// The month of each day, as index in the months of the year (starting at `1`)
+    pub month_mask: Cow<'static, [u8]>,
+    pub month_day_mask: Cow<'static, [i8]>,
+    pub neg_month_day_mask: Cow<'static, [i8]>,
+    pub month_range: Cow<'static, [u16]>,
+    pub weekday_mask: Cow<'static, [u32]>,
+    /// The months selected by `BYMONTH`, as index in the months of the year
+    pub selected_months: Vec<u8>,
     /// Week number mask
     pub week_no_mask: Option<Vec<u8>>,
 }
 
 impl YearInfo {
     pub fn new(year: i32, rrule: &RRule) -> Self {
+        if let Some(rscale) = rrule.calendar() {
+            return Self::new_calendar(year, rscale, rrule);
+        }
+
         // It should never fail, since there is always a 1st of January, is there?
         let first_year_day = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
 
@@ -72,11 +81,12 @@ impl YearInfo {
             next_year_len,
             year_ordinal,
             week_no_mask: None,
-            month_mask: base_masks.month_mask,
-            month_day_mask: base_masks.month_day_mask,
-            neg_month_day_mask: base_masks.neg_month_day_mask,
-            month_range: base_masks.month_range,
-            weekday_mask: base_masks.weekday_mask,
+            month_mask: Cow::Borrowed(base_masks.month_mask),
+            month_day_mask: Cow::Borrowed(base_masks.month_day_mask),
+            neg_month_day_mask: Cow::Borrowed(base_masks.neg_month_day_mask),
+            month_range: Cow::Borrowed(base_masks.month_range),
+            weekday_mask: Cow::Borrowed(base_masks.weekday_mask),
+            selected_months: rrule.by_month.clone(),
         };
 
         if rrule.by_week_no.is_empty() {
@@ -207,4 +217,102 @@ impl YearInfo {
 
         result
     }
+
+    /// Creates the masks of a year in a non-Gregorian calendar.
+    ///
+    /// `BYWEEKNO` can't be used with these calendars, so there is no week number mask.
+    fn new_calendar(year: i32, rscale: RScale, rrule: &RRule) -> Self {
+        let months = rscale.months(year);
+        let next_months = rscale.months(year + 1);
+        let year_len: u16 = months.iter().map(|month| month.len).sum();
+        let next_year_len: u16 = next_months.iter().map(|month| month.len).sum();
+        let year_ordinal = rscale.year_start(year);
+
+        // Every mask is 7 days longer to handle cross-year weekly periods.
+        let mask_len = usize::from(year_len) + 7;
+        let mut month_mask = Vec::with_capacity(mask_len);
+        let mut month_day_mask = Vec::with_capacity(mask_len);
+        let mut neg_month_day_mask = Vec::with_capacity(mask_len);
+        let mut month_range = vec![0];
+        let next_first_month = next_months.first().map(|month| (1, month));
+        for (index, month) in (1..).zip(&months).chain(next_first_month) {
+            let len = i8::try_from(month.len).expect("months have at most 31 days");
+            for day in 1..=len {
+                month_mask.push(index);
+                month_day_mask.push(day);
+                neg_month_day_mask.push(day - len - 1);
+            }
+            if month_range.len() <= months.len() {
+                month_range.push(month_range[month_range.len() - 1] + month.len);
+            }
+        }
+        month_mask.truncate(mask_len);
+        month_day_mask.truncate(mask_len);
+        neg_month_day_mask.truncate(mask_len);
+
+        // The Unix epoch is on a Thursday
+        let year_start_weekday = u32::try_from((year_ordinal + 3).rem_euclid(7))
+            .expect("7 is the modulus, so the range is 0-6, and u32 covers that range");
+        let weekday_mask = (0..u32::from(year_len) + 14)
+            .map(|day| (year_start_weekday + day) % 7)
+            .collect();
+
+        Self {
+            year,
+            year_len,
+            next_year_len,
+            year_ordinal,
+            month_mask: Cow::Owned(month_mask),
+            month_day_mask: Cow::Owned(month_day_mask),
+            neg_month_day_mask: Cow::Owned(neg_month_day_mask),
+            month_range: Cow::Owned(month_range),
+            weekday_mask: Cow::Owned(weekday_mask),
+            selected_months: selected_calendar_months(&months, rrule),
+            week_no_mask: None,
+        }
+    }
+}
+
+/// Returns the index of the months of the year which are selected by `BYMONTH`.
+///
+/// A leap month which is missing in the year is replaced by the month with the same number
+/// when skipping backward, and by the month after it when skipping forward.
+fn selected_calendar_months(months: &[CalendarMonth], rrule: &RRule) -> Vec<u8> {
+    let position = |number: u8, leap: bool| {
+        months
+            .iter()
+            .position(|month| month.number == number && month.leap == leap)
+    };
+
+    let mut selected = vec![];
+    for (index, month) in months.iter().enumerate() {
+        let by_month = if month.leap {
+            &rrule.by_leap_month
+        } else {
+            &rrule.by_month
+        };
+        if by_month.contains(&month.number) {
+            selected.push(index);
+        }
+    }
+    for leap_month in &rrule.by_leap_month {
+        if position(*leap_month, true).is_some() {
+            continue;
+        }
+        let replacement = match rrule.skip {
+            Some(Skip::Backward) => position(*leap_month, false),
+            Some(Skip::Forward) => position(*leap_month, false)
+                .map(|index| index + 1)
+                .filter(|index| *index < months.len()),
+            Some(Skip::Omit) | None => None,
+        };
+        selected.extend(replacement);
+    }
+    selected.sort_unstable();
+    selected.dedup();
+
+    selected
+        .into_iter()
+        .map(|index| u8::try_from(index + 1).expect("a year has at most 13 months"))
+        .collect()
 }
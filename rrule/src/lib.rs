@@ -36,9 +36,13 @@
 //! assert_eq!(rrule_set.get_rrule().len(), 2);
 //! ```
 //!
+//! Whole iCalendar objects (e.g. `.ics` files) can be parsed with [`VCalendar`], which returns
+//! a [`VEvent`] with its own [`RRuleSet`] for every `VEVENT` component found in the input.
+//!
 //! # Generating occurrences
 //! You can loop over the occurrences of a [`RRuleSet`] by calling any of the following methods:
 //! - [`RRuleSet::all`]: Generate all recurrences that match the rules (with a limit to prevent infinite loops).
+//! - [`RRuleSet::all_within_limits`]: Generate all recurrences that match the rules, within the [`IterLimits`] of the set.
 //! - [`RRuleSet::all_unchecked`]: Generate all recurrences that match the rules (without a limit).
 //! - ...
 //!
@@ -98,10 +102,27 @@ mod error;
 mod iter;
 mod parser;
 mod tests;
+#[cfg(feature = "text")]
+mod text;
 mod validator;
 
-pub use crate::core::{Frequency, NWeekday, RRule, RRuleResult, RRuleSet, Tz};
+pub use crate::core::{EventDuration, FromDateTime, IntoDateTime};
+pub use crate::core::{
+    Frequency, NWeekday, Occurrence, RRule, RRuleResult, RRuleSet, RScale, Skip, Tz,
+};
+#[cfg(feature = "jiff")]
+pub use crate::core::{JiffOffset, JiffTz};
+pub use crate::core::{Observance, ObservanceKind, VTimezone};
+pub use crate::core::{Property, VCalendar, VEvent};
+pub use crate::core::{RecurrenceOverride, RecurrenceRange};
 pub use crate::core::{Unvalidated, Validated};
 pub use chrono::Weekday;
-pub use error::{ParseError, RRuleError, ValidationError};
-pub use iter::RRuleSetIter;
+pub use error::{Diagnostic, FieldError, Location, ParseError, RRuleError, ValidationError};
+pub use iter::{
+    BetweenIter, IterLimits, OccurrenceIter, RRuleSetConvertIter, RRuleSetIter, RRuleSetRevIter,
+};
+#[cfg(feature = "text")]
+pub use text::{
+    Conjunction, English, French, German, Japanese, Locale, Phrase, Spanish, TextError,
+    TextErrorKind,
+};
@@ -0,0 +1,239 @@
+//! Parsing of iCalendar components, i.e. the blocks delimited by `BEGIN:<NAME>` and `END:<NAME>`,
+//! as they are found in `.ics` files.
+
+use super::{
+    utils::{unfold_lines, UnfoldedLine},
+    Located, ParseError,
+};
+
+/// A single content line of a component, split into its name, parameters and value.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ComponentProperty {
+    /// The unfolded input line of the property.
+    pub source: UnfoldedLine<'static>,
+    pub name: String,
+    pub parameters: Option<String>,
+    pub value: String,
+}
+
+impl ComponentProperty {
+    /// Splits a content line of the form `NAME[;PARAMETERS]:VALUE` into its parts.
+    ///
+    /// Colons and semicolons inside quoted parameter values are not treated as delimiters.
+    pub(crate) fn new(source: UnfoldedLine<'_>) -> Result<Self, Located> {
+        let content = &source.content;
+        let mut in_quotes = false;
+        let mut name_end = None;
+        let mut value_start = None;
+
+        for (idx, c) in content.char_indices() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                ';' if !in_quotes && name_end.is_none() => name_end = Some(idx),
+                ':' if !in_quotes => {
+                    value_start = Some(idx);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let invalid_line = || ParseError::InvalidContentLine(content.to_string()).at(&source, None);
+        let value_start = value_start.ok_or_else(invalid_line)?;
+        let name_end = name_end.unwrap_or(value_start);
+        let name = &content[..name_end];
+        if name.is_empty() {
+            return Err(invalid_line());
+        }
+        let parameters = (name_end < value_start).then(|| &content[name_end + 1..value_start]);
+
+        Ok(Self {
+            name: name.into(),
+            parameters: parameters.map(Into::into),
+            value: content[value_start + 1..].into(),
+            source: source.into_owned(),
+        })
+    }
+}
+
+/// A component like `VCALENDAR` or `VEVENT` with its properties and nested components.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Component {
+    /// The uppercased name of the component, e.g. `VEVENT`.
+    pub name: String,
+    pub properties: Vec<ComponentProperty>,
+    pub components: Vec<Self>,
+}
+
+impl Component {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_uppercase(),
+            properties: vec![],
+            components: vec![],
+        }
+    }
+
+    /// Parses the input into a root component without a name, which holds all the
+    /// top level components (and properties) found in the input.
+    pub(crate) fn parse_root(s: &str) -> Result<Self, Located> {
+        // Stack of open components, the root component is always at the bottom.
+        let mut stack = vec![Self::new("")];
+
+        for unfolded in unfold_lines(s) {
+            if unfolded.content.trim().is_empty() {
+                continue;
+            }
+            let property = ComponentProperty::new(unfolded)?;
+
+            if property.name.eq_ignore_ascii_case("BEGIN") {
+                stack.push(Self::new(property.value.trim()));
+            } else if property.name.eq_ignore_ascii_case("END") {
+                let name = property.value.trim().to_uppercase();
+                if stack.len() < 2 || stack.last().map(|c| &c.name) != Some(&name) {
+                    return Err(ParseError::UnexpectedComponentEnd(name)
+                        .at(&property.source, Some(&property.name)));
+                }
+                let component = stack.pop().expect("stack has at least 2 components");
+                if let Some(parent) = stack.last_mut() {
+                    parent.components.push(component);
+                }
+            } else if let Some(current) = stack.last_mut() {
+                current.properties.push(property);
+            }
+        }
+
+        let last = stack.pop().expect("root component is never popped");
+        if !stack.is_empty() {
+            return Err(ParseError::MissingComponentEnd(last.name).into());
+        }
+        Ok(last)
+    }
+
+    /// Returns all the nested components with the given name, searching the whole tree.
+    pub(crate) fn find_components(&self, name: &str) -> Vec<&Self> {
+        let mut found = vec![];
+        for component in &self.components {
+            if component.name.eq_ignore_ascii_case(name) {
+                found.push(component);
+            } else {
+                found.extend(component.find_components(name));
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Location;
+
+    #[test]
+    fn splits_content_lines() {
+        let tests = [
+            ("SUMMARY:Weekly meeting", "SUMMARY", None, "Weekly meeting"),
+            (
+                "DTSTART;TZID=Europe/Berlin:20120201T093000",
+                "DTSTART",
+                Some("TZID=Europe/Berlin"),
+                "20120201T093000",
+            ),
+            (
+                "ATTENDEE;CN=\"Doe; John: Jr\":mailto:john@example.com",
+                "ATTENDEE",
+                Some("CN=\"Doe; John: Jr\""),
+                "mailto:john@example.com",
+            ),
+        ];
+        for (input, name, parameters, value) in tests {
+            let source = UnfoldedLine {
+                line: 3,
+                content: input.into(),
+                folds: vec![],
+            };
+            let expected_output = ComponentProperty {
+                source: source.clone().into_owned(),
+                name: name.into(),
+                parameters: parameters.map(Into::into),
+                value: value.into(),
+            };
+            assert_eq!(ComponentProperty::new(source), Ok(expected_output));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_content_lines() {
+        for input in ["SUMMARY", ":value", "ATTENDEE;CN=\"a:b"] {
+            let source = UnfoldedLine {
+                line: 1,
+                content: input.into(),
+                folds: vec![],
+            };
+            assert_eq!(
+                ComponentProperty::new(source),
+                Err(
+                    ParseError::InvalidContentLine(input.into()).located(Location {
+                        line: 1,
+                        span: 0..input.len(),
+                        property: None,
+                    })
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn parses_nested_components() {
+        let input = "BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nUID:1\nBEGIN:VALARM\nACTION:DISPLAY\nEND:VALARM\nEND:VEVENT\nEND:VCALENDAR";
+        let root = Component::parse_root(input).unwrap();
+
+        assert_eq!(root.components.len(), 1);
+        let calendar = &root.components[0];
+        assert_eq!(calendar.name, "VCALENDAR");
+        assert_eq!(calendar.properties[0].name, "VERSION");
+
+        let events = root.find_components("VEVENT");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].properties[0].value, "1");
+        assert_eq!(events[0].components[0].name, "VALARM");
+    }
+
+    #[test]
+    fn parses_folded_properties() {
+        let input = "BEGIN:VEVENT\r\nSUMMARY:A very\r\n  long summary\r\nUID:1\r\nEND:VEVENT\r\n";
+        let root = Component::parse_root(input).unwrap();
+
+        let event = &root.components[0];
+        assert_eq!(event.properties[0].value, "A very long summary");
+        assert_eq!(event.properties[1].source.line, 4);
+    }
+
+    #[test]
+    fn rejects_unbalanced_components() {
+        assert_eq!(
+            Component::parse_root("BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VCALENDAR"),
+            Err(
+                ParseError::UnexpectedComponentEnd("VCALENDAR".into()).located(Location {
+                    line: 3,
+                    span: 4..13,
+                    property: Some("END".into()),
+                })
+            )
+        );
+        assert_eq!(
+            Component::parse_root("END:VEVENT"),
+            Err(
+                ParseError::UnexpectedComponentEnd("VEVENT".into()).located(Location {
+                    line: 1,
+                    span: 4..10,
+                    property: Some("END".into()),
+                })
+            )
+        );
+        assert_eq!(
+            Component::parse_root("BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VEVENT"),
+            Err(ParseError::MissingComponentEnd("VCALENDAR".into()).into())
+        );
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
         datetime::{datestring_to_date, parse_timezone},
         ParseError,
     },
-    Tz,
+    Tz, VTimezone,
 };
 
 use super::{content_line_parts::ContentLineCaptures, parameters::parse_parameters};
@@ -35,56 +35,64 @@ impl TryFrom<ContentLineCaptures<'_>> for Vec<chrono::DateTime<Tz>> {
     type Error = ParseError;
 
     fn try_from(value: ContentLineCaptures) -> Result<Self, Self::Error> {
-        let parameters: HashMap<DateParameter, String> = value
-            .parameters
-            .map(parse_parameters)
-            .transpose()?
-            .unwrap_or_default();
-
-        match parameters
-            .get(&DateParameter::Value)
-            .map(|val| val.to_ascii_lowercase())
-            .as_deref()
-        {
-            Some("date") => {
-                warn!(
+        parse_date_content_line(value, &[])
+    }
+}
+
+/// Parses an `RDATE` or `EXDATE` line, looking up its `TZID` in `custom_timezones` first.
+pub(crate) fn parse_date_content_line(
+    value: ContentLineCaptures,
+    custom_timezones: &[&'static VTimezone],
+) -> Result<Vec<chrono::DateTime<Tz>>, ParseError> {
+    let parameters: HashMap<DateParameter, String> = value
+        .parameters
+        .map(parse_parameters)
+        .transpose()?
+        .unwrap_or_default();
+
+    match parameters
+        .get(&DateParameter::Value)
+        .map(|val| val.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("date") => {
+            warn!(
                     "Parameter `DATE` is not supported for property name: `{}`. The dates will be interpreter with the `DATE-TIME` parameter instead.",
                     value.property_name
                 );
-            }
-            Some("period") => {
-                warn!(
+        }
+        Some("period") => {
+            warn!(
                     "Parameter `PERIOD` is not supported for property name: `{}`. The dates will be interpreter with the `DATE-TIME` parameter instead.",
                     value.property_name
                 );
-            }
-            Some("date-time") => {}
-            Some(param) => {
-                warn!(
-                    "Encountered unexpected parameter `{param}` for property name: `{}`",
-                    value.property_name
-                );
-            }
-            None => {}
         }
-
-        let timezone = parameters
-            .get(&DateParameter::Timezone)
-            .map(|tz| parse_timezone(tz))
-            .transpose()?;
-        let property = format!("{}", value.property_name);
-
-        let mut dates = vec![];
-        for val in value.value.split(',') {
-            if val.is_empty() {
-                continue;
-            }
-            let datetime = datestring_to_date(val, timezone, &property)?;
-            dates.push(datetime);
+        Some("date-time") => {}
+        Some(param) => {
+            warn!(
+                "Encountered unexpected parameter `{param}` for property name: `{}`",
+                value.property_name
+            );
         }
+        None => {}
+    }
+
+    let timezone = parameters
+        .get(&DateParameter::Timezone)
+        .map(|tz| parse_timezone(tz, custom_timezones))
+        .transpose()?;
+    let property = format!("{}", value.property_name);
 
-        Ok(dates)
+    let mut dates = vec![];
+    for val in value.value.split(',') {
+        if val.is_empty() {
+            continue;
+        }
+        let datetime = datestring_to_date(val, timezone, &property)?;
+        dates.push(datetime);
     }
+
+    Ok(dates)
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 mod content_line_parts;
 mod date_content_line;
 mod parameters;
+mod recurrence_id_content_line;
 mod rule_content_line;
 mod start_date_content_line;
 
@@ -12,6 +13,9 @@ use crate::Tz;
 use crate::Unvalidated;
 
 pub(crate) use content_line_parts::ContentLineCaptures;
+pub(crate) use date_content_line::parse_date_content_line;
+pub(crate) use recurrence_id_content_line::parse_recurrence_id;
+pub(crate) use rule_content_line::parse_rule_parts;
 pub(crate) use start_date_content_line::StartDateContentLine;
 
 use super::ParseError;
@@ -31,6 +35,8 @@ pub(crate) enum PropertyName {
     ExDate,
     RDate,
     DtStart,
+    DtEnd,
+    Duration,
 }
 
 impl Display for PropertyName {
@@ -41,6 +47,8 @@ impl Display for PropertyName {
             Self::ExDate => write!(f, "EXDATE"),
             Self::RDate => write!(f, "RDATE"),
             Self::DtStart => write!(f, "DTSTART"),
+            Self::DtEnd => write!(f, "DTEND"),
+            Self::Duration => write!(f, "DURATION"),
         }
     }
 }
@@ -55,6 +63,8 @@ impl FromStr for PropertyName {
             "RDATE" => Self::RDate,
             "EXDATE" => Self::ExDate,
             "DTSTART" => Self::DtStart,
+            "DTEND" => Self::DtEnd,
+            "DURATION" => Self::Duration,
             _ => return Err(ParseError::UnrecognizedPropertyName(s.into())),
         };
         Ok(name)
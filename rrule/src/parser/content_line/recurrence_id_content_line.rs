@@ -0,0 +1,109 @@
+use std::{collections::HashMap, str::FromStr};
+
+use super::parameters::parse_parameters;
+use crate::{
+    core::{Tz, VTimezone},
+    parser::{
+        datetime::{datestring_to_date, parse_timezone},
+        ParseError,
+    },
+    RecurrenceRange,
+};
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+enum RecurrenceIdParameter {
+    Timezone,
+    Value,
+    Range,
+}
+
+impl FromStr for RecurrenceIdParameter {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let param = match &s.to_uppercase()[..] {
+            "TZID" => Self::Timezone,
+            "VALUE" => Self::Value,
+            "RANGE" => Self::Range,
+            _ => return Err(ParseError::UnrecognizedParameter(s.into())),
+        };
+        Ok(param)
+    }
+}
+
+/// Parses the parameters and value of a `RECURRENCE-ID` property into the original start of
+/// the instance and the range of instances it modifies, looking up its `TZID` in
+/// `custom_timezones` first.
+pub(crate) fn parse_recurrence_id(
+    parameters: Option<&str>,
+    value: &str,
+    custom_timezones: &[&'static VTimezone],
+) -> Result<(chrono::DateTime<Tz>, RecurrenceRange), ParseError> {
+    let parameters: HashMap<RecurrenceIdParameter, String> = parameters
+        .map(parse_parameters)
+        .transpose()?
+        .unwrap_or_default();
+
+    let timezone = parameters
+        .get(&RecurrenceIdParameter::Timezone)
+        .map(|tz| parse_timezone(tz, custom_timezones))
+        .transpose()?;
+    let range = parameters
+        .get(&RecurrenceIdParameter::Range)
+        .map(|range| range.parse())
+        .transpose()?
+        .unwrap_or_default();
+    let datetime = datestring_to_date(value, timezone, "RECURRENCE-ID")?;
+
+    Ok((datetime, range))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn parses_recurrence_id() {
+        let berlin = Tz::Europe__Berlin;
+        let tests = [
+            (
+                None,
+                "20230110T090000Z",
+                Tz::UTC.with_ymd_and_hms(2023, 1, 10, 9, 0, 0).unwrap(),
+                RecurrenceRange::ThisInstance,
+            ),
+            (
+                Some("TZID=Europe/Berlin;RANGE=THISANDFUTURE"),
+                "20230110T090000",
+                berlin.with_ymd_and_hms(2023, 1, 10, 9, 0, 0).unwrap(),
+                RecurrenceRange::ThisAndFuture,
+            ),
+            (
+                Some("VALUE=DATE"),
+                "20230110",
+                Tz::LOCAL.with_ymd_and_hms(2023, 1, 10, 0, 0, 0).unwrap(),
+                RecurrenceRange::ThisInstance,
+            ),
+        ];
+        for (parameters, value, datetime, range) in tests {
+            assert_eq!(
+                parse_recurrence_id(parameters, value, &[]),
+                Ok((datetime, range))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_recurrence_id() {
+        assert_eq!(
+            parse_recurrence_id(Some("RANGE=THISANDPRIOR"), "20230110T090000Z", &[]),
+            Err(ParseError::InvalidRecurrenceRange("THISANDPRIOR".into()))
+        );
+        assert_eq!(
+            parse_recurrence_id(Some("LANGUAGE=en"), "20230110T090000Z", &[]),
+            Err(ParseError::UnrecognizedParameter("LANGUAGE".into()))
+        );
+    }
+}
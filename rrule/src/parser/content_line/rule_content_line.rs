@@ -1,4 +1,9 @@
-use std::{collections::HashMap, marker::PhantomData, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+    str::FromStr,
+};
 
 use chrono::Weekday;
 
@@ -10,7 +15,7 @@ use crate::{
         utils::parse_str_to_vec,
         ParseError,
     },
-    Frequency, RRule, Unvalidated,
+    Frequency, RRule, RScale, Skip, Unvalidated,
 };
 
 use super::content_line_parts::ContentLineCaptures;
@@ -33,6 +38,8 @@ pub enum RRuleProperty {
     Wkst,
     #[cfg(feature = "by-easter")]
     ByEaster,
+    RScale,
+    Skip,
 }
 
 impl FromStr for RRuleProperty {
@@ -56,151 +63,183 @@ impl FromStr for RRuleProperty {
             "WKST" => Self::Wkst,
             #[cfg(feature = "by-easter")]
             "BYEASTER" => Self::ByEaster,
+            "RSCALE" => Self::RScale,
+            "SKIP" => Self::Skip,
             _ => return Err(ParseError::UnrecognizedParameter(s.into())),
         };
         Ok(prop)
     }
 }
 
+impl Display for RRuleProperty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Freq => "FREQ",
+            Self::Until => "UNTIL",
+            Self::Count => "COUNT",
+            Self::Interval => "INTERVAL",
+            Self::BySecond => "BYSECOND",
+            Self::ByMinute => "BYMINUTE",
+            Self::ByHour => "BYHOUR",
+            Self::ByDay => "BYDAY",
+            Self::ByMonthDay => "BYMONTHDAY",
+            Self::ByYearDay => "BYYEARDAY",
+            Self::ByWeekNo => "BYWEEKNO",
+            Self::ByMonth => "BYMONTH",
+            Self::BySetPos => "BYSETPOS",
+            Self::Wkst => "WKST",
+            #[cfg(feature = "by-easter")]
+            Self::ByEaster => "BYEASTER",
+            Self::RScale => "RSCALE",
+            Self::Skip => "SKIP",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 impl TryFrom<ContentLineCaptures<'_>> for RRule<Unvalidated> {
     type Error = ParseError;
 
     fn try_from(value: ContentLineCaptures) -> Result<Self, Self::Error> {
-        if let Some(parameters) = value.parameters {
-            if !parameters.is_empty() {
-                return Err(ParseError::PropertyParametersNotSupported(
-                    parameters.into(),
-                ));
-            }
+        parse_rule_parts(value).map_err(|mut errors| errors.swap_remove(0).1)
+    }
+}
+
+/// Parses an `RRULE` or `EXRULE` content line like [`RRule::try_from`], but returns the errors
+/// of all the rule parts instead of only the first one, each with the name of its part. Errors
+/// of the whole line have the name of the property instead.
+pub(crate) fn parse_rule_parts(
+    value: ContentLineCaptures,
+) -> Result<RRule<Unvalidated>, Vec<(String, ParseError)>> {
+    let property_name = value.property_name.to_string();
+    if let Some(parameters) = value.parameters {
+        if !parameters.is_empty() {
+            return Err(vec![(
+                property_name,
+                ParseError::PropertyParametersNotSupported(parameters.into()),
+            )]);
         }
+    }
 
-        let properties: HashMap<RRuleProperty, String> = parse_parameters(value.value)?;
+    let properties: HashMap<RRuleProperty, String> =
+        parse_parameters(value.value).map_err(|err| vec![(property_name, err)])?;
 
-        props_to_rrule(&properties)
-    }
+    props_to_rrule(&properties)
+}
+
+/// Parses the value of the rule part `property`, if it is set, with `parse`. The error is added
+/// to `errors` instead of being returned, so that the other parts can still be parsed.
+fn parse_part<T>(
+    props: &HashMap<RRuleProperty, String>,
+    property: RRuleProperty,
+    errors: &mut Vec<(String, ParseError)>,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> Option<T> {
+    let value = props.get(&property)?;
+    parse(value)
+        .map_err(|err| errors.push((property.to_string(), err)))
+        .ok()
 }
 
-/// Takes a map of [`RRuleProperty`] and returns an [`RRule`].
+/// Takes a map of [`RRuleProperty`] and returns an [`RRule`], or the errors of all the parts.
 #[allow(clippy::too_many_lines)]
 fn props_to_rrule(
     props: &HashMap<RRuleProperty, String>,
-) -> Result<RRule<Unvalidated>, ParseError> {
-    let freq = props
-        .get(&RRuleProperty::Freq)
-        .map(|freq| Frequency::from_str(freq))
-        .transpose()?
-        .ok_or_else(|| ParseError::MissingProperty("FREQ".into()))?;
-    let interval = props
-        .get(&RRuleProperty::Interval)
-        .map(|interval| {
-            interval
-                .parse()
-                .map_err(|_| ParseError::InvalidInterval(interval.into()))
-        })
-        .transpose()?
-        .unwrap_or(1);
-    let count = props
-        .get(&RRuleProperty::Count)
-        .map(|count| {
-            count
-                .parse()
-                .map_err(|_| ParseError::InvalidCount(count.into()))
-        })
-        .transpose()?;
-    let until = props
-        .get(&RRuleProperty::Until)
-        .map(|until| datestring_to_date(until, None, "UNTIL"))
-        .transpose()?;
-    let week_start = props
-        .get(&RRuleProperty::Wkst)
-        .map(|week_start| {
-            str_to_weekday(week_start)
-                .map_err(|_| ParseError::InvalidWeekdayStart(week_start.into()))
-        })
-        .transpose()?
-        .unwrap_or(Weekday::Mon);
-    let by_set_pos = props
-        .get(&RRuleProperty::BySetPos)
-        .map(|by_set_pos| {
-            parse_str_to_vec(by_set_pos, |_| true)
-                .map_err(|_| ParseError::InvalidBySetPos(by_set_pos.into()))
-        })
-        .transpose()?
-        .unwrap_or_default();
-    let by_month = props
-        .get(&RRuleProperty::ByMonth)
-        .map(|by_month| {
-            parse_str_to_vec(by_month, |month| (1..=12).contains(&month))
-                .map_err(|_| ParseError::InvalidByMonth(by_month.into()))
+) -> Result<RRule<Unvalidated>, Vec<(String, ParseError)>> {
+    let mut errors = vec![];
+
+    let freq = parse_part(props, RRuleProperty::Freq, &mut errors, Frequency::from_str);
+    if !props.contains_key(&RRuleProperty::Freq) {
+        errors.push((
+            RRuleProperty::Freq.to_string(),
+            ParseError::MissingProperty("FREQ".into()),
+        ));
+    }
+    let interval = parse_part(props, RRuleProperty::Interval, &mut errors, |interval| {
+        interval
+            .parse()
+            .map_err(|_| ParseError::InvalidInterval(interval.into()))
+    })
+    .unwrap_or(1);
+    let count = parse_part(props, RRuleProperty::Count, &mut errors, |count| {
+        count
+            .parse()
+            .map_err(|_| ParseError::InvalidCount(count.into()))
+    });
+    let until = parse_part(props, RRuleProperty::Until, &mut errors, |until| {
+        datestring_to_date(until, None, "UNTIL")
+    });
+    let week_start = parse_part(props, RRuleProperty::Wkst, &mut errors, |week_start| {
+        str_to_weekday(week_start).map_err(|_| ParseError::InvalidWeekdayStart(week_start.into()))
+    })
+    .unwrap_or(Weekday::Mon);
+    let by_set_pos = parse_part(props, RRuleProperty::BySetPos, &mut errors, |by_set_pos| {
+        parse_str_to_vec(by_set_pos, |_| true)
+            .map_err(|_| ParseError::InvalidBySetPos(by_set_pos.into()))
+    })
+    .unwrap_or_default();
+    let (by_month, by_leap_month) =
+        parse_part(props, RRuleProperty::ByMonth, &mut errors, |by_month| {
+            parse_by_month(by_month).map_err(|_| ParseError::InvalidByMonth(by_month.into()))
         })
-        .transpose()?
         .unwrap_or_default();
-    let by_month_day = props
-        .get(&RRuleProperty::ByMonthDay)
-        .map(|by_month_day| {
+    let by_month_day = parse_part(
+        props,
+        RRuleProperty::ByMonthDay,
+        &mut errors,
+        |by_month_day| {
             parse_str_to_vec(by_month_day, |monthday| (-31..=31).contains(&monthday))
                 .map_err(|_| ParseError::InvalidByMonthDay(by_month_day.into()))
-        })
-        .transpose()?
-        .unwrap_or_default();
-    let by_year_day = props
-        .get(&RRuleProperty::ByYearDay)
-        .map(|by_year_day| {
+        },
+    )
+    .unwrap_or_default();
+    let by_year_day = parse_part(
+        props,
+        RRuleProperty::ByYearDay,
+        &mut errors,
+        |by_year_day| {
             parse_str_to_vec(by_year_day, |yearday| (-366..=366).contains(&yearday))
                 .map_err(|_| ParseError::InvalidByYearDay(by_year_day.into()))
-        })
-        .transpose()?
-        .unwrap_or_default();
-    let by_week_no = props
-        .get(&RRuleProperty::ByWeekNo)
-        .map(|by_week_no| {
-            parse_str_to_vec(by_week_no, |weekno| (-53..=53).contains(&weekno))
-                .map_err(|_| ParseError::InvalidByWeekNo(by_week_no.into()))
-        })
-        .transpose()?
-        .unwrap_or_default();
-    let by_weekday = props
-        .get(&RRuleProperty::ByDay)
-        .map(|by_weekday| parse_weekdays(by_weekday))
-        .transpose()?
-        .unwrap_or_default();
-    let by_hour = props
-        .get(&RRuleProperty::ByHour)
-        .map(|by_hour| {
-            parse_str_to_vec(by_hour, |hour| hour < 24)
-                .map_err(|_| ParseError::InvalidByHour(by_hour.into()))
-        })
-        .transpose()?
-        .unwrap_or_default();
-    let by_minute = props
-        .get(&RRuleProperty::ByMinute)
-        .map(|by_minute| {
-            parse_str_to_vec(by_minute, |minute| minute < 60)
-                .map_err(|_| ParseError::InvalidByMinute(by_minute.into()))
-        })
-        .transpose()?
-        .unwrap_or_default();
-    let by_second = props
-        .get(&RRuleProperty::BySecond)
-        .map(|by_second| {
-            parse_str_to_vec(by_second, |second| second < 60)
-                .map_err(|_| ParseError::InvalidBySecond(by_second.into()))
-        })
-        .transpose()?
-        .unwrap_or_default();
+        },
+    )
+    .unwrap_or_default();
+    let by_week_no = parse_part(props, RRuleProperty::ByWeekNo, &mut errors, |by_week_no| {
+        parse_str_to_vec(by_week_no, |weekno| (-53..=53).contains(&weekno))
+            .map_err(|_| ParseError::InvalidByWeekNo(by_week_no.into()))
+    })
+    .unwrap_or_default();
+    let by_weekday =
+        parse_part(props, RRuleProperty::ByDay, &mut errors, parse_weekdays).unwrap_or_default();
+    let by_hour = parse_part(props, RRuleProperty::ByHour, &mut errors, |by_hour| {
+        parse_str_to_vec(by_hour, |hour| hour < 24)
+            .map_err(|_| ParseError::InvalidByHour(by_hour.into()))
+    })
+    .unwrap_or_default();
+    let by_minute = parse_part(props, RRuleProperty::ByMinute, &mut errors, |by_minute| {
+        parse_str_to_vec(by_minute, |minute| minute < 60)
+            .map_err(|_| ParseError::InvalidByMinute(by_minute.into()))
+    })
+    .unwrap_or_default();
+    let by_second = parse_part(props, RRuleProperty::BySecond, &mut errors, |by_second| {
+        parse_str_to_vec(by_second, |second| second < 60)
+            .map_err(|_| ParseError::InvalidBySecond(by_second.into()))
+    })
+    .unwrap_or_default();
 
     #[cfg(not(feature = "by-easter"))]
     let by_easter = None;
     #[cfg(feature = "by-easter")]
-    let by_easter = props
-        .get(&RRuleProperty::ByEaster)
-        .map(|new_by_easter: &String| {
-            i16::from_str(new_by_easter)
-                .map_err(|_| ParseError::InvalidByEaster(new_by_easter.into()))
-        })
-        .transpose()?;
+    let by_easter = parse_part(props, RRuleProperty::ByEaster, &mut errors, |by_easter| {
+        i16::from_str(by_easter).map_err(|_| ParseError::InvalidByEaster(by_easter.into()))
+    });
+
+    let rscale = parse_part(props, RRuleProperty::RScale, &mut errors, RScale::from_str);
+    let skip = parse_part(props, RRuleProperty::Skip, &mut errors, Skip::from_str);
 
     // Check if mandatory fields are set
+    let Some(freq) = freq.filter(|_| errors.is_empty()) else {
+        return Err(errors);
+    };
     Ok(RRule {
         freq,
         interval,
@@ -218,10 +257,29 @@ fn props_to_rrule(
         by_minute,
         by_second,
         by_easter,
+        rscale,
+        skip,
+        by_leap_month,
         stage: PhantomData,
     })
 }
 
+/// Parses the months of `BYMONTH`, and returns the leap months (e.g. `5L`) separately.
+fn parse_by_month(by_month: &str) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let (leap_months, months): (Vec<_>, Vec<_>) = by_month
+        .split(',')
+        .partition(|month| month.ends_with(['L', 'l']));
+    let leap_months = leap_months
+        .into_iter()
+        .map(|month| &month[..month.len() - 1])
+        .collect::<Vec<_>>();
+    let is_month = |month| (1..=12).contains(&month);
+    Ok((
+        parse_str_to_vec(&months.join(","), is_month)?,
+        parse_str_to_vec(&leap_months.join(","), is_month)?,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::content_line::{ContentLineCaptures, PropertyName};
@@ -267,6 +325,21 @@ mod tests {
                     ..Default::default()
                 },
             ),
+            (
+                ContentLineCaptures {
+                    property_name: PropertyName::RRule,
+                    parameters: None,
+                    value: "RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L,6;SKIP=FORWARD",
+                },
+                RRule {
+                    freq: Frequency::Yearly,
+                    by_month: vec![6],
+                    by_leap_month: vec![5],
+                    rscale: Some(RScale::Hebrew),
+                    skip: Some(Skip::Forward),
+                    ..Default::default()
+                },
+            ),
         ];
 
         for (input, expected_output) in tests {
@@ -299,7 +372,7 @@ mod tests {
         let res = props_to_rrule(&props);
         assert_eq!(
             res.unwrap_err(),
-            ParseError::InvalidFrequency("DAIL".into())
+            vec![("FREQ".into(), ParseError::InvalidFrequency("DAIL".into()))]
         );
     }
 
@@ -309,11 +382,17 @@ mod tests {
         props.insert(RRuleProperty::Freq, "DAILY".into());
         props.insert(RRuleProperty::ByHour, "24".into());
         let res = props_to_rrule(&props);
-        assert_eq!(res.unwrap_err(), ParseError::InvalidByHour("24".into()));
+        assert_eq!(
+            res.unwrap_err(),
+            vec![("BYHOUR".into(), ParseError::InvalidByHour("24".into()))]
+        );
 
         props.insert(RRuleProperty::ByHour, "5,6,25".into());
         let res = props_to_rrule(&props);
-        assert_eq!(res.unwrap_err(), ParseError::InvalidByHour("5,6,25".into()));
+        assert_eq!(
+            res.unwrap_err(),
+            vec![("BYHOUR".into(), ParseError::InvalidByHour("5,6,25".into()))]
+        );
     }
 
     #[test]
@@ -322,13 +401,44 @@ mod tests {
         props.insert(RRuleProperty::Freq, "DAILY".into());
         props.insert(RRuleProperty::ByMinute, "60".into());
         let res = props_to_rrule(&props);
-        assert_eq!(res.unwrap_err(), ParseError::InvalidByMinute("60".into()));
+        assert_eq!(
+            res.unwrap_err(),
+            vec![("BYMINUTE".into(), ParseError::InvalidByMinute("60".into()))]
+        );
 
         props.insert(RRuleProperty::ByMinute, "4,5,64".into());
         let res = props_to_rrule(&props);
         assert_eq!(
             res.unwrap_err(),
-            ParseError::InvalidByMinute("4,5,64".into())
+            vec![(
+                "BYMINUTE".into(),
+                ParseError::InvalidByMinute("4,5,64".into())
+            )]
+        );
+    }
+
+    #[test]
+    fn collects_errors_of_all_rule_parts() {
+        let parts = ContentLineCaptures {
+            property_name: PropertyName::RRule,
+            parameters: None,
+            value: "COUNT=x;BYMONTHDAY=32;BYHOUR=5;BYMINUTE=60",
+        };
+        assert_eq!(
+            parse_rule_parts(parts.clone()),
+            Err(vec![
+                ("FREQ".into(), ParseError::MissingProperty("FREQ".into())),
+                ("COUNT".into(), ParseError::InvalidCount("x".into())),
+                (
+                    "BYMONTHDAY".into(),
+                    ParseError::InvalidByMonthDay("32".into())
+                ),
+                ("BYMINUTE".into(), ParseError::InvalidByMinute("60".into())),
+            ])
+        );
+        assert_eq!(
+            RRule::try_from(parts),
+            Err(ParseError::MissingProperty("FREQ".into()))
         );
     }
 }
@@ -5,7 +5,7 @@ use super::{
     parameters::parse_parameters,
 };
 use crate::{
-    core::Tz,
+    core::{Tz, VTimezone},
     parser::{
         datetime::{datestring_to_date, parse_timezone},
         ParseError,
@@ -25,6 +25,16 @@ impl TryFrom<&ContentLineCaptures<'_>> for StartDateContentLine {
     type Error = ParseError;
 
     fn try_from(content_line: &ContentLineCaptures) -> Result<Self, Self::Error> {
+        Self::parse(content_line, &[])
+    }
+}
+
+impl StartDateContentLine {
+    /// Parses a `DTSTART` (or `DTEND`) line, looking up its `TZID` in `custom_timezones` first.
+    pub(crate) fn parse(
+        content_line: &ContentLineCaptures,
+        custom_timezones: &[&'static VTimezone],
+    ) -> Result<Self, ParseError> {
         let parameters: HashMap<DateParameter, String> = content_line
             .parameters
             .as_ref()
@@ -34,7 +44,7 @@ impl TryFrom<&ContentLineCaptures<'_>> for StartDateContentLine {
 
         let mut timezone = parameters
             .get(&DateParameter::Timezone)
-            .map(|tz| parse_timezone(tz))
+            .map(|tz| parse_timezone(tz, custom_timezones))
             .transpose()?;
         if timezone.is_none() && content_line.value.to_uppercase().ends_with('Z') {
             timezone = Some(UTC);
@@ -56,7 +66,8 @@ impl TryFrom<&ContentLineCaptures<'_>> for StartDateContentLine {
             }
         }
 
-        let datetime = datestring_to_date(content_line.value, timezone, "DTSTART")?;
+        let property = content_line.property_name.to_string();
+        let datetime = datestring_to_date(content_line.value, timezone, &property)?;
 
         Ok(Self {
             datetime,
@@ -1,14 +1,119 @@
 use std::str::FromStr;
 
 use super::{regex::ParsedDateString, ParseError};
-use crate::{core::Tz, NWeekday};
-use chrono::{NaiveDate, TimeZone, Weekday};
-
-/// Attempts to convert a `str` to a `chrono_tz::Tz`.
-pub(crate) fn parse_timezone(tz: &str) -> Result<Tz, ParseError> {
-    chrono_tz::Tz::from_str(tz)
-        .map_err(|_| ParseError::InvalidTimezone(tz.into()))
-        .map(Tz::Tz)
+use crate::{core::Tz, EventDuration, NWeekday, VTimezone};
+use chrono::{Duration, FixedOffset, NaiveDate, TimeZone, Weekday};
+
+/// Attempts to convert a `str` to a [`Tz`].
+///
+/// The `TZID`s of `custom_timezones` take precedence over the names known by `chrono_tz`.
+/// With the `windows-timezones` feature, Windows timezone IDs are resolved to IANA timezones.
+/// A UTC offset like `+0530` is parsed into a [`Tz::Fixed`].
+pub(crate) fn parse_timezone(
+    tz: &str,
+    custom_timezones: &[&'static VTimezone],
+) -> Result<Tz, ParseError> {
+    if let Some(custom) = custom_timezones
+        .iter()
+        .find(|custom| custom.get_tzid() == tz)
+    {
+        return Ok(Tz::VTimezone(custom));
+    }
+
+    if let Ok(tz) = chrono_tz::Tz::from_str(tz) {
+        return Ok(Tz::Tz(tz));
+    }
+
+    if tz.starts_with(['+', '-']) {
+        return parse_utc_offset(tz)
+            .map(Tz::Fixed)
+            .map_err(|_| ParseError::InvalidTimezone(tz.into()));
+    }
+
+    #[cfg(feature = "windows-timezones")]
+    if let Some(iana) = super::windows_timezones::windows_to_iana(tz) {
+        if let Ok(tz) = chrono_tz::Tz::from_str(iana) {
+            return Ok(Tz::Tz(tz));
+        }
+    }
+
+    Err(ParseError::InvalidTimezone(tz.into()))
+}
+
+/// Parses a UTC offset like `+0100`, `-0500` or `+013045`.
+pub(crate) fn parse_utc_offset(value: &str) -> Result<FixedOffset, ParseError> {
+    let invalid = || ParseError::InvalidUtcOffset(value.into());
+
+    let (sign, digits) = if let Some(digits) = value.strip_prefix('+') {
+        (1, digits)
+    } else if let Some(digits) = value.strip_prefix('-') {
+        (-1, digits)
+    } else {
+        return Err(invalid());
+    };
+    if !matches!(digits.len(), 4 | 6) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let part = |range: std::ops::Range<usize>| {
+        digits
+            .get(range)
+            .map_or(Ok(0), str::parse::<i32>)
+            .map_err(|_| invalid())
+    };
+    let seconds = part(0..2)? * 3600 + part(2..4)? * 60 + part(4..6)?;
+
+    FixedOffset::east_opt(sign * seconds).ok_or_else(invalid)
+}
+
+/// Parses a positive duration like `PT1H30M`, `P1DT12H` or `P2W`, see
+/// [RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545#section-3.3.6).
+///
+/// Days and weeks are nominal and kept apart from the exact hours, minutes and seconds.
+pub(crate) fn parse_duration(value: &str) -> Result<EventDuration, ParseError> {
+    let invalid = || ParseError::InvalidDuration(value.into());
+
+    let value_upper = value.to_uppercase();
+    let rest = value_upper.strip_prefix('+').unwrap_or(&value_upper);
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+    let (date, time) = match rest.split_once('T') {
+        Some((_, "")) => return Err(invalid()),
+        Some((date, time)) => (date, Some(time)),
+        None if rest.is_empty() => return Err(invalid()),
+        None => (rest, None),
+    };
+
+    // Every unit is optional, but they have to be in this order
+    let sum_units = |mut part: &str, units: &[(char, i64)]| {
+        let mut sum = 0_i64;
+        for (unit, multiplier) in units {
+            if let Some((digits, rest)) = part.split_once(*unit) {
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                sum = digits
+                    .parse::<i64>()
+                    .ok()
+                    .and_then(|value| value.checked_mul(*multiplier))
+                    .and_then(|value| sum.checked_add(value))
+                    .ok_or_else(invalid)?;
+                part = rest;
+            }
+        }
+        if part.is_empty() {
+            Ok(sum)
+        } else {
+            Err(invalid())
+        }
+    };
+    let days = sum_units(date, &[('W', 7), ('D', 1)])?;
+    let days = u32::try_from(days).map_err(|_| invalid())?;
+    let seconds = match time {
+        Some(time) => sum_units(time, &[('H', 3600), ('M', 60), ('S', 1)])?,
+        None => 0,
+    };
+    let time = Duration::try_seconds(seconds).ok_or_else(invalid)?;
+
+    Ok(EventDuration::new(days, time))
 }
 
 /// Convert a datetime string and a timezone to a `chrono::DateTime<Tz>`.
@@ -142,6 +247,39 @@ mod tests {
 
     const US_PACIFIC: Tz = Tz::US__Pacific;
 
+    #[test]
+    fn parses_valid_durations() {
+        let tests = [
+            ("PT1H30M", (0, Duration::minutes(90))),
+            ("P1DT12H", (1, Duration::hours(12))),
+            ("+P2W", (14, Duration::zero())),
+            ("PT15M10S", (0, Duration::seconds(910))),
+            ("P1D", (1, Duration::zero())),
+            ("PT36H", (0, Duration::hours(36))),
+            ("pt0s", (0, Duration::zero())),
+        ];
+
+        for (input, expected_output) in tests {
+            let output = parse_duration(input);
+            assert_eq!(
+                output,
+                Ok(EventDuration::new(expected_output.0, expected_output.1))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        let tests = [
+            "", "P", "PT", "-PT1H", "1H", "PT1D", "P1H", "PT30M1H", "PTH", "P1DT", "P2W1W",
+        ];
+
+        for input in tests {
+            let res = parse_duration(input);
+            assert_eq!(res, Err(ParseError::InvalidDuration(input.into())));
+        }
+    }
+
     #[test]
     fn parses_valid_nweekdays() {
         let tests = [
@@ -252,4 +390,40 @@ mod tests {
             assert!(res.is_err());
         }
     }
+
+    #[test]
+    fn parses_fixed_offset_timezones() {
+        let offset = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+        assert_eq!(parse_timezone("+0530", &[]), Ok(Tz::Fixed(offset)));
+        assert_eq!(
+            parse_timezone("-0800", &[]),
+            Ok(Tz::Fixed(FixedOffset::west_opt(8 * 3600).unwrap()))
+        );
+        assert_eq!(
+            parse_timezone("+05:30", &[]),
+            Err(ParseError::InvalidTimezone("+05:30".into()))
+        );
+    }
+
+    #[test]
+    fn parses_utc_offsets() {
+        let tests = [
+            ("+0100", 3600),
+            ("-0500", -5 * 3600),
+            ("+0000", 0),
+            ("+013045", 3600 + 30 * 60 + 45),
+        ];
+        for (input, seconds) in tests {
+            assert_eq!(
+                parse_utc_offset(input),
+                Ok(FixedOffset::east_opt(seconds).unwrap())
+            );
+        }
+        for input in ["0100", "+01", "+01:00", "+2500", "-01a0", ""] {
+            assert_eq!(
+                parse_utc_offset(input),
+                Err(ParseError::InvalidUtcOffset(input.into()))
+            );
+        }
+    }
 }
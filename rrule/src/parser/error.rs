@@ -1,7 +1,14 @@
 #![allow(clippy::module_name_repetitions)]
+use std::{fmt::Display, ops::Range};
+
 use thiserror::Error;
 
+use super::{utils::UnfoldedLine, Grammar};
+use crate::{RRuleError, VCalendar};
+
+/// Errors that can occur while parsing an iCalendar string.
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
 pub enum ParseError {
     #[error("`{0}` is not a valid timezone.")]
     InvalidTimezone(String),
@@ -44,6 +51,18 @@ pub enum ParseError {
     InvalidByMinute(String),
     #[error("`{0}` is not a valid BYSECOND value. Expected a comma-separated list of values in range 0..=59, e.g. `0,15,30,45`")]
     InvalidBySecond(String),
+    #[error("`{0}` is not a valid RSCALE value. Supported calendars are `GREGORIAN`, `HEBREW` and `ISLAMIC-CIVIL`.")]
+    InvalidRScale(String),
+    #[error("`{0}` is not a valid SKIP value. Valid values are `OMIT`, `BACKWARD` and `FORWARD`.")]
+    InvalidSkip(String),
+    #[error(
+        "`{0}` is not a valid DURATION value. Expected a positive duration like `PT1H30M` or `P1D`"
+    )]
+    InvalidDuration(String),
+    #[error(
+        "`{0}` is not a valid RANGE value. Valid values are `THISINSTANCE` and `THISANDFUTURE`."
+    )]
+    InvalidRecurrenceRange(String),
     #[error("`{0}` is not a valid BYSETPOS value. Expected a comma-separated list of integers, e.g. `-3,1`")]
     InvalidBySetPos(String),
     #[error("The property `{0}` wasn't found, and it is required.")]
@@ -63,7 +82,7 @@ pub enum ParseError {
     #[error("Property parameters aren't supported for RRULE / EXRULE, found parameters: `{0}`")]
     PropertyParametersNotSupported(String),
     #[error(
-        "`{0}` is not a valid property name, expected one of: `RRULE,EXRULE,DTSTART,RDATE,EXDATE`"
+        "`{0}` is not a valid property name, expected one of: `RRULE,EXRULE,DTSTART,DTEND,DURATION,RDATE,EXDATE`"
     )]
     UnrecognizedPropertyName(String),
     #[error(
@@ -74,10 +93,358 @@ pub enum ParseError {
         "The value of `DTSTART` was specified in local timezone, but `UNTIL` was specified with a zulu time when it had to be specified in local time as well"
     )]
     DtStartUntilMismatchTimezone,
+    #[error("`DTEND` and `DURATION` can not both be specified.")]
+    DtEndAndDuration,
+    #[error("The value of `DTEND` needs to be at or after the value of `DTSTART`.")]
+    DtEndBeforeDtStart,
     #[error("Property parameter `{parameter}` was set to have value `{parameter_value}`, but found `{found_value}` ")]
     ParameterValueMismatch {
         parameter: String,
         parameter_value: String,
         found_value: String,
     },
+    #[error(
+        "`{0}` is not a valid content line. Expected a line of the form `NAME[;PARAMETERS]:VALUE`"
+    )]
+    InvalidContentLine(String),
+    #[error("Found `END:{0}` without a matching `BEGIN:{0}`.")]
+    UnexpectedComponentEnd(String),
+    #[error("The component `{0}` was never closed, expected `END:{0}`.")]
+    MissingComponentEnd(String),
+    #[error("`{0}` is not a valid UTC offset. Expected a value like `+0100` or `-053000`")]
+    InvalidUtcOffset(String),
+    #[error("The component `{0}` wasn't found, and it is required.")]
+    MissingComponent(String),
+}
+
+impl ParseError {
+    /// Attaches the location of the error in the content line `source` of the property
+    /// named `property`. The location is the part of the line holding the invalid value,
+    /// or the whole line if the error doesn't name one.
+    pub(crate) fn at(self, source: &UnfoldedLine<'_>, property: Option<&str>) -> Located {
+        let content = &source.content;
+        let range = self
+            .invalid_value()
+            .and_then(|value| {
+                let mut ranges = find_value(content, value);
+                // A duplicate is the last one of the values
+                match self {
+                    Self::DuplicateProperty(_) => ranges.last(),
+                    _ => ranges.next(),
+                }
+            })
+            .unwrap_or(0..content.len());
+        let (line, span) = source.locate(range);
+        Located {
+            error: self,
+            location: Some(Box::new(Location {
+                line,
+                span,
+                property: property.map(Into::into),
+            })),
+        }
+    }
+
+    /// Returns where the error was found in `source`, if it was returned when parsing
+    /// `source` into an [`crate::RRuleSet`] or a [`crate::VCalendar`].
+    ///
+    /// The error is located by parsing `source` again, as a calendar first and as the
+    /// properties of an [`crate::RRuleSet`] otherwise.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{RRuleError, RRuleSet};
+    ///
+    /// let input = "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=x";
+    /// let Err(RRuleError::ParserError(error)) = input.parse::<RRuleSet>() else {
+    ///     panic!("input is invalid");
+    /// };
+    /// assert_eq!(error.locate(input).map(|location| location.get_line()), Some(2));
+    /// ```
+    #[must_use]
+    pub fn locate(&self, source: &str) -> Option<Location> {
+        let calendar_location = VCalendar::parse_located(source)
+            .err()
+            .filter(
+                |located| matches!(&located.error, RRuleError::ParserError(found) if found == self),
+            )
+            .and_then(|located| located.location);
+        calendar_location
+            .or_else(|| {
+                Grammar::parse_lenient(source)
+                    .1
+                    .into_iter()
+                    .find(|(_, located)| located.error == *self)
+                    .and_then(|(_, located)| located.location)
+            })
+            .map(|location| *location)
+    }
+
+    /// Returns the value of the input which is invalid, if the error holds one.
+    fn invalid_value(&self) -> Option<&str> {
+        match self {
+            Self::InvalidTimezone(value)
+            | Self::InvalidDateTime { value, .. }
+            | Self::InvalidDateTimeFormat(value)
+            | Self::InvalidDateTimeInLocalTimezone { value, .. }
+            | Self::DateTimeInLocalTimezoneIsAmbiguous { value, .. }
+            | Self::InvalidFrequency(value)
+            | Self::InvalidWeekday(value)
+            | Self::InvalidWeekdayStart(value)
+            | Self::InvalidByEaster(value)
+            | Self::InvalidInterval(value)
+            | Self::InvalidCount(value)
+            | Self::InvalidByHour(value)
+            | Self::InvalidByWeekNo(value)
+            | Self::InvalidByYearDay(value)
+            | Self::InvalidByMonthDay(value)
+            | Self::InvalidByMonth(value)
+            | Self::InvalidByMinute(value)
+            | Self::InvalidBySecond(value)
+            | Self::InvalidRScale(value)
+            | Self::InvalidSkip(value)
+            | Self::InvalidDuration(value)
+            | Self::InvalidRecurrenceRange(value)
+            | Self::InvalidBySetPos(value)
+            | Self::InvalidParameterFormat(value)
+            | Self::UnrecognizedParameter(value)
+            | Self::DuplicateProperty(value)
+            | Self::PropertyParametersNotSupported(value)
+            | Self::UnrecognizedPropertyName(value)
+            | Self::UnexpectedComponentEnd(value)
+            | Self::InvalidUtcOffset(value)
+            | Self::ParameterValueMismatch {
+                found_value: value, ..
+            } => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the ranges of `value` in `content`, where it is delimited like a property name,
+/// parameter or value, so `20230101` is not found in `20230101T090000`.
+fn find_value<'a>(content: &'a str, value: &'a str) -> impl Iterator<Item = Range<usize>> + 'a {
+    let is_delimiter = |c: Option<char>| matches!(c, None | Some(':' | ';' | ',' | '='));
+    content
+        .match_indices(value)
+        .filter(|_| !value.is_empty())
+        .map(|(start, _)| start..start + value.len())
+        .filter(move |range| {
+            is_delimiter(content[..range.start].chars().next_back())
+                && is_delimiter(content[range.end..].chars().next())
+        })
+}
+
+/// An error of the parser together with the location of the input which caused it, if it is
+/// known.
+///
+/// The location is kept next to the error instead of in it, so the errors returned by the
+/// parser stay the same. It is only used by [`ParseError::locate`]. The location is boxed
+/// to keep the `Err` variants of the parser small.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Located<E = ParseError> {
+    pub error: E,
+    pub location: Option<Box<Location>>,
+}
+
+impl<E> Located<E> {
+    /// Converts the error, keeping its location.
+    pub(crate) fn map<F: From<E>>(self) -> Located<F> {
+        Located {
+            error: self.error.into(),
+            location: self.location,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ParseError {
+    /// Attaches the given location to the error.
+    pub(crate) fn located(self, location: Location) -> Located {
+        Located {
+            error: self,
+            location: Some(Box::new(location)),
+        }
+    }
+}
+
+impl<E> From<E> for Located<E> {
+    fn from(error: E) -> Self {
+        Self {
+            error,
+            location: None,
+        }
+    }
+}
+
+/// Where a [`ParseError`] was found in the input, see [`ParseError::locate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub(crate) line: usize,
+    pub(crate) span: Range<usize>,
+    pub(crate) property: Option<String>,
+}
+
+impl Location {
+    /// Returns the 1-based number of the physical input line.
+    #[must_use]
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the byte range of the invalid part within the physical input line.
+    #[must_use]
+    pub fn get_span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the name of the property of the line, like `EXDATE`, if it is known.
+    #[must_use]
+    pub fn get_property(&self) -> Option<&str> {
+        self.property.as_deref()
+    }
+}
+
+/// Renders a [`ParseError`] together with the input line which caused it, with carets
+/// under the invalid part.
+///
+/// # Usage
+///
+/// ```
+/// use rrule::{Diagnostic, RRuleError, RRuleSet};
+///
+/// let input = "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY\nEXDATE:20120202T093000Z,2012023T093000Z";
+/// let Err(RRuleError::ParserError(error)) = input.parse::<RRuleSet>() else {
+///     panic!("input is invalid");
+/// };
+/// assert_eq!(
+///     Diagnostic::new(&error, input).to_string(),
+///     "error: `2012023T093000Z` is not a valid datetime format for `EXDATE`.
+///  --> line 3, column 25 (EXDATE)
+///   |
+/// 3 | EXDATE:20120202T093000Z,2012023T093000Z
+///   |                         ^^^^^^^^^^^^^^^
+/// "
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Diagnostic<'a> {
+    error: &'a ParseError,
+    source: &'a str,
+    location: Option<Location>,
+}
+
+impl<'a> Diagnostic<'a> {
+    /// Creates the diagnostic of an error which was returned when parsing `source` into an
+    /// [`crate::RRuleSet`] or a [`crate::VCalendar`].
+    ///
+    /// The error is located with [`ParseError::locate`], so it is only rendered with an input
+    /// line if `source` is the input which returned it.
+    #[must_use]
+    pub fn new(error: &'a ParseError, source: &'a str) -> Self {
+        Self {
+            error,
+            source,
+            location: error.locate(source),
+        }
+    }
+
+    /// Returns where the error was found in the source, if it is known.
+    ///
+    /// ```
+    /// use rrule::{Diagnostic, RRuleError, RRuleSet};
+    ///
+    /// let input = "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=x";
+    /// let Err(RRuleError::ParserError(error)) = input.parse::<RRuleSet>() else {
+    ///     panic!("input is invalid");
+    /// };
+    /// let location = Diagnostic::new(&error, input).get_location().cloned().unwrap();
+    /// assert_eq!(location.get_line(), 2);
+    /// assert_eq!(location.get_span(), 23..24);
+    /// assert_eq!(location.get_property(), Some("RRULE"));
+    /// ```
+    #[must_use]
+    pub fn get_location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "error: {}", self.error)?;
+        let Some(location) = &self.location else {
+            return Ok(());
+        };
+        let Some(line) = self.source.lines().nth(location.line.saturating_sub(1)) else {
+            return writeln!(f, " --> line {}", location.line);
+        };
+
+        // The span is in bytes, but the carets are placed by characters
+        let start = location.span.start.min(line.len());
+        let end = location.span.end.clamp(start, line.len());
+        let column = line.get(..start).map_or(0, |text| text.chars().count());
+        let width = line.get(start..end).map_or(0, |text| text.chars().count());
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        write!(
+            f,
+            "{}--> line {}, column {}",
+            gutter,
+            location.line,
+            column + 1
+        )?;
+        match &location.property {
+            Some(property) => writeln!(f, " ({})", property)?,
+            None => writeln!(f)?,
+        }
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, line)?;
+        writeln!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(column),
+            "^".repeat(width.max(1))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_diagnostics() {
+        let error = ParseError::InvalidByHour("24".into());
+        let source = format!(
+            "DTSTART:20120201T093000Z\n{}RRULE:FREQ=DAILY;BYHOUR=24",
+            "RDATE:20120202T093000Z\n".repeat(10)
+        );
+        assert_eq!(
+            Diagnostic::new(&error, &source).to_string(),
+            "error: `24` is not a valid BYHOUR value. Expected a comma-separated list of values in range 0..=23, e.g. `1,3,4`
+  --> line 12, column 25 (RRULE)
+   |
+12 | RRULE:FREQ=DAILY;BYHOUR=24
+   |                         ^^
+"
+        );
+
+        let error = ParseError::MissingStartDate;
+        assert_eq!(
+            Diagnostic::new(&error, "").to_string(),
+            format!("error: {}\n", error)
+        );
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        let error = ParseError::InvalidTimezone("Europe/Zürich".into());
+        let source = "DTSTART;TZID=Europe/Zürich:20120201T120000\nRRULE:FREQ=DAILY";
+        assert!(Diagnostic::new(&error, source).to_string().ends_with(
+            "1 | DTSTART;TZID=Europe/Zürich:20120201T120000\n  |              ^^^^^^^^^^^^^\n"
+        ));
+    }
 }
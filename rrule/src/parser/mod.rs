@@ -1,25 +1,34 @@
 //! Module for parsing text inputs to a [`Grammar`] which can further be used
 //! to construct an [`crate::RRuleSet`].
+pub(crate) mod component;
 mod content_line;
 mod datetime;
 mod error;
 mod regex;
 mod utils;
+#[cfg(feature = "windows-timezones")]
+mod windows_timezones;
 
 use std::str::FromStr;
 
-pub(crate) use content_line::{ContentLine, ContentLineCaptures};
-pub(crate) use datetime::str_to_weekday;
-pub use error::ParseError;
+pub(crate) use content_line::{
+    parse_recurrence_id, ContentLine, ContentLineCaptures, PropertyName,
+};
+pub(crate) use datetime::{datestring_to_date, parse_duration, parse_utc_offset, str_to_weekday};
+pub(crate) use error::Located;
+pub use error::{Diagnostic, Location, ParseError};
 
-use crate::RRule;
+use crate::{EventDuration, VTimezone};
 
-use self::content_line::{PropertyName, StartDateContentLine};
+use self::content_line::{parse_date_content_line, parse_rule_parts, StartDateContentLine};
+use self::utils::{unfold_lines, UnfoldedLine};
 
 /// Grammar represents a well-formatted rrule input.
 #[derive(Debug, PartialEq)]
 pub(crate) struct Grammar {
     pub start: Option<StartDateContentLine>,
+    /// The duration of every occurrence, given by `DURATION` or derived from `DTEND`.
+    pub duration: Option<EventDuration>,
     pub content_lines: Vec<ContentLine>,
 }
 
@@ -27,51 +36,192 @@ impl FromStr for Grammar {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let content_lines_parts = s
-            .lines()
-            .map(ContentLineCaptures::new)
-            .collect::<Result<Vec<_>, _>>()?;
+        let (grammar, mut errors) = Self::parse_lenient(s);
+        if errors.is_empty() {
+            Ok(grammar)
+        } else {
+            Err(errors.swap_remove(0).1.error)
+        }
+    }
+}
 
-        let start = content_lines_parts
+impl Grammar {
+    /// Parses the grammar like [`Grammar::from_str`], but skips the lines which are invalid and
+    /// returns the errors of all of them, each with the field it concerns: the rule part for
+    /// errors in an `RRULE` or `EXRULE`, and the property otherwise.
+    pub(crate) fn parse_lenient(s: &str) -> (Self, Vec<(String, Located)>) {
+        let mut errors = vec![];
+        let unfolded_lines = unfold_lines(s);
+        let content_lines_parts = unfolded_lines
             .iter()
-            .find(|parts| matches!(parts.property_name, PropertyName::DtStart))
-            .map(StartDateContentLine::try_from)
-            .transpose()?;
+            .filter_map(
+                |unfolded| match ContentLineCaptures::new(&unfolded.content) {
+                    Ok(parts) => Some((unfolded, parts)),
+                    Err(err) => {
+                        // The name of the property, if the line has one
+                        let property = unfolded
+                            .content
+                            .split_once(':')
+                            .map(|(name, _)| name.split(';').next().unwrap_or(name));
+                        let field = property.unwrap_or("RRULE").to_uppercase();
+                        errors.push((field, err.at(unfolded, property)));
+                        None
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+        let has_date_generation_rules = content_lines_parts.iter().any(|(_, parts)| {
+            matches!(
+                parts.property_name,
+                PropertyName::RRule | PropertyName::RDate
+            )
+        });
 
-        let mut content_lines = vec![];
+        let (grammar, captures_errors) = Self::from_captures_lenient(content_lines_parts, &[]);
+        errors.extend(captures_errors);
 
-        for parts in content_lines_parts {
-            let line = match parts.property_name {
-                PropertyName::RRule => {
-                    let rrule = RRule::try_from(parts)?;
-                    ContentLine::RRule(rrule)
-                }
-                PropertyName::ExRule => {
-                    let rrule = RRule::try_from(parts)?;
-                    ContentLine::ExRule(rrule)
+        // Need to be at least one `RDATE` or `RRULE`
+        if !has_date_generation_rules {
+            errors.push((
+                "RRULE".into(),
+                ParseError::MissingDateGenerationRules.into(),
+            ));
+        }
+
+        (grammar, errors)
+    }
+
+    /// Builds the grammar from already captured content lines, each paired with the unfolded
+    /// input line it was found on. A `TZID` is first looked up in `custom_timezones`, before
+    /// falling back to the IANA timezones.
+    ///
+    /// Unlike [`Grammar::from_str`], this does not require any `RRULE` or `RDATE` to be present.
+    pub(crate) fn from_captures(
+        content_lines_parts: Vec<(&UnfoldedLine<'_>, ContentLineCaptures<'_>)>,
+        custom_timezones: &[&'static VTimezone],
+    ) -> Result<Self, Located> {
+        let (grammar, mut errors) =
+            Self::from_captures_lenient(content_lines_parts, custom_timezones);
+        if errors.is_empty() {
+            Ok(grammar)
+        } else {
+            Err(errors.swap_remove(0).1)
+        }
+    }
+
+    /// Builds the grammar like [`Grammar::from_captures`], but skips the content lines which are
+    /// invalid and returns their errors, like [`Grammar::parse_lenient`].
+    fn from_captures_lenient(
+        content_lines_parts: Vec<(&UnfoldedLine<'_>, ContentLineCaptures<'_>)>,
+        custom_timezones: &[&'static VTimezone],
+    ) -> (Self, Vec<(String, Located)>) {
+        let mut errors = vec![];
+
+        let start_parts = content_lines_parts
+            .iter()
+            .find(|(_, parts)| matches!(parts.property_name, PropertyName::DtStart));
+        let start = start_parts.and_then(|(source, parts)| {
+            StartDateContentLine::parse(parts, custom_timezones)
+                .map_err(|err| errors.push(("DTSTART".into(), err.at(source, Some("DTSTART")))))
+                .ok()
+        });
+        let duration =
+            match Self::parse_duration(&content_lines_parts, start.as_ref(), custom_timezones) {
+                Ok(duration) => duration,
+                // The error of the start date was already reported
+                Err((
+                    _,
+                    Located {
+                        error: ParseError::MissingStartDate,
+                        ..
+                    },
+                )) if start_parts.is_some() => None,
+                Err((field, err)) => {
+                    errors.push((field.into(), err));
+                    None
                 }
-                PropertyName::RDate => ContentLine::RDate(TryFrom::try_from(parts)?),
-                PropertyName::ExDate => ContentLine::ExDate(TryFrom::try_from(parts)?),
-                PropertyName::DtStart => {
+            };
+
+        let mut content_lines = vec![];
+
+        for (source, parts) in content_lines_parts {
+            let property_name = parts.property_name.to_string();
+            let content_line = match parts.property_name {
+                PropertyName::RRule => parse_rule_parts(parts).map(ContentLine::RRule),
+                PropertyName::ExRule => parse_rule_parts(parts).map(ContentLine::ExRule),
+                PropertyName::RDate => parse_date_content_line(parts, custom_timezones)
+                    .map(ContentLine::RDate)
+                    .map_err(|err| vec![(property_name.clone(), err)]),
+                PropertyName::ExDate => parse_date_content_line(parts, custom_timezones)
+                    .map(ContentLine::ExDate)
+                    .map_err(|err| vec![(property_name.clone(), err)]),
+                PropertyName::DtStart | PropertyName::DtEnd | PropertyName::Duration => {
                     // Nothing to do
                     continue;
                 }
             };
-            content_lines.push(line);
+            match content_line {
+                Ok(content_line) => content_lines.push(content_line),
+                Err(line_errors) => errors.extend(
+                    line_errors
+                        .into_iter()
+                        .map(|(field, err)| (field, err.at(source, Some(&property_name)))),
+                ),
+            }
         }
 
-        // Need to be at least one `RDATE` or `RRULE`
-        if !content_lines
-            .iter()
-            .any(|line| matches!(line, ContentLine::RRule(_) | ContentLine::RDate(_)))
-        {
-            return Err(ParseError::MissingDateGenerationRules);
+        (
+            Self {
+                start,
+                duration,
+                content_lines,
+            },
+            errors,
+        )
+    }
+
+    /// Parses the duration of the occurrences from the `DURATION` or the `DTEND` line, if any.
+    /// An error is returned with the property it concerns.
+    fn parse_duration(
+        content_lines_parts: &[(&UnfoldedLine<'_>, ContentLineCaptures<'_>)],
+        start: Option<&StartDateContentLine>,
+        custom_timezones: &[&'static VTimezone],
+    ) -> Result<Option<EventDuration>, (&'static str, Located)> {
+        let find = |property_name| {
+            content_lines_parts
+                .iter()
+                .find(|(_, parts)| parts.property_name == property_name)
+        };
+
+        match (find(PropertyName::DtEnd), find(PropertyName::Duration)) {
+            (Some(_), Some((source, _))) => Err((
+                "DURATION",
+                ParseError::DtEndAndDuration.at(source, Some("DURATION")),
+            )),
+            (Some((source, parts)), None) => {
+                let end = StartDateContentLine::parse(parts, custom_timezones)
+                    .map_err(|err| ("DTEND", err.at(source, Some("DTEND"))))?;
+                let start = start.ok_or(("DTSTART", ParseError::MissingStartDate.into()))?;
+                if end.datetime < start.datetime {
+                    return Err((
+                        "DTEND",
+                        ParseError::DtEndBeforeDtStart.at(source, Some("DTEND")),
+                    ));
+                }
+                Ok(Some((end.datetime - start.datetime).into()))
+            }
+            (None, Some((source, parts))) => parse_duration(parts.value)
+                .map(Some)
+                .map_err(|err| ("DURATION", err.at(source, Some("DURATION")))),
+            (None, None) => Ok(None),
         }
+    }
 
-        Ok(Self {
-            start,
-            content_lines,
-        })
+    /// Returns true if there is at least one `RRULE` or `RDATE`.
+    pub(crate) fn has_date_generation_rules(&self) -> bool {
+        self.content_lines
+            .iter()
+            .any(|line| matches!(line, ContentLine::RRule(_) | ContentLine::RDate(_)))
     }
 }
 
@@ -91,6 +241,7 @@ mod test {
 (
     "DTSTART:19970902T090000Z\nRRULE:FREQ=YEARLY;COUNT=3\n", Grammar {
     start: Some(StartDateContentLine { datetime: UTC.with_ymd_and_hms(1997, 9, 2,9, 0, 0).unwrap(), timezone: Some(UTC), value: "DATE-TIME" }),
+    duration: None,
     content_lines: vec![
         ContentLine::RRule(RRule {
             freq: Frequency::Yearly,
@@ -102,6 +253,7 @@ mod test {
 ),
 ("DTSTART:20120201T093000Z\nRRULE:FREQ=WEEKLY;INTERVAL=5;UNTIL=20130130T230000Z;BYDAY=MO,FR", Grammar {
     start: Some(StartDateContentLine { datetime: UTC.with_ymd_and_hms(2012, 2, 1,9, 30, 0).unwrap(), timezone: Some(UTC), value: "DATE-TIME" }),
+    duration: None,
     content_lines: vec![
         ContentLine::RRule(RRule {
             freq: Frequency::Weekly,
@@ -114,6 +266,7 @@ mod test {
 }),
 ("DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY;COUNT=5\nEXDATE;TZID=Europe/Berlin:20120202T130000,20120203T130000", Grammar {
     start: Some(StartDateContentLine { datetime: UTC.with_ymd_and_hms(2012, 2, 1,12, 0, 0).unwrap(), timezone: Some(UTC), value: "DATE-TIME" }),
+    duration: None,
     content_lines: vec![
         ContentLine::RRule(RRule {
             freq: Frequency::Daily,
@@ -128,6 +281,7 @@ mod test {
 }),
 ("DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY;COUNT=5\nEXDATE;TZID=Europe/Berlin:20120202T130000,20120203T130000\nEXRULE:FREQ=WEEKLY;COUNT=10", Grammar {
     start: Some(StartDateContentLine { datetime: UTC.with_ymd_and_hms(2012, 2, 1,12, 0, 0).unwrap(), timezone: Some(UTC), value: "DATE-TIME" }),
+    duration: None,
     content_lines: vec![
         ContentLine::RRule(RRule {
             freq: Frequency::Daily,
@@ -166,6 +320,175 @@ mod test {
         }
     }
 
+    #[test]
+    fn parses_folded_input() {
+        let input = "DTSTART:20120201T120000Z\r\nRRULE:FREQ=DAILY;\r\n COUNT=5\r\nEXDATE;TZID=Europe/Berlin:20120202T130000,\r\n\t20120203T130000\r\n";
+        let grammar = Grammar::from_str(input).unwrap();
+        assert_eq!(
+            grammar.content_lines,
+            vec![
+                ContentLine::RRule(RRule {
+                    freq: Frequency::Daily,
+                    count: Some(5),
+                    ..Default::default()
+                }),
+                ContentLine::ExDate(vec![
+                    BERLIN.with_ymd_and_hms(2012, 2, 2, 13, 0, 0).unwrap(),
+                    BERLIN.with_ymd_and_hms(2012, 2, 3, 13, 0, 0).unwrap(),
+                ])
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_indented_input() {
+        let input = "DTSTART:20120201T120000Z
+            RRULE:FREQ=DAILY;COUNT=5
+            RRULE:FREQ=WEEKLY;INTERVAL=2";
+        let grammar = Grammar::from_str(input).unwrap();
+        assert_eq!(
+            grammar.start.map(|start| start.datetime),
+            Some(UTC.with_ymd_and_hms(2012, 2, 1, 12, 0, 0).unwrap())
+        );
+        assert_eq!(
+            grammar.content_lines,
+            vec![
+                ContentLine::RRule(RRule {
+                    freq: Frequency::Daily,
+                    count: Some(5),
+                    ..Default::default()
+                }),
+                ContentLine::RRule(RRule {
+                    freq: Frequency::Weekly,
+                    interval: 2,
+                    ..Default::default()
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_errors_unwrapped_and_locates_them() {
+        let input = "DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY;\n COUNT=5\nEXDATE:20120202T130000Z,\n 2012020T130000Z";
+        let error = Grammar::from_str(input).unwrap_err();
+        assert_eq!(
+            error,
+            ParseError::InvalidDateTime {
+                value: "2012020T130000Z".into(),
+                property: "EXDATE".into(),
+            }
+        );
+        assert_eq!(error.locate(input).map(|location| location.line), Some(5));
+        // The error is not returned for another input
+        assert_eq!(
+            error.locate("DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY"),
+            None
+        );
+    }
+
+    #[test]
+    fn reports_physical_line_of_errors() {
+        let input = "DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY;\n COUNT=5\nEXDATE:20120202T130000Z,\n 2012020T130000Z";
+        let (_, errors) = Grammar::parse_lenient(input);
+        assert_eq!(
+            errors.first().map(|(_, located)| located),
+            Some(
+                &ParseError::InvalidDateTime {
+                    value: "2012020T130000Z".into(),
+                    property: "EXDATE".into(),
+                }
+                .located(Location {
+                    line: 5,
+                    span: 1..16,
+                    property: Some("EXDATE".into()),
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn locates_errors_of_content_lines() {
+        let tests = [
+            (
+                "DTSTART:20120201T120000Z\nRRULES:FREQ=DAILY",
+                ParseError::UnrecognizedPropertyName("RRULES".into()),
+                (2, 0..6, Some("RRULES")),
+            ),
+            (
+                "DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY\nEXDATE;TZID=Europe/Berlin;VALUES=DATE:20120202",
+                ParseError::UnrecognizedParameter("VALUES".into()),
+                (3, 26..32, Some("EXDATE")),
+            ),
+            (
+                "DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY\nRDATE;TZID=Europe/Berlin;\n TZID=Europe/Paris:20120202T130000",
+                ParseError::DuplicateProperty("TZID".into()),
+                (4, 1..5, Some("RDATE")),
+            ),
+            (
+                "DTSTART;TZID:20120201T120000\nRRULE:FREQ=DAILY",
+                ParseError::InvalidParameterFormat("TZID".into()),
+                (1, 8..12, Some("DTSTART")),
+            ),
+            (
+                "DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY;BYHOUR=24",
+                ParseError::InvalidByHour("24".into()),
+                (2, 24..26, Some("RRULE")),
+            ),
+        ];
+        for (input, expected_error, (line, span, property)) in tests {
+            let (_, errors) = Grammar::parse_lenient(input);
+            assert_eq!(
+                errors.first().map(|(_, located)| located),
+                Some(&expected_error.located(Location {
+                    line,
+                    span,
+                    property: property.map(Into::into),
+                })),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_duration_of_occurrences() {
+        let tests = [
+            ("DTSTART:20120201T120000Z\nDURATION:PT1H30M\nRRULE:FREQ=DAILY", Some(chrono::Duration::minutes(90))),
+            ("DTSTART:20120201T120000Z\nDTEND;TZID=Europe/Berlin:20120202T140000\nRRULE:FREQ=DAILY", Some(chrono::Duration::hours(25))),
+            ("DTSTART:20120201T120000Z\nDTEND:20120201T120000Z\nRRULE:FREQ=DAILY", Some(chrono::Duration::zero())),
+            ("DTSTART:20120201T120000Z\nRRULE:FREQ=DAILY", None),
+        ];
+        for (input, expected_duration) in tests {
+            let grammar = Grammar::from_str(input).unwrap();
+            assert_eq!(grammar.duration, expected_duration.map(EventDuration::from));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_duration_of_occurrences() {
+        let tests = [
+            (
+                "DTSTART:20120201T120000Z\nDTEND:20120201T130000Z\nDURATION:PT1H\nRRULE:FREQ=DAILY",
+                ParseError::DtEndAndDuration,
+            ),
+            (
+                "DTSTART:20120201T120000Z\nDTEND:20120201T110000Z\nRRULE:FREQ=DAILY",
+                ParseError::DtEndBeforeDtStart,
+            ),
+            (
+                "DTSTART:20120201T120000Z\nDURATION:-PT1H\nRRULE:FREQ=DAILY",
+                ParseError::InvalidDuration("-PT1H".into()),
+            ),
+            (
+                "DTEND:20120201T120000Z\nRRULE:FREQ=DAILY",
+                ParseError::MissingStartDate,
+            ),
+        ];
+        for (input, expected_error) in tests {
+            let res = Grammar::from_str(input);
+            assert_eq!(res, Err(expected_error));
+        }
+    }
+
     #[test]
     fn allows_input_without_start_date() {
         let tests = [
@@ -1,5 +1,82 @@
 //! Utility functions for parsing rrule input.
-use std::str::FromStr;
+use std::{borrow::Cow, ops::Range, str::FromStr};
+
+/// A logical content line, after unfolding.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct UnfoldedLine<'a> {
+    /// The 1-based number of the physical line where the content line starts.
+    pub line: usize,
+    pub content: Cow<'a, str>,
+    /// The offsets in `content` where each of the following physical lines starts.
+    pub folds: Vec<usize>,
+}
+
+impl UnfoldedLine<'_> {
+    /// Returns the 1-based number of the physical line where `range` of the content starts,
+    /// and the byte range within that physical line. The range is cut at the end of the
+    /// physical line.
+    pub(crate) fn locate(&self, range: Range<usize>) -> (usize, Range<usize>) {
+        let fold = self.folds.partition_point(|offset| *offset <= range.start);
+        let end = self
+            .folds
+            .get(fold)
+            .map_or(range.end, |next| range.end.min(*next));
+        // A folded physical line starts with the space or tab that was removed
+        let (start, indent) = match fold {
+            0 => (0, 0),
+            _ => (self.folds[fold - 1], 1),
+        };
+        (
+            self.line + fold,
+            range.start - start + indent..end.max(range.start) - start + indent,
+        )
+    }
+
+    pub(crate) fn into_owned(self) -> UnfoldedLine<'static> {
+        UnfoldedLine {
+            line: self.line,
+            content: Cow::Owned(self.content.into_owned()),
+            folds: self.folds,
+        }
+    }
+}
+
+/// Splits the input into logical content lines, unfolding lines as described in
+/// [RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545#section-3.1).
+///
+/// A line break (`CRLF` or `LF`) directly followed by a single space or tab is removed
+/// together with that whitespace character, joining the two physical lines. After a `LF`,
+/// a line which starts with more whitespace is indented instead of folded, and is kept as
+/// it is, while a line can be folded right before a whitespace character after a `CRLF`.
+pub(crate) fn unfold_lines(s: &str) -> Vec<UnfoldedLine<'_>> {
+    let mut lines: Vec<UnfoldedLine> = vec![];
+    let mut after_crlf = false;
+
+    for (idx, terminated_line) in s.split_inclusive('\n').enumerate() {
+        let physical_line = terminated_line
+            .strip_suffix('\n')
+            .map_or(terminated_line, |line| {
+                line.strip_suffix('\r').unwrap_or(line)
+            });
+        let continuation = physical_line
+            .strip_prefix([' ', '\t'])
+            .filter(|rest| after_crlf || !rest.starts_with(char::is_whitespace));
+        after_crlf = terminated_line.ends_with("\r\n");
+        match (continuation, lines.last_mut()) {
+            (Some(continuation), Some(previous)) => {
+                previous.folds.push(previous.content.len());
+                previous.content.to_mut().push_str(continuation);
+            }
+            _ => lines.push(UnfoldedLine {
+                line: idx + 1,
+                content: Cow::Borrowed(physical_line),
+                folds: vec![],
+            }),
+        }
+    }
+
+    lines
+}
 
 /// Attempts to convert a comma separated `&str` to a `Vec<T>` of unique and sorted values.
 /// The function accepts a closure which can be used to validate the values which are parsed.
@@ -29,7 +106,7 @@ pub(crate) fn parse_str_to_vec<T: FromStr + Ord + PartialEq + Copy, F: Fn(T) ->
 
 #[cfg(test)]
 mod tests {
-    use super::parse_str_to_vec;
+    use super::{parse_str_to_vec, unfold_lines};
 
     #[test]
     fn parses_str_to_vec() {
@@ -51,4 +128,51 @@ mod tests {
             assert_eq!(output, expected_output);
         }
     }
+
+    #[test]
+    fn unfolds_lines() {
+        let input = "DTSTART:20120201T093000Z\r\nRRULE:FREQ=WEEKLY;\r\n BYDAY=MO,\r\n\tFR\nEXDATE:20120203T093000Z,\n 20120206T093000Z\n";
+        let lines = unfold_lines(input)
+            .into_iter()
+            .map(|line| (line.line, line.content.into_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                (1, "DTSTART:20120201T093000Z".to_string()),
+                (2, "RRULE:FREQ=WEEKLY;BYDAY=MO,FR".to_string()),
+                (5, "EXDATE:20120203T093000Z,20120206T093000Z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_indented_lines() {
+        let input = "DTSTART:20120201T093000Z\n    RRULE:FREQ=WEEKLY;\n BYDAY=MO\n\t\tEXDATE:20120203T093000Z";
+        let lines = unfold_lines(input)
+            .into_iter()
+            .map(|line| (line.line, line.content.into_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                (1, "DTSTART:20120201T093000Z".to_string()),
+                (2, "    RRULE:FREQ=WEEKLY;BYDAY=MO".to_string()),
+                (4, "\t\tEXDATE:20120203T093000Z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn locates_ranges_of_folded_lines() {
+        let input =
+            "DTSTART:20120201T093000Z\nEXDATE:20120203T093000Z,\n 20120206T093000Z,\n\t2012";
+        let line = &unfold_lines(input)[1];
+        assert_eq!(line.locate(0..6), (2, 0..6));
+        assert_eq!(line.locate(7..23), (2, 7..23));
+        // Cut at the end of the physical line
+        assert_eq!(line.locate(7..30), (2, 7..24));
+        assert_eq!(line.locate(24..40), (3, 1..17));
+        assert_eq!(line.locate(41..45), (4, 1..5));
+    }
 }
@@ -0,0 +1,305 @@
+use crate::tests::common::{check_occurrences, ymd_hms};
+use crate::{Diagnostic, Location, ParseError, RRuleError, VCalendar};
+
+/// Parses `input` as a calendar and returns the parse error with its location.
+fn parse_error_of(input: &str) -> (ParseError, Option<Location>) {
+    match input.parse::<VCalendar>() {
+        Err(RRuleError::ParserError(error)) => {
+            let location = Diagnostic::new(&error, input).get_location().cloned();
+            (error, location)
+        }
+        res => unreachable!("expected a parse error, got {res:?}"),
+    }
+}
+
+#[test]
+fn parses_events_of_calendar() {
+    let calendar: VCalendar = "BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Example Corp.//Example Client//EN
+BEGIN:VEVENT
+UID:19970901T130000Z-123401@example.com
+DTSTAMP:19970901T130000Z
+DTSTART:19970903T163000Z
+DTEND:19970903T173000Z
+SUMMARY:Annual Employee Review
+RRULE:FREQ=YEARLY;COUNT=3
+EXDATE:19980903T163000Z
+CATEGORIES:BUSINESS,HUMAN RESOURCES
+END:VEVENT
+BEGIN:VEVENT
+UID:19970901T130000Z-123402@example.com
+DTSTART;TZID=Europe/Berlin:19970401T090000
+RDATE;TZID=Europe/Berlin:19970402T090000
+X-CUSTOM;X-PARAM=1:custom value
+BEGIN:VALARM
+ACTION:DISPLAY
+TRIGGER:-PT15M
+END:VALARM
+END:VEVENT
+END:VCALENDAR"
+        .parse()
+        .unwrap();
+
+    let events = calendar.get_events();
+    assert_eq!(events.len(), 2);
+
+    let review = &events[0];
+    assert_eq!(
+        review.get_uid(),
+        Some("19970901T130000Z-123401@example.com")
+    );
+    assert_eq!(
+        review.get_property("summary").unwrap().get_value(),
+        "Annual Employee Review"
+    );
+    assert_eq!(
+        review.get_property("CATEGORIES").unwrap().get_value(),
+        "BUSINESS,HUMAN RESOURCES"
+    );
+    // Recurrence properties are part of the rrule set instead
+    assert!(review.get_property("RRULE").is_none());
+    assert!(review.get_property("DTEND").is_none());
+    assert_eq!(review.get_properties().len(), 4);
+    assert_eq!(
+        review.get_rrule_set().get_duration(),
+        Some(chrono::Duration::hours(1).into())
+    );
+    check_occurrences(
+        &review.get_rrule_set().clone().all(10).dates,
+        &["1997-09-03T16:30:00+00:00", "1999-09-03T16:30:00+00:00"],
+    );
+
+    let other = &events[1];
+    let custom = other.get_property("X-CUSTOM").unwrap();
+    assert_eq!(custom.get_parameters(), Some("X-PARAM=1"));
+    assert_eq!(custom.get_value(), "custom value");
+    check_occurrences(
+        &other.get_rrule_set().clone().all(10).dates,
+        &["1997-04-02T09:00:00+02:00"],
+    );
+}
+
+#[test]
+fn event_without_recurrence_occurs_once() {
+    let calendar: VCalendar = "BEGIN:VEVENT
+UID:single
+DTSTART:20200101T100000Z
+SUMMARY:Only once
+END:VEVENT"
+        .parse()
+        .unwrap();
+
+    let events = calendar.into_events();
+    assert_eq!(events.len(), 1);
+    check_occurrences(
+        &events[0].clone().into_rrule_set().all(10).dates,
+        &["2020-01-01T10:00:00+00:00"],
+    );
+}
+
+#[test]
+fn calendar_without_events() {
+    let calendar: VCalendar = "BEGIN:VCALENDAR\nVERSION:2.0\nEND:VCALENDAR"
+        .parse()
+        .unwrap();
+    assert!(calendar.get_events().is_empty());
+}
+
+#[test]
+fn rejects_event_without_start_date() {
+    let res = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:1\nRRULE:FREQ=DAILY\nEND:VEVENT\nEND:VCALENDAR"
+        .parse::<VCalendar>();
+    assert_eq!(
+        res,
+        Err(RRuleError::ParserError(ParseError::MissingStartDate))
+    );
+}
+
+#[test]
+fn rejects_unclosed_event() {
+    let res =
+        parse_error_of("BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART:20200101T100000Z\nEND:VCALENDAR");
+    assert_eq!(
+        res,
+        (
+            ParseError::UnexpectedComponentEnd("VCALENDAR".into()),
+            Some(Location {
+                line: 4,
+                span: 4..13,
+                property: Some("END".into()),
+            })
+        )
+    );
+}
+
+#[test]
+fn resolves_tzid_from_vtimezone() {
+    let calendar: VCalendar = "BEGIN:VCALENDAR
+BEGIN:VTIMEZONE
+TZID:W. Europe Standard Time
+BEGIN:STANDARD
+DTSTART:16010101T030000
+TZOFFSETFROM:+0200
+TZOFFSETTO:+0100
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=10
+END:STANDARD
+BEGIN:DAYLIGHT
+DTSTART:16010101T020000
+TZOFFSETFROM:+0100
+TZOFFSETTO:+0200
+RRULE:FREQ=YEARLY;BYDAY=-1SU;BYMONTH=3
+END:DAYLIGHT
+END:VTIMEZONE
+BEGIN:VEVENT
+UID:weekly
+DTSTART;TZID=W. Europe Standard Time:20230315T090000
+RRULE:FREQ=WEEKLY;COUNT=3
+EXDATE;TZID=W. Europe Standard Time:20230322T090000
+END:VEVENT
+END:VCALENDAR"
+        .parse()
+        .unwrap();
+
+    assert_eq!(calendar.get_timezones().len(), 1);
+    let rrule_set = calendar.get_events()[0].get_rrule_set();
+    assert_eq!(
+        rrule_set.get_dt_start().timezone().name(),
+        "W. Europe Standard Time"
+    );
+    check_occurrences(
+        &rrule_set.clone().all(10).dates,
+        &["2023-03-15T09:00:00+01:00", "2023-03-29T09:00:00+02:00"],
+    );
+}
+
+#[test]
+fn rejects_unknown_tzid() {
+    let res = parse_error_of(
+        "BEGIN:VEVENT\nDTSTART;TZID=Custom Standard Time:20230315T090000\nEND:VEVENT",
+    );
+    assert_eq!(
+        res,
+        (
+            ParseError::InvalidTimezone("Custom Standard Time".into()),
+            Some(Location {
+                line: 2,
+                span: 13..33,
+                property: Some("DTSTART".into()),
+            })
+        )
+    );
+}
+
+#[test]
+fn locates_errors_of_events() {
+    let res = parse_error_of("BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:1\nDTSTART:20200101T100000Z\nRRULE:FREQ=DAILY;COUNT=x\nEND:VEVENT\nEND:VCALENDAR");
+    assert_eq!(
+        res,
+        (
+            ParseError::InvalidCount("x".into()),
+            Some(Location {
+                line: 5,
+                span: 23..24,
+                property: Some("RRULE".into()),
+            })
+        )
+    );
+}
+
+#[test]
+fn folds_modified_instances_into_their_event() {
+    let calendar: VCalendar = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:standup@example.com
+SUMMARY:Moved standup
+RECURRENCE-ID:20230103T090000Z
+DTSTART:20230103T140000Z
+DURATION:PT30M
+END:VEVENT
+BEGIN:VEVENT
+UID:standup@example.com
+SUMMARY:Standup
+DTSTART:20230101T090000Z
+DURATION:PT15M
+RRULE:FREQ=DAILY;COUNT=5
+END:VEVENT
+BEGIN:VEVENT
+UID:standup@example.com
+SUMMARY:Later standup
+RECURRENCE-ID;RANGE=THISANDFUTURE:20230104T090000Z
+DTSTART:20230104T100000Z
+END:VEVENT
+BEGIN:VEVENT
+UID:other@example.com
+RECURRENCE-ID:20230102T120000Z
+DTSTART:20230102T130000Z
+END:VEVENT
+END:VCALENDAR"
+        .parse()
+        .unwrap();
+
+    // The instance without an event to modify is kept as it is
+    let events = calendar.get_events();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].get_uid(), Some("other@example.com"));
+    assert_eq!(
+        events[1].get_recurrence_id(),
+        Some(&ymd_hms(2023, 1, 2, 12, 0, 0))
+    );
+
+    let standup = &events[0];
+    assert_eq!(standup.get_recurrence_id(), None);
+    let modified = standup.get_modified_instances();
+    assert_eq!(modified.len(), 2);
+    assert_eq!(
+        modified[0].get_property("SUMMARY").unwrap().get_value(),
+        "Moved standup"
+    );
+    assert_eq!(
+        modified[1].get_recurrence_id(),
+        Some(&ymd_hms(2023, 1, 4, 9, 0, 0))
+    );
+
+    // The moved instance only occurs once, at its new start
+    let occurrences: Vec<_> = standup
+        .get_rrule_set()
+        .occurrences()
+        .map(|occurrence| (occurrence.start, occurrence.end))
+        .collect();
+    assert_eq!(
+        occurrences,
+        vec![
+            (ymd_hms(2023, 1, 1, 9, 0, 0), ymd_hms(2023, 1, 1, 9, 15, 0)),
+            (ymd_hms(2023, 1, 2, 9, 0, 0), ymd_hms(2023, 1, 2, 9, 15, 0)),
+            (
+                ymd_hms(2023, 1, 3, 14, 0, 0),
+                ymd_hms(2023, 1, 3, 14, 30, 0)
+            ),
+            (
+                ymd_hms(2023, 1, 4, 10, 0, 0),
+                ymd_hms(2023, 1, 4, 10, 15, 0)
+            ),
+            (
+                ymd_hms(2023, 1, 5, 10, 0, 0),
+                ymd_hms(2023, 1, 5, 10, 15, 0)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn rejects_invalid_recurrence_id() {
+    let res = parse_error_of("BEGIN:VCALENDAR\nBEGIN:VEVENT\nUID:1\nRECURRENCE-ID;RANGE=THISANDPRIOR:20200101T100000Z\nDTSTART:20200101T100000Z\nEND:VEVENT\nEND:VCALENDAR");
+    assert_eq!(
+        res,
+        (
+            ParseError::InvalidRecurrenceRange("THISANDPRIOR".into()),
+            Some(Location {
+                line: 4,
+                span: 20..32,
+                property: Some("RECURRENCE-ID".into()),
+            })
+        )
+    );
+}
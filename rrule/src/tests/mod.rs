@@ -1,5 +1,6 @@
 #![cfg(test)]
 
+mod calendar;
 mod common;
 mod datetime;
 mod daylight_saving;
@@ -7,4 +8,7 @@ mod regression;
 mod rfc_tests;
 mod rrule;
 mod rruleset;
+mod rscale;
 mod serde;
+#[cfg(feature = "text")]
+mod text;
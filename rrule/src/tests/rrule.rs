@@ -1,6 +1,6 @@
 use crate::core::Tz;
 use crate::tests::common::{test_recurring_rrule, ymd_hms};
-use crate::{Frequency, NWeekday, RRule, RRuleSet, Weekday};
+use crate::{Frequency, NWeekday, RRule, RRuleSet, Skip, Weekday};
 use chrono::{Datelike, TimeZone};
 
 #[test]
@@ -3938,3 +3938,154 @@ fn test_between_inclusive_both_hit() {
 
     assert_eq!(vec![after, middle, before], rrule.all_unchecked());
 }
+
+#[test]
+fn monthly_by_month_day_with_skip() {
+    let rrule = RRule {
+        freq: Frequency::Monthly,
+        count: Some(5),
+        by_month_day: vec![31],
+        skip: Some(Skip::Backward),
+        ..Default::default()
+    };
+    test_recurring_rrule(
+        rrule.clone(),
+        true,
+        ymd_hms(2024, 1, 31, 9, 0, 0),
+        &[
+            ymd_hms(2024, 1, 31, 9, 0, 0),
+            ymd_hms(2024, 2, 29, 9, 0, 0),
+            ymd_hms(2024, 3, 31, 9, 0, 0),
+            ymd_hms(2024, 4, 30, 9, 0, 0),
+            ymd_hms(2024, 5, 31, 9, 0, 0),
+        ],
+    );
+    test_recurring_rrule(
+        rrule.skip(Skip::Forward),
+        true,
+        ymd_hms(2024, 1, 31, 9, 0, 0),
+        &[
+            ymd_hms(2024, 1, 31, 9, 0, 0),
+            ymd_hms(2024, 3, 1, 9, 0, 0),
+            ymd_hms(2024, 3, 31, 9, 0, 0),
+            ymd_hms(2024, 5, 1, 9, 0, 0),
+            ymd_hms(2024, 5, 31, 9, 0, 0),
+        ],
+    );
+}
+
+#[test]
+fn monthly_by_negative_month_day_with_skip() {
+    let rrule = RRule {
+        freq: Frequency::Monthly,
+        count: Some(4),
+        by_month_day: vec![-30],
+        skip: Some(Skip::Backward),
+        ..Default::default()
+    };
+    test_recurring_rrule(
+        rrule.clone(),
+        true,
+        ymd_hms(2024, 1, 2, 9, 0, 0),
+        &[
+            ymd_hms(2024, 1, 2, 9, 0, 0),
+            ymd_hms(2024, 1, 31, 9, 0, 0),
+            ymd_hms(2024, 3, 2, 9, 0, 0),
+            ymd_hms(2024, 4, 1, 9, 0, 0),
+        ],
+    );
+    test_recurring_rrule(
+        rrule.skip(Skip::Forward),
+        true,
+        ymd_hms(2024, 1, 2, 9, 0, 0),
+        &[
+            ymd_hms(2024, 1, 2, 9, 0, 0),
+            ymd_hms(2024, 2, 1, 9, 0, 0),
+            ymd_hms(2024, 3, 2, 9, 0, 0),
+            ymd_hms(2024, 4, 1, 9, 0, 0),
+        ],
+    );
+}
+
+#[test]
+fn yearly_on_leap_day_with_skip() {
+    let rrule = RRule {
+        freq: Frequency::Yearly,
+        count: Some(5),
+        skip: Some(Skip::Backward),
+        ..Default::default()
+    };
+    test_recurring_rrule(
+        rrule.clone(),
+        true,
+        ymd_hms(2024, 2, 29, 9, 0, 0),
+        &[
+            ymd_hms(2024, 2, 29, 9, 0, 0),
+            ymd_hms(2025, 2, 28, 9, 0, 0),
+            ymd_hms(2026, 2, 28, 9, 0, 0),
+            ymd_hms(2027, 2, 28, 9, 0, 0),
+            ymd_hms(2028, 2, 29, 9, 0, 0),
+        ],
+    );
+    test_recurring_rrule(
+        rrule.skip(Skip::Forward),
+        true,
+        ymd_hms(2024, 2, 29, 9, 0, 0),
+        &[
+            ymd_hms(2024, 2, 29, 9, 0, 0),
+            ymd_hms(2025, 3, 1, 9, 0, 0),
+            ymd_hms(2026, 3, 1, 9, 0, 0),
+            ymd_hms(2027, 3, 1, 9, 0, 0),
+            ymd_hms(2028, 2, 29, 9, 0, 0),
+        ],
+    );
+}
+
+#[test]
+fn skip_with_by_weekday_and_by_set_pos() {
+    // The day the 30th moved to must still be a weekday
+    let rrule = RRule {
+        freq: Frequency::Monthly,
+        count: Some(3),
+        by_month_day: vec![30],
+        by_weekday: vec![
+            NWeekday::Every(Weekday::Mon),
+            NWeekday::Every(Weekday::Tue),
+            NWeekday::Every(Weekday::Wed),
+            NWeekday::Every(Weekday::Thu),
+            NWeekday::Every(Weekday::Fri),
+        ],
+        skip: Some(Skip::Backward),
+        ..Default::default()
+    };
+    test_recurring_rrule(
+        rrule,
+        true,
+        ymd_hms(2026, 1, 1, 9, 0, 0),
+        &[
+            ymd_hms(2026, 1, 30, 9, 0, 0),
+            ymd_hms(2026, 3, 30, 9, 0, 0),
+            ymd_hms(2026, 4, 30, 9, 0, 0),
+        ],
+    );
+
+    // The moved days are part of the set `BYSETPOS` selects from
+    let rrule = RRule {
+        freq: Frequency::Monthly,
+        count: Some(3),
+        by_month_day: vec![29, 30, 31],
+        by_set_pos: vec![-1],
+        skip: Some(Skip::Backward),
+        ..Default::default()
+    };
+    test_recurring_rrule(
+        rrule,
+        true,
+        ymd_hms(2025, 1, 1, 9, 0, 0),
+        &[
+            ymd_hms(2025, 1, 31, 9, 0, 0),
+            ymd_hms(2025, 2, 28, 9, 0, 0),
+            ymd_hms(2025, 3, 31, 9, 0, 0),
+        ],
+    );
+}
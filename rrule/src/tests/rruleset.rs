@@ -1,5 +1,9 @@
 use crate::tests::common::{check_occurrences, test_recurring_rrule_set, ymd_hms};
-use crate::{Frequency, NWeekday, RRule, RRuleSet, Weekday};
+use crate::{
+    Frequency, IterLimits, Location, NWeekday, Occurrence, ParseError, RRule, RRuleError, RRuleSet,
+    RecurrenceOverride, RecurrenceRange, Tz, ValidationError, Weekday,
+};
+use chrono::{Duration, TimeZone};
 
 #[test]
 #[cfg(feature = "exrule")]
@@ -658,3 +662,1107 @@ fn yearly_with_interval_2() {
         &[ymd_hms(1960, 1, 1, 9, 0, 0), ymd_hms(1962, 1, 1, 9, 0, 0)],
     );
 }
+
+#[test]
+#[cfg(feature = "windows-timezones")]
+fn resolves_windows_timezone_ids() {
+    let set: RRuleSet = "DTSTART;TZID=Romance Standard Time:20230320T090000\n\
+        RRULE:FREQ=WEEKLY;COUNT=3\n\
+        EXDATE;TZID=Pacific Standard Time:20230327T000000"
+        .parse()
+        .unwrap();
+
+    assert_eq!(set.get_dt_start().timezone(), Tz::Europe__Paris);
+    check_occurrences(
+        &set.all(10).dates,
+        &["2023-03-20T09:00:00+01:00", "2023-04-03T09:00:00+02:00"],
+    );
+}
+
+#[test]
+fn fixed_offset_timezone() {
+    let offset = chrono::FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+    let dt_start = offset
+        .with_ymd_and_hms(2023, 3, 20, 9, 0, 0)
+        .unwrap()
+        .with_timezone(&Tz::from(offset));
+
+    let rrule = RRule::new(Frequency::Daily)
+        .count(2)
+        .validate(dt_start)
+        .unwrap();
+    let set = RRuleSet::new(dt_start).rrule(rrule);
+
+    assert_eq!(
+        set.to_string(),
+        "DTSTART;TZID=+0530:20230320T090000\nRRULE:FREQ=DAILY;COUNT=2;BYHOUR=9;BYMINUTE=0;BYSECOND=0"
+    );
+    assert_eq!(set.to_string().parse::<RRuleSet>(), Ok(set.clone()));
+    check_occurrences(
+        &set.all(10).dates,
+        &["2023-03-20T09:00:00+05:30", "2023-03-21T09:00:00+05:30"],
+    );
+}
+
+#[test]
+fn generic_over_chrono_timezones() {
+    let berlin = chrono_tz::Europe::Berlin;
+    let dt_start = berlin.with_ymd_and_hms(2023, 3, 25, 9, 0, 0).unwrap();
+    let rrule = RRule::new(Frequency::Daily)
+        .count(3)
+        .validate(dt_start.with_timezone(&Tz::Europe__Berlin))
+        .unwrap();
+    let set = RRuleSet::new(dt_start)
+        .rrule(rrule)
+        .exdate(berlin.with_ymd_and_hms(2023, 3, 26, 9, 0, 0).unwrap())
+        .rdate(berlin.with_ymd_and_hms(2023, 3, 30, 12, 0, 0).unwrap());
+
+    let dates: Vec<chrono::DateTime<chrono_tz::Tz>> = set.into_iter().collect();
+    assert_eq!(
+        dates
+            .iter()
+            .map(chrono::DateTime::to_rfc3339)
+            .collect::<Vec<_>>(),
+        [
+            "2023-03-25T09:00:00+01:00",
+            "2023-03-27T09:00:00+02:00",
+            "2023-03-30T12:00:00+02:00"
+        ]
+    );
+
+    let result = set.after(dates[1]).all(10);
+    assert_eq!(result.dates, dates[1..]);
+}
+
+#[test]
+fn iterates_in_reverse() {
+    let sets = [
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;UNTIL=20211231T090000Z",
+        "DTSTART:20200229T090000Z\nRRULE:FREQ=YEARLY;INTERVAL=3;UNTIL=20400101T000000Z",
+        "DTSTART:19970902T090000Z\nRRULE:FREQ=YEARLY;UNTIL=20050101T000000Z;BYDAY=TU,TH;BYSETPOS=1,-1",
+        "DTSTART:19970902T090000Z\nRRULE:FREQ=MONTHLY;UNTIL=19990101T000000Z;BYDAY=-1FR;BYMONTHDAY=-1,-2,-3,-4,-5,-6,-7",
+        "DTSTART:20230103T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=MO,SU;UNTIL=20230601T000000Z",
+        "DTSTART:20230101T093000Z\nRRULE:FREQ=DAILY;INTERVAL=5;BYHOUR=8,17;BYMINUTE=15;UNTIL=20230301T000000Z",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=HOURLY;INTERVAL=5;BYHOUR=1,11,21;BYDAY=MO;UNTIL=20230401T000000Z",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=MINUTELY;INTERVAL=15;BYHOUR=10;BYMINUTE=0,15,45;UNTIL=20230110T000000Z",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=SECONDLY;INTERVAL=20;BYMINUTE=31;UNTIL=20230101T113300Z",
+        "DTSTART;TZID=Europe/Berlin:20230301T023000\nRRULE:FREQ=DAILY;UNTIL=20231105T000000Z",
+        "DTSTART;TZID=Europe/Berlin:20230320T000000\nRRULE:FREQ=HOURLY;INTERVAL=2;UNTIL=20230330T000000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=20;BYDAY=MO,WE\nRDATE:20230105T120000Z,20230104T090000Z\nEXDATE:20230111T090000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;UNTIL=20230301T000000Z\nEXRULE:FREQ=WEEKLY;BYDAY=SA,SU\nRDATE:20230301T120000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=WEEKLY;COUNT=10\nRRULE:FREQ=DAILY;INTERVAL=3;UNTIL=20230201T000000Z",
+        "DTSTART:20240223T090000Z\nRRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=14;SKIP=FORWARD;UNTIL=20400101T000000Z",
+        "DTSTART:20240223T090000Z\nRRULE:RSCALE=HEBREW;FREQ=MONTHLY;INTERVAL=5;UNTIL=20300101T000000Z",
+        "DTSTART:20240311T090000Z\nRRULE:RSCALE=ISLAMIC-CIVIL;FREQ=MONTHLY;BYMONTHDAY=30,1;SKIP=FORWARD;UNTIL=20260101T000000Z",
+        "DTSTART:20240131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31,-31;SKIP=FORWARD;UNTIL=20260101T000000Z",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let mut expected = rrule_set.clone().all_unchecked();
+        expected.reverse();
+        let dates: Vec<_> = rrule_set.iter_rev().unwrap().collect();
+        assert_eq!(dates, expected, "{set}");
+    }
+}
+
+#[test]
+fn iterates_in_reverse_with_before_and_after() {
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;BYDAY=MO,TH"
+        .parse()
+        .unwrap();
+    assert!(rrule_set.iter_rev().is_err());
+
+    let rrule_set = rrule_set
+        .after(ymd_hms(2023, 1, 10, 0, 0, 0))
+        .before(ymd_hms(2023, 1, 23, 9, 0, 0));
+    let dates: Vec<_> = rrule_set.iter_rev().unwrap().collect();
+    check_occurrences(
+        &dates,
+        &[
+            "2023-01-23T09:00:00+00:00",
+            "2023-01-19T09:00:00+00:00",
+            "2023-01-16T09:00:00+00:00",
+            "2023-01-12T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn iterates_from_a_date() {
+    let sets = [
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31,-1",
+        "DTSTART:20200229T090000Z\nRRULE:FREQ=YEARLY;INTERVAL=3",
+        "DTSTART:19970902T090000Z\nRRULE:FREQ=YEARLY;BYDAY=TU,TH;BYSETPOS=1,-1",
+        "DTSTART:20230103T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=MO,SU",
+        "DTSTART:20230101T093000Z\nRRULE:FREQ=DAILY;INTERVAL=5;BYHOUR=8,17;BYMINUTE=15",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=HOURLY;INTERVAL=5;BYHOUR=1,11,21;BYDAY=MO",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=MINUTELY;INTERVAL=15;BYHOUR=10;BYMINUTE=0,15,45",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=SECONDLY;INTERVAL=20;BYMINUTE=31",
+        "DTSTART;TZID=Europe/Berlin:20230320T023000\nRRULE:FREQ=HOURLY;INTERVAL=2",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=40;BYDAY=MO,WE\nRDATE:20230125T120000Z\nEXDATE:20230201T090000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY\nEXRULE:FREQ=WEEKLY;BYDAY=SA,SU",
+        "DTSTART:20240223T090000Z\nRRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=14;SKIP=BACKWARD",
+        "DTSTART:20240311T090000Z\nRRULE:RSCALE=ISLAMIC-CIVIL;FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=30;SKIP=FORWARD",
+        "DTSTART:20200229T090000Z\nRRULE:FREQ=YEARLY;SKIP=BACKWARD",
+    ];
+    let seek_dates = [
+        ymd_hms(2022, 1, 1, 0, 0, 0),
+        ymd_hms(2023, 1, 25, 10, 0, 0),
+        ymd_hms(2023, 3, 26, 2, 0, 0),
+        ymd_hms(2024, 2, 29, 9, 0, 0),
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        for dt in seek_dates {
+            let expected: Vec<_> = rrule_set
+                .clone()
+                .into_iter()
+                .skip_while(|date| *date < dt)
+                .take(20)
+                .collect();
+            let dates: Vec<_> = rrule_set.iter_from(dt).take(20).collect();
+            assert_eq!(dates, expected, "{set} from {dt}");
+        }
+    }
+}
+
+#[test]
+fn seeks_without_iterating_from_dt_start() {
+    let rrule_set: RRuleSet = "DTSTART:20010101T000000Z\nRRULE:FREQ=SECONDLY;INTERVAL=7"
+        .parse()
+        .unwrap();
+    let result = rrule_set
+        .clone()
+        .after(ymd_hms(2024, 6, 1, 12, 0, 0))
+        .all(2);
+    check_occurrences(
+        &result.dates,
+        &["2024-06-01T12:00:02+00:00", "2024-06-01T12:00:09+00:00"],
+    );
+
+    let mut iter = rrule_set.into_iter();
+    iter.seek(ymd_hms(2024, 6, 1, 12, 0, 0));
+    iter.seek(ymd_hms(2001, 1, 1, 0, 0, 0));
+    assert_eq!(iter.next(), Some(ymd_hms(2024, 6, 1, 12, 0, 2)));
+}
+
+#[test]
+fn occurrences_end_after_the_duration() {
+    let rrule_set: RRuleSet =
+        "DTSTART:20230101T090000Z\nDTEND:20230101T103000Z\nRRULE:FREQ=DAILY;COUNT=2"
+            .parse()
+            .unwrap();
+    assert_eq!(rrule_set.get_duration(), Some(Duration::minutes(90).into()));
+
+    let occurrences: Vec<_> = rrule_set.occurrences().collect();
+    assert_eq!(
+        occurrences,
+        vec![
+            Occurrence {
+                recurrence_id: ymd_hms(2023, 1, 1, 9, 0, 0),
+                start: ymd_hms(2023, 1, 1, 9, 0, 0),
+                end: ymd_hms(2023, 1, 1, 10, 30, 0),
+                payload: None,
+            },
+            Occurrence {
+                recurrence_id: ymd_hms(2023, 1, 2, 9, 0, 0),
+                start: ymd_hms(2023, 1, 2, 9, 0, 0),
+                end: ymd_hms(2023, 1, 2, 10, 30, 0),
+                payload: None,
+            },
+        ]
+    );
+
+    // Without duration, occurrences end at their start
+    let rrule_set = RRuleSet::new(ymd_hms(2023, 1, 1, 9, 0, 0)).rdate(ymd_hms(2023, 1, 5, 9, 0, 0));
+    let occurrences: Vec<_> = rrule_set.occurrences().collect();
+    assert_eq!(occurrences[0].start, occurrences[0].end);
+}
+
+#[test]
+fn adds_days_of_a_duration_in_local_time() {
+    let tests = [
+        (
+            "DURATION:P1D",
+            ["2023-03-26T09:00:00+02:00", "2023-10-29T09:00:00+01:00"],
+        ),
+        (
+            "DURATION:PT24H",
+            ["2023-03-26T10:00:00+02:00", "2023-10-29T08:00:00+01:00"],
+        ),
+        (
+            "DURATION:P1DT1H",
+            ["2023-03-26T10:00:00+02:00", "2023-10-29T10:00:00+01:00"],
+        ),
+    ];
+    for (duration, expected_ends) in tests {
+        // Both occurrences end after a daylight saving time transition in Berlin
+        let rrule_set: RRuleSet = format!(
+            "DTSTART;TZID=Europe/Berlin:20230325T090000\n{duration}\nRDATE;TZID=Europe/Berlin:20230325T090000,20231028T090000"
+        )
+        .parse()
+        .unwrap();
+        let ends = rrule_set
+            .occurrences()
+            .map(|occurrence| occurrence.end.to_rfc3339())
+            .collect::<Vec<_>>();
+        assert_eq!(ends, expected_ends, "{duration}");
+    }
+}
+
+#[test]
+fn finds_overlapping_occurrences() {
+    let rrule_set: RRuleSet =
+        "DTSTART:20230101T220000Z\nDURATION:PT4H\nRRULE:FREQ=DAILY\nEXDATE:20230104T220000Z"
+            .parse()
+            .unwrap();
+
+    let starts = |start, end| {
+        rrule_set
+            .overlapping(start..end)
+            .into_iter()
+            .map(|occurrence| occurrence.start)
+            .collect::<Vec<_>>()
+    };
+    // Includes the occurrence which started the day before
+    assert_eq!(
+        starts(ymd_hms(2023, 1, 3, 1, 0, 0), ymd_hms(2023, 1, 3, 23, 0, 0)),
+        vec![ymd_hms(2023, 1, 2, 22, 0, 0), ymd_hms(2023, 1, 3, 22, 0, 0)]
+    );
+    // Occurrences ending at the start or starting at the end of the range are excluded
+    assert!(starts(ymd_hms(2023, 1, 3, 2, 0, 0), ymd_hms(2023, 1, 3, 22, 0, 0)).is_empty());
+    // Excluded dates are not returned
+    assert_eq!(
+        starts(ymd_hms(2023, 1, 4, 12, 0, 0), ymd_hms(2023, 1, 6, 0, 0, 0)),
+        vec![ymd_hms(2023, 1, 5, 22, 0, 0)]
+    );
+    // `before` and `after` are not used
+    let bounded = rrule_set.clone().after(ymd_hms(2023, 1, 10, 0, 0, 0));
+    assert_eq!(
+        bounded
+            .overlapping(ymd_hms(2023, 1, 1, 0, 0, 0)..ymd_hms(2023, 1, 2, 0, 0, 0))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn finds_overlapping_occurrences_without_duration() {
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=HOURLY;INTERVAL=6"
+        .parse()
+        .unwrap();
+
+    let occurrences =
+        rrule_set.overlapping(ymd_hms(2023, 1, 1, 15, 0, 0)..ymd_hms(2023, 1, 2, 3, 0, 0));
+    let starts: Vec<_> = occurrences
+        .iter()
+        .map(|occurrence| occurrence.start)
+        .collect();
+    assert_eq!(
+        starts,
+        vec![ymd_hms(2023, 1, 1, 15, 0, 0), ymd_hms(2023, 1, 1, 21, 0, 0)]
+    );
+    assert!(occurrences
+        .iter()
+        .all(|occurrence| occurrence.start == occurrence.end));
+}
+
+#[test]
+fn duration_string_roundtrip() {
+    let tests = [
+        ("DURATION:PT1H30M", "DURATION:PT1H30M"),
+        ("DURATION:P1DT12H", "DURATION:P1DT12H"),
+        ("DURATION:P14D", "DURATION:P2W"),
+        ("DURATION:PT1H5S", "DURATION:PT1H0M5S"),
+        ("DTEND:20230101T090000Z", "DURATION:PT0S"),
+        ("DTEND;TZID=Europe/Berlin:20230102T100000", "DURATION:PT24H"),
+    ];
+    for (input, expected) in tests {
+        let rrule_set: RRuleSet = format!("DTSTART:20230101T090000Z\n{input}\nRRULE:FREQ=DAILY")
+            .parse()
+            .unwrap();
+        let output = rrule_set.to_string();
+        assert_eq!(output.lines().nth(1), Some(expected));
+        assert_eq!(output.parse::<RRuleSet>().unwrap(), rrule_set);
+    }
+
+    // Negative durations are replaced by zero
+    let rrule_set = RRuleSet::new(ymd_hms(2023, 1, 1, 9, 0, 0)).duration(Duration::hours(-1));
+    assert_eq!(rrule_set.get_duration(), Some(Duration::zero().into()));
+}
+
+#[test]
+fn yields_overridden_occurrences_in_order() {
+    let rrule_set: RRuleSet = "DTSTART:20230102T090000Z\nDURATION:PT1H\nRRULE:FREQ=DAILY;COUNT=5"
+        .parse()
+        .unwrap();
+    let rrule_set = rrule_set
+        // Moved after the next instance
+        .recurrence_override(
+            ymd_hms(2023, 1, 2, 9, 0, 0),
+            RecurrenceOverride::new(ymd_hms(2023, 1, 3, 14, 0, 0))
+                .duration(Duration::minutes(30))
+                .payload("moved"),
+        )
+        // Moved before the previous instance
+        .recurrence_override(
+            ymd_hms(2023, 1, 5, 9, 0, 0),
+            RecurrenceOverride::new(ymd_hms(2023, 1, 3, 8, 0, 0)),
+        )
+        // Not an instance of the set
+        .recurrence_override(
+            ymd_hms(2023, 1, 4, 10, 0, 0),
+            RecurrenceOverride::new(ymd_hms(2023, 1, 1, 10, 0, 0)),
+        );
+
+    let occurrences: Vec<_> = rrule_set.occurrences().collect();
+    let starts: Vec<_> = occurrences
+        .iter()
+        .map(|occurrence| occurrence.start)
+        .collect();
+    assert_eq!(
+        starts,
+        vec![
+            ymd_hms(2023, 1, 3, 8, 0, 0),
+            ymd_hms(2023, 1, 3, 9, 0, 0),
+            ymd_hms(2023, 1, 3, 14, 0, 0),
+            ymd_hms(2023, 1, 4, 9, 0, 0),
+            ymd_hms(2023, 1, 6, 9, 0, 0),
+        ]
+    );
+    assert_eq!(
+        occurrences[2],
+        Occurrence {
+            recurrence_id: ymd_hms(2023, 1, 2, 9, 0, 0),
+            start: ymd_hms(2023, 1, 3, 14, 0, 0),
+            end: ymd_hms(2023, 1, 3, 14, 30, 0),
+            payload: Some("moved".into()),
+        }
+    );
+    assert_eq!(occurrences[0].end, ymd_hms(2023, 1, 3, 9, 0, 0));
+    // The dates of the set itself are not modified
+    assert_eq!(
+        rrule_set.clone().all(10).dates[0],
+        ymd_hms(2023, 1, 2, 9, 0, 0)
+    );
+
+    for (start, end) in [
+        (ymd_hms(2023, 1, 3, 8, 30, 0), ymd_hms(2023, 1, 3, 14, 0, 0)),
+        (ymd_hms(2023, 1, 3, 14, 0, 0), ymd_hms(2023, 1, 7, 0, 0, 0)),
+        (ymd_hms(2022, 1, 1, 0, 0, 0), ymd_hms(2023, 1, 3, 8, 30, 0)),
+    ] {
+        let expected: Vec<_> = occurrences
+            .iter()
+            .filter(|occurrence| occurrence.overlaps(&(start..end)))
+            .cloned()
+            .collect();
+        assert_eq!(rrule_set.overlapping(start..end), expected);
+    }
+}
+
+#[test]
+fn overrides_this_and_future_occurrences() {
+    let rrule_set: RRuleSet =
+        "DTSTART:20230102T090000Z\nDURATION:PT1H\nRRULE:FREQ=WEEKLY;BYDAY=MO,TH"
+            .parse()
+            .unwrap();
+    let rrule_set = rrule_set
+        .recurrence_override(
+            ymd_hms(2023, 1, 9, 9, 0, 0),
+            RecurrenceOverride::new(ymd_hms(2023, 1, 8, 15, 0, 0))
+                .duration(Duration::hours(2))
+                .payload("afternoon")
+                .range(RecurrenceRange::ThisAndFuture),
+        )
+        .recurrence_override(
+            ymd_hms(2023, 1, 16, 9, 0, 0),
+            RecurrenceOverride::new(ymd_hms(2023, 1, 16, 9, 0, 0)).payload("cancelled"),
+        );
+
+    let occurrences: Vec<_> = rrule_set.occurrences().take(6).collect();
+    let starts: Vec<_> = occurrences
+        .iter()
+        .map(|occurrence| occurrence.start)
+        .collect();
+    assert_eq!(
+        starts,
+        vec![
+            ymd_hms(2023, 1, 2, 9, 0, 0),
+            ymd_hms(2023, 1, 5, 9, 0, 0),
+            ymd_hms(2023, 1, 8, 15, 0, 0),
+            ymd_hms(2023, 1, 11, 15, 0, 0),
+            ymd_hms(2023, 1, 16, 9, 0, 0),
+            ymd_hms(2023, 1, 18, 15, 0, 0),
+        ]
+    );
+    let payloads: Vec<_> = occurrences
+        .iter()
+        .map(|occurrence| occurrence.payload.as_deref())
+        .collect();
+    assert_eq!(
+        payloads,
+        vec![
+            None,
+            None,
+            Some("afternoon"),
+            Some("afternoon"),
+            Some("cancelled"),
+            Some("afternoon")
+        ]
+    );
+    assert_eq!(occurrences[4].end, ymd_hms(2023, 1, 16, 11, 0, 0));
+
+    let overlapping =
+        rrule_set.overlapping(ymd_hms(2023, 1, 11, 16, 0, 0)..ymd_hms(2023, 1, 18, 0, 0, 0));
+    assert_eq!(overlapping, occurrences[3..5].to_vec());
+}
+
+#[test]
+fn ignores_this_and_future_override_of_missing_instance() {
+    let rrule_set: RRuleSet =
+        "DTSTART:20230102T090000Z\nDURATION:PT1H\nRRULE:FREQ=WEEKLY;BYDAY=MO,TH\nEXDATE:20230112T090000Z"
+            .parse()
+            .unwrap();
+    let original: Vec<_> = rrule_set.occurrences().take(6).collect();
+
+    // Neither the 10th of January nor an excluded instance is an instance of the set
+    for recurrence_id in [ymd_hms(2023, 1, 10, 9, 0, 0), ymd_hms(2023, 1, 12, 9, 0, 0)] {
+        let rrule_set = rrule_set.clone().recurrence_override(
+            recurrence_id,
+            RecurrenceOverride::new(ymd_hms(2023, 1, 10, 15, 0, 0))
+                .payload("afternoon")
+                .range(RecurrenceRange::ThisAndFuture),
+        );
+        assert_eq!(
+            rrule_set.occurrences().take(6).collect::<Vec<_>>(),
+            original
+        );
+        assert_eq!(
+            rrule_set.overlapping(ymd_hms(2023, 1, 1, 0, 0, 0)..ymd_hms(2023, 1, 20, 0, 0, 0)),
+            original[..5].to_vec()
+        );
+    }
+}
+
+#[test]
+fn splits_at_an_instance() {
+    let sets = [
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=10",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20230501T000000Z",
+        "DTSTART;TZID=Europe/Berlin:20230301T023000\nRRULE:FREQ=DAILY;COUNT=40\nEXDATE:20230327T003000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=MONTHLY;COUNT=3\nRRULE:FREQ=WEEKLY;COUNT=20\nRDATE:20230110T120000Z,20230301T120000Z\nEXDATE:20230115T090000Z,20230212T090000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=20\nEXRULE:FREQ=WEEKLY;COUNT=4;BYDAY=SA,SU",
+        "DTSTART:20230101T090000\nRRULE:FREQ=HOURLY;INTERVAL=7;COUNT=30",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let dates = rrule_set.clone().all(100).dates;
+        for dt in [dates[0], dates[1], dates[5], dates[dates.len() - 1]] {
+            let (first, second) = rrule_set.split_at(dt);
+            let first_dates = first.clone().all(100).dates;
+            let second_dates = second.clone().all(100).dates;
+            assert!(first_dates.iter().all(|date| *date < dt), "{set} at {dt}");
+            assert_eq!(second.get_dt_start(), &dt);
+            assert_eq!([first_dates, second_dates].concat(), dates, "{set} at {dt}");
+
+            // Both sets can be written and parsed back, unless the first one is empty
+            if dt != dates[0] {
+                assert_eq!(first.to_string().parse::<RRuleSet>().unwrap(), first);
+            }
+            assert_eq!(second.to_string().parse::<RRuleSet>().unwrap(), second);
+        }
+    }
+}
+
+#[test]
+fn splits_count_and_dates() {
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=10\nRRULE:FREQ=MONTHLY;COUNT=2\nRDATE:20230102T120000Z,20230201T120000Z\nEXDATE:20230103T090000Z,20230105T090000Z"
+        .parse()
+        .unwrap();
+    let rrule_set = rrule_set.recurrence_override(
+        ymd_hms(2023, 1, 6, 9, 0, 0),
+        RecurrenceOverride::new(ymd_hms(2023, 1, 6, 10, 0, 0)),
+    );
+    let (first, second) = rrule_set.split_at(ymd_hms(2023, 1, 4, 9, 0, 0));
+
+    let counts = |rrule_set: &RRuleSet| {
+        rrule_set
+            .get_rrule()
+            .iter()
+            .map(|rrule| (rrule.get_count(), rrule.get_until().copied()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        counts(&first),
+        vec![
+            (None, Some(ymd_hms(2023, 1, 3, 9, 0, 0))),
+            (None, Some(ymd_hms(2023, 1, 1, 9, 0, 0))),
+        ]
+    );
+    assert_eq!(counts(&second), vec![(Some(7), None), (Some(1), None)]);
+    assert_eq!(first.get_rdate(), &vec![ymd_hms(2023, 1, 2, 12, 0, 0)]);
+    assert_eq!(second.get_rdate(), &vec![ymd_hms(2023, 2, 1, 12, 0, 0)]);
+    assert_eq!(first.get_exdate(), &vec![ymd_hms(2023, 1, 3, 9, 0, 0)]);
+    assert_eq!(second.get_exdate(), &vec![ymd_hms(2023, 1, 5, 9, 0, 0)]);
+    assert!(first.get_recurrence_overrides().is_empty());
+    assert_eq!(second.get_recurrence_overrides().len(), 1);
+
+    // Rules which ended before the split are kept as they are, and removed from the second set
+    let (first, second) = rrule_set.split_at(ymd_hms(2023, 3, 1, 9, 0, 0));
+    assert_eq!(counts(&first), vec![(Some(10), None), (Some(2), None)]);
+    assert!(second.get_rrule().is_empty());
+
+    // Nothing is before the start of the set
+    let (first, second) = rrule_set.split_at(ymd_hms(2022, 1, 1, 9, 0, 0));
+    assert!(first.get_rrule().is_empty());
+    assert_eq!(second, rrule_set);
+}
+
+#[test]
+fn splits_instances_moved_across_the_split() {
+    let sets = [
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=FORWARD;UNTIL=20250101T000000Z",
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=FORWARD;COUNT=60",
+        "DTSTART:20200229T090000Z\nRRULE:FREQ=YEARLY;SKIP=FORWARD;UNTIL=20300101T000000Z",
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=BACKWARD;UNTIL=20250101T000000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=WEEKLY;BYDAY=MO,FR;UNTIL=20230501T000000Z\nRRULE:FREQ=DAILY;UNTIL=20230110T000000Z",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let dates = rrule_set.clone().all(1000).dates;
+        for dt in [
+            ymd_hms(2020, 7, 1, 0, 0, 0),
+            ymd_hms(2021, 3, 1, 0, 0, 0),
+            ymd_hms(2023, 1, 4, 12, 0, 0),
+            ymd_hms(2023, 2, 1, 0, 0, 0),
+            ymd_hms(2023, 3, 1, 9, 0, 0),
+        ] {
+            let (first, second) = rrule_set.split_at(dt);
+            let first_dates = first.clone().all(1000).dates;
+            let second_dates = second.clone().all(1000).dates;
+            assert!(first_dates.iter().all(|date| *date < dt), "{set} at {dt}");
+            assert_eq!([first_dates, second_dates].concat(), dates, "{set} at {dt}");
+        }
+    }
+
+    let rrule_set: RRuleSet =
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=FORWARD;UNTIL=20250101T000000Z"
+            .parse()
+            .unwrap();
+    let (first, second) = rrule_set.split_at(ymd_hms(2020, 7, 1, 0, 0, 0));
+    assert_eq!(
+        first.get_rrule()[0].get_until(),
+        Some(&ymd_hms(2020, 5, 31, 9, 0, 0))
+    );
+    assert_eq!(second.get_dt_start(), &ymd_hms(2020, 7, 1, 9, 0, 0));
+    assert_eq!(second.get_rdate(), &vec![ymd_hms(2020, 7, 1, 9, 0, 0)]);
+    assert_eq!(first.all(100).dates.len() + second.all(100).dates.len(), 60);
+}
+
+#[test]
+fn splits_rules_with_an_interval() {
+    let sets = [
+        "DTSTART:20210115T090000Z\nRRULE:FREQ=MONTHLY;INTERVAL=2;COUNT=6\nRDATE:20210220T090000Z",
+        "DTSTART:20230102T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;COUNT=12\nRRULE:FREQ=DAILY;INTERVAL=3;COUNT=30\nRDATE:20230111T120000Z,20230301T120000Z",
+        "DTSTART:20230102T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;UNTIL=20230601T000000Z\nRRULE:FREQ=DAILY;INTERVAL=3;UNTIL=20230501T000000Z\nRDATE:20230111T120000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;INTERVAL=2;COUNT=60\nEXRULE:FREQ=WEEKLY;INTERVAL=3;BYDAY=SA,SU\nRDATE:20230110T120000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=HOURLY;INTERVAL=5;COUNT=40\nRRULE:FREQ=MINUTELY;INTERVAL=45;COUNT=100",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let dates = rrule_set.clone().all(1000).dates;
+        for dt in dates.iter().step_by(3) {
+            let (first, second) = rrule_set.split_at(*dt);
+            let first_dates = first.clone().all(1000).dates;
+            let second_dates = second.clone().all(1000).dates;
+            assert!(first_dates.iter().all(|date| date < dt), "{set} at {dt}");
+            assert_eq!([first_dates, second_dates].concat(), dates, "{set} at {dt}");
+            assert_eq!(second.to_string().parse::<RRuleSet>().unwrap(), second);
+        }
+    }
+
+    let rrule_set: RRuleSet = sets[0].parse().unwrap();
+    let (_, second) = rrule_set.split_at(ymd_hms(2021, 2, 20, 9, 0, 0));
+    assert_eq!(second.get_dt_start(), &ymd_hms(2021, 3, 15, 9, 0, 0));
+    assert_eq!(
+        second.all(10).dates,
+        vec![
+            ymd_hms(2021, 2, 20, 9, 0, 0),
+            ymd_hms(2021, 3, 15, 9, 0, 0),
+            ymd_hms(2021, 5, 15, 9, 0, 0),
+            ymd_hms(2021, 7, 15, 9, 0, 0),
+            ymd_hms(2021, 9, 15, 9, 0, 0),
+            ymd_hms(2021, 11, 15, 9, 0, 0),
+        ]
+    );
+
+    // The second set starts at an earlier instance the periods of both rules line up with
+    let rrule_set: RRuleSet = sets[1].parse().unwrap();
+    let (_, second) = rrule_set.split_at(ymd_hms(2023, 1, 11, 12, 0, 0));
+    assert_eq!(second.get_dt_start(), &ymd_hms(2023, 1, 8, 9, 0, 0));
+    assert_eq!(
+        second.get_exdate(),
+        &vec![ymd_hms(2023, 1, 8, 9, 0, 0), ymd_hms(2023, 1, 11, 9, 0, 0)]
+    );
+}
+
+#[test]
+fn removes_rules_without_instances_from_split() {
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;UNTIL=20230105T090000Z\nRRULE:FREQ=WEEKLY;BYDAY=WE;UNTIL=20230301T000000Z"
+        .parse()
+        .unwrap();
+
+    // The weekly rule starts after the split
+    let (first, second) = rrule_set.split_at(ymd_hms(2023, 1, 3, 9, 0, 0));
+    assert_eq!(first.get_rrule().len(), 1);
+    assert_eq!(second.get_rrule().len(), 2);
+
+    // The daily rule ends before the split, and is kept as it is
+    let (first, second) = rrule_set.split_at(ymd_hms(2023, 1, 11, 9, 0, 0));
+    assert_eq!(first.get_rrule()[0], rrule_set.get_rrule()[0]);
+    assert_eq!(
+        first.get_rrule()[1].get_until(),
+        Some(&ymd_hms(2023, 1, 4, 9, 0, 0))
+    );
+    assert_eq!(second.get_rrule().len(), 1);
+}
+
+#[test]
+#[cfg(feature = "exrule")]
+fn splits_exrules() {
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;UNTIL=20230301T000000Z\nEXRULE:FREQ=MONTHLY;BYMONTHDAY=15;UNTIL=20230120T000000Z"
+        .parse()
+        .unwrap();
+    let (first, second) = rrule_set.split_at(ymd_hms(2023, 1, 3, 9, 0, 0));
+    assert!(first.get_exrule().is_empty());
+    assert_eq!(second.get_exrule().len(), 1);
+
+    let (first, second) = rrule_set.split_at(ymd_hms(2023, 1, 18, 9, 0, 0));
+    assert_eq!(first.get_exrule(), rrule_set.get_exrule());
+    assert!(second.get_exrule().is_empty());
+
+    // An instance moved by `SKIP=FORWARD` is excluded from the second set with an exdate
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;UNTIL=20230601T000000Z\nEXRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=FORWARD"
+        .parse()
+        .unwrap();
+    let (first, second) = rrule_set.split_at(ymd_hms(2023, 3, 1, 0, 0, 0));
+    assert_eq!(second.get_exdate(), &vec![ymd_hms(2023, 3, 1, 9, 0, 0)]);
+    assert_eq!(
+        [first.all(1000).dates, second.all(1000).dates].concat(),
+        rrule_set.all(1000).dates
+    );
+}
+
+#[test]
+fn counts_and_finds_last_occurrence() {
+    let sets = [
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=10\nEXDATE:20230103T090000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;UNTIL=20231201T000000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=MONTHLY;COUNT=3\nRRULE:FREQ=WEEKLY;UNTIL=20230401T000000Z\nRDATE:20230110T120000Z,20240301T120000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=20\nEXRULE:FREQ=WEEKLY;BYDAY=SA,SU",
+        "DTSTART:20230101T090000Z\nRDATE:20230110T120000Z,20230301T120000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30;COUNT=5",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let dates = rrule_set.clone().all(1000).dates;
+        assert!(rrule_set.is_finite(), "{set}");
+        assert_eq!(rrule_set.last(), dates.last().copied(), "{set}");
+        assert_eq!(
+            rrule_set.count_occurrences(1000),
+            Some(dates.len() as u64),
+            "{set}"
+        );
+        if !dates.is_empty() {
+            assert_eq!(
+                rrule_set.count_occurrences(dates.len() as u64 - 1),
+                None,
+                "{set}"
+            );
+        }
+    }
+}
+
+#[test]
+fn counts_occurrences_between_before_and_after() {
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY"
+        .parse()
+        .unwrap();
+    assert!(!rrule_set.is_finite());
+    assert_eq!(rrule_set.last(), None);
+    assert_eq!(rrule_set.count_occurrences(u64::MAX), None);
+
+    let rrule_set = rrule_set
+        .after(ymd_hms(2023, 1, 10, 9, 0, 0))
+        .before(ymd_hms(2023, 3, 1, 9, 0, 0));
+    assert!(rrule_set.is_finite());
+    assert_eq!(rrule_set.last(), Some(ymd_hms(2023, 3, 1, 9, 0, 0)));
+    assert_eq!(rrule_set.count_occurrences(100), Some(51));
+}
+
+#[test]
+fn contains_the_dates_of_the_set() {
+    let sets = [
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31,-1",
+        "DTSTART:20200229T090000Z\nRRULE:FREQ=YEARLY;INTERVAL=3",
+        "DTSTART:19970902T090000Z\nRRULE:FREQ=YEARLY;BYDAY=TU,TH;BYSETPOS=1,-1",
+        "DTSTART:20230103T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=MO,SU",
+        "DTSTART:20230101T093000Z\nRRULE:FREQ=DAILY;INTERVAL=5;BYHOUR=8,17;BYMINUTE=15",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=HOURLY;INTERVAL=5;BYHOUR=1,11,21;BYDAY=MO",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=MINUTELY;INTERVAL=15;BYHOUR=10;BYMINUTE=0,15,45",
+        "DTSTART;TZID=Europe/Berlin:20230320T023000\nRRULE:FREQ=HOURLY;INTERVAL=2",
+        "DTSTART;TZID=Europe/Berlin:20230301T023000\nRRULE:FREQ=DAILY",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=40;BYDAY=MO,WE\nRDATE:20230125T120000Z\nEXDATE:20230201T090000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;UNTIL=20230301T000000Z\nEXRULE:FREQ=WEEKLY;BYDAY=SA,SU",
+        "DTSTART:20240223T090000Z\nRRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=14;SKIP=BACKWARD",
+        "DTSTART:20240311T090000Z\nRRULE:RSCALE=ISLAMIC-CIVIL;FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=30;SKIP=FORWARD",
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=FORWARD",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let dates = rrule_set.clone().all(50).dates;
+        for date in &dates {
+            assert!(rrule_set.contains(*date), "{set} contains {date}");
+        }
+        for window in dates.windows(2) {
+            let (previous, next) = (window[0], window[1]);
+            let middle = previous + (next - previous) / 2;
+            for dt in [
+                previous + Duration::seconds(1),
+                middle,
+                next - Duration::hours(1),
+            ] {
+                assert_eq!(
+                    rrule_set.contains(dt),
+                    dates.contains(&dt),
+                    "{set} contains {dt}"
+                );
+            }
+        }
+        assert!(!rrule_set.contains(*rrule_set.get_dt_start() - Duration::days(1)));
+    }
+}
+
+#[test]
+fn contains_without_iterating_from_dt_start() {
+    let rrule_set: RRuleSet = "DTSTART:20010101T000000Z\nRRULE:FREQ=SECONDLY;INTERVAL=7;BYHOUR=12"
+        .parse()
+        .unwrap();
+    let rrule_set = rrule_set.limit();
+    assert!(rrule_set.contains(ymd_hms(2024, 6, 1, 12, 0, 2)));
+    assert!(!rrule_set.contains(ymd_hms(2024, 6, 1, 12, 0, 3)));
+    assert!(!rrule_set.contains(ymd_hms(2024, 6, 1, 13, 0, 2)));
+
+    let rrule = &rrule_set.get_rrule()[0];
+    assert!(rrule.contains(rrule_set.get_dt_start(), &ymd_hms(2024, 6, 1, 12, 0, 9)));
+}
+
+#[test]
+fn finds_next_and_previous_recurrence() {
+    let sets = [
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31,-1",
+        "DTSTART:20230103T090000Z\nRRULE:FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=MO,SU",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=HOURLY;INTERVAL=5;BYHOUR=1,11,21;BYDAY=MO",
+        "DTSTART;TZID=Europe/Berlin:20230320T023000\nRRULE:FREQ=HOURLY;INTERVAL=2",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=40;BYDAY=MO,WE\nRDATE:20230125T120000Z\nEXDATE:20230201T090000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY\nEXRULE:FREQ=WEEKLY;BYDAY=SA,SU",
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=FORWARD",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let dates = rrule_set.clone().all(50).dates;
+        for window in dates.windows(2) {
+            let (previous, next) = (window[0], window[1]);
+            assert_eq!(
+                rrule_set.next_after(previous, true),
+                Some(previous),
+                "{set}"
+            );
+            assert_eq!(rrule_set.next_after(previous, false), Some(next), "{set}");
+            assert_eq!(rrule_set.prev_before(next, true), Some(next), "{set}");
+            assert_eq!(rrule_set.prev_before(next, false), Some(previous), "{set}");
+
+            let middle = previous + (next - previous) / 2;
+            for inclusive in [true, false] {
+                assert_eq!(rrule_set.next_after(middle, inclusive), Some(next), "{set}");
+                assert_eq!(
+                    rrule_set.prev_before(middle, inclusive),
+                    Some(previous),
+                    "{set}"
+                );
+            }
+        }
+
+        let dt_start = *rrule_set.get_dt_start();
+        assert_eq!(
+            rrule_set.next_after(dt_start - Duration::days(1), false),
+            Some(dates[0])
+        );
+        assert_eq!(rrule_set.prev_before(dates[0], false), None);
+    }
+}
+
+#[test]
+fn finds_next_and_previous_recurrence_of_finished_set() {
+    let rrule_set: RRuleSet = "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=3"
+        .parse()
+        .unwrap();
+    let last = ymd_hms(2023, 1, 3, 9, 0, 0);
+    assert_eq!(rrule_set.next_after(last, true), Some(last));
+    assert_eq!(rrule_set.next_after(last, false), None);
+    assert_eq!(
+        rrule_set.prev_before(ymd_hms(2030, 1, 1, 0, 0, 0), false),
+        Some(last)
+    );
+
+    // The `before` and `after` dates of the set are not used
+    let rrule_set = rrule_set.before(ymd_hms(2023, 1, 2, 0, 0, 0));
+    assert_eq!(rrule_set.prev_before(last, true), Some(last));
+}
+
+#[test]
+fn finds_next_and_previous_recurrence_without_iterating_from_dt_start() {
+    let rrule_set: RRuleSet = "DTSTART:20010101T000000Z\nRRULE:FREQ=SECONDLY;INTERVAL=7;BYHOUR=12"
+        .parse()
+        .unwrap();
+    let rrule_set = rrule_set.limit();
+    let dt = ymd_hms(2024, 6, 1, 12, 0, 2);
+    assert_eq!(
+        rrule_set.next_after(dt, false),
+        Some(ymd_hms(2024, 6, 1, 12, 0, 9))
+    );
+    assert_eq!(
+        rrule_set.prev_before(ymd_hms(2024, 6, 1, 12, 0, 9), false),
+        Some(dt)
+    );
+}
+
+#[test]
+fn iterates_between_two_dates() {
+    let sets = [
+        "DTSTART:20200131T090000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=31,-1",
+        "DTSTART:20230101T103000Z\nRRULE:FREQ=HOURLY;INTERVAL=5;BYHOUR=1,11,21;BYDAY=MO",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY;COUNT=40;BYDAY=MO,WE\nRDATE:20230125T120000Z\nEXDATE:20230201T090000Z",
+        "DTSTART:20230101T090000Z\nRRULE:FREQ=DAILY\nEXRULE:FREQ=WEEKLY;BYDAY=SA,SU",
+    ];
+    for set in sets {
+        let rrule_set: RRuleSet = set.parse().unwrap();
+        let dates = rrule_set.clone().all(50).dates;
+        let (start, end) = (dates[5], dates[20]);
+        for inclusive in [true, false] {
+            let expected: Vec<_> = dates
+                .iter()
+                .filter(|date| {
+                    if inclusive {
+                        (start..=end).contains(date)
+                    } else {
+                        **date > start && **date < end
+                    }
+                })
+                .copied()
+                .collect();
+            let mut iter = rrule_set.between(start, end, inclusive);
+            assert_eq!(iter.by_ref().collect::<Vec<_>>(), expected, "{set}");
+            assert!(!iter.was_limited());
+
+            // Dates between the recurrences are never yielded
+            let minute = Duration::minutes(1);
+            let between: Vec<_> = rrule_set
+                .between(start - minute, end - minute, inclusive)
+                .collect();
+            assert_eq!(between, dates[5..20], "{set}");
+        }
+    }
+}
+
+#[test]
+fn iterates_between_two_dates_without_a_limit() {
+    let rrule_set: RRuleSet = "DTSTART:20010101T000000Z\nRRULE:FREQ=SECONDLY;INTERVAL=2"
+        .parse()
+        .unwrap();
+    let start = ymd_hms(2024, 6, 1, 0, 0, 0);
+    let mut iter = rrule_set
+        .limit()
+        .between(start, start + Duration::days(2), true);
+    assert_eq!(iter.by_ref().count(), 86_401);
+    assert!(!iter.was_limited());
+}
+
+#[test]
+fn reports_if_between_was_limited() {
+    let rrule_set: RRuleSet =
+        "DTSTART:20010101T000000Z\nRRULE:FREQ=MINUTELY;BYMONTH=2;BYMONTHDAY=30"
+            .parse()
+            .unwrap();
+    let start = ymd_hms(2024, 1, 1, 0, 0, 0);
+    let mut iter = rrule_set
+        .limit()
+        .between(start, start + Duration::days(3650), true);
+    assert_eq!(iter.next(), None);
+    assert!(iter.was_limited());
+}
+
+#[test]
+fn returns_more_than_u16_max_recurrences() {
+    let rrule_set: RRuleSet =
+        "DTSTART:20210101T000000Z\nRRULE:FREQ=MINUTELY;UNTIL=20210301T000000Z"
+            .parse()
+            .unwrap();
+    let limits = IterLimits::default().max_results(100_000);
+    let result = rrule_set.clone().iter_limits(limits).all_within_limits();
+    assert_eq!(result.dates.len(), 59 * 24 * 60 + 1);
+    assert!(!result.limited);
+    assert_eq!(result.dates.last(), Some(&ymd_hms(2021, 3, 1, 0, 0, 0)));
+
+    let limits = IterLimits::unlimited().max_results(70_000);
+    let result = rrule_set.iter_limits(limits).all_within_limits();
+    assert_eq!(result.dates.len(), 70_000);
+    assert!(result.limited);
+}
+
+#[test]
+fn stops_at_the_loop_iterations_limit() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T000000Z\nRRULE:FREQ=DAILY;BYMONTH=2;BYMONTHDAY=1"
+        .parse()
+        .unwrap();
+
+    // There are 364 days without a recurrence after the first one
+    let limits = IterLimits::unlimited().max_loop_iterations(40);
+    let result = rrule_set.clone().iter_limits(limits).all_within_limits();
+    assert_eq!(result.dates, vec![ymd_hms(2021, 2, 1, 0, 0, 0)]);
+    assert!(result.limited);
+
+    let limits = IterLimits::unlimited().max_loop_iterations(400);
+    let mut iter = rrule_set.iter_limits(limits).into_iter();
+    assert_eq!(iter.next(), Some(ymd_hms(2021, 2, 1, 0, 0, 0)));
+    assert_eq!(iter.next(), Some(ymd_hms(2022, 2, 1, 0, 0, 0)));
+}
+
+#[test]
+fn limits_the_loop_iterations_by_default() {
+    // A minute has a single recurrence, so the one at `BYSETPOS=2` never exists
+    let rrule_set: RRuleSet =
+        "DTSTART:20210101T000000Z\nRRULE:FREQ=MINUTELY;BYMONTH=11;BYMONTHDAY=21;BYSETPOS=2;COUNT=29"
+            .parse()
+            .unwrap();
+    assert_eq!(rrule_set.get_iter_limits(), IterLimits::default());
+
+    let dt = ymd_hms(2021, 11, 21, 0, 0, 0);
+    assert!(!rrule_set.contains(dt));
+    assert!(!rrule_set.get_rrule()[0].contains(rrule_set.get_dt_start(), &dt));
+    assert_eq!(rrule_set.count_occurrences(100), None);
+    assert_eq!(rrule_set.last(), None);
+    assert_eq!(rrule_set.next_after(dt, true), None);
+    assert_eq!(rrule_set.prev_before(dt, true), None);
+    assert_eq!(
+        rrule_set.between(dt, dt + Duration::days(1), true).next(),
+        None
+    );
+    assert_eq!(rrule_set.occurrences().next(), None);
+    assert!(rrule_set.overlapping(dt..dt + Duration::days(1)).is_empty());
+}
+
+#[test]
+fn stops_at_the_duration_limit() {
+    let rrule_set: RRuleSet = "DTSTART:20210101T000000Z\nRRULE:FREQ=SECONDLY"
+        .parse()
+        .unwrap();
+    let limits = IterLimits::default().max_duration(std::time::Duration::ZERO);
+    let rrule_set = rrule_set.iter_limits(limits);
+    assert_eq!(rrule_set.get_iter_limits(), limits);
+
+    let result = rrule_set.clone().all(10);
+    assert!(result.dates.is_empty());
+    assert!(result.limited);
+
+    let mut iter = rrule_set.between(
+        ymd_hms(2021, 1, 1, 0, 0, 0),
+        ymd_hms(2021, 1, 2, 0, 0, 0),
+        true,
+    );
+    assert_eq!(iter.next(), None);
+    assert!(iter.was_limited());
+    assert_eq!(
+        rrule_set.prev_before(ymd_hms(2021, 1, 2, 0, 0, 0), true),
+        None
+    );
+}
+
+#[test]
+fn parses_leniently() {
+    let input = "DTSTART:20210101T090000Z\nRRULE:FREQ=WEEKLY;COUNT=3\nEXDATE:20210108T090000Z";
+    assert_eq!(
+        RRuleSet::parse_lenient(input),
+        Ok(input.parse::<RRuleSet>().unwrap())
+    );
+}
+
+#[test]
+fn collects_all_errors_when_parsing_leniently() {
+    let input = "DTSTART:20210101T090000Z\n\
+        RRULE:FREQ=WEEKLY;COUNT=x;BYHOUR=25\n\
+        RRULE:FREQ=WEEKLY;UNTIL=20201231T000000Z;BYMONTHDAY=1\n\
+        EXDATE:2021";
+    let errors = RRuleSet::parse_lenient(input).unwrap_err();
+    let fields = errors
+        .iter()
+        .map(|error| error.get_field())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, ["COUNT", "BYHOUR", "EXDATE", "UNTIL", "BYMONTHDAY"]);
+    assert_eq!(
+        errors[0].get_error(),
+        &RRuleError::ParserError(ParseError::InvalidCount("x".into()))
+    );
+    assert_eq!(
+        errors[0].get_location(),
+        Some(&Location {
+            line: 2,
+            span: 24..25,
+            property: Some("RRULE".into()),
+        })
+    );
+    assert!(matches!(
+        errors[3].get_error(),
+        RRuleError::ValidationError(ValidationError::UntilBeforeStart { .. })
+    ));
+    assert_eq!(
+        input.parse::<RRuleSet>().unwrap_err(),
+        errors[0].get_error().clone()
+    );
+}
+
+#[test]
+fn reports_missing_start_date_when_parsing_leniently() {
+    let errors = RRuleSet::parse_lenient("RRULE:FREQ=DAILY;BYDAY=XX").unwrap_err();
+    let fields = errors
+        .iter()
+        .map(|error| error.get_field())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, ["BYDAY", "DTSTART"]);
+    assert_eq!(
+        errors[1].get_error(),
+        &RRuleError::ParserError(ParseError::MissingStartDate)
+    );
+}
+
+#[test]
+fn keeps_parse_errors_unwrapped() {
+    let errors = [
+        ("RRULE:FREQ=DAILY", ParseError::MissingStartDate),
+        (
+            "DTSTART:20210101T090000Z\nRRULE:FREQ=DAILY;BYHOUR=25",
+            ParseError::InvalidByHour("25".into()),
+        ),
+        (
+            "DTSTART;TZID=Mars/Olympus:20210101T090000\nRRULE:FREQ=DAILY",
+            ParseError::InvalidTimezone("Mars/Olympus".into()),
+        ),
+    ];
+    for (input, error) in errors {
+        assert_eq!(
+            input.parse::<RRuleSet>(),
+            Err(RRuleError::ParserError(error)),
+            "{input}"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::tests::common::check_occurrences;
+use crate::{Frequency, RRule, RRuleSet, RScale, Skip, Unvalidated, ValidationError};
+
+fn occurrences(set: &str, limit: u16) -> Vec<chrono::DateTime<crate::Tz>> {
+    let rrule_set: RRuleSet = set.parse().unwrap();
+    rrule_set.all(limit).dates
+}
+
+#[test]
+fn hebrew_yearly_on_month_day() {
+    // Every 1 Nisan
+    let dates = occurrences(
+        "DTSTART:20230323T090000Z\nRRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=7;BYMONTHDAY=1",
+        4,
+    );
+    check_occurrences(
+        &dates,
+        &[
+            "2023-03-23T09:00:00+00:00",
+            "2024-04-09T09:00:00+00:00",
+            "2025-03-30T09:00:00+00:00",
+            "2026-03-19T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn hebrew_yearly_defaults_to_start_date() {
+    // 1 Adar II 5784, which is 1 Adar in years without leap month
+    let dates = occurrences(
+        "DTSTART:20240311T090000Z\nRRULE:RSCALE=HEBREW;FREQ=YEARLY;COUNT=3",
+        10,
+    );
+    check_occurrences(
+        &dates,
+        &[
+            "2024-03-11T09:00:00+00:00",
+            "2025-03-01T09:00:00+00:00",
+            "2026-02-18T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn hebrew_leap_month_with_skip() {
+    // 14 Adar I, which only exists in leap years
+    let tests = [
+        (
+            "OMIT",
+            [
+                "2024-02-23T09:00:00+00:00",
+                "2027-02-21T09:00:00+00:00",
+                "2030-02-17T09:00:00+00:00",
+            ],
+        ),
+        // 14 Shevat in years without leap month
+        (
+            "BACKWARD",
+            [
+                "2024-02-23T09:00:00+00:00",
+                "2025-02-12T09:00:00+00:00",
+                "2026-02-01T09:00:00+00:00",
+            ],
+        ),
+        // 14 Adar in years without leap month
+        (
+            "FORWARD",
+            [
+                "2024-02-23T09:00:00+00:00",
+                "2025-03-14T09:00:00+00:00",
+                "2026-03-03T09:00:00+00:00",
+            ],
+        ),
+    ];
+    for (skip, expected) in tests {
+        let dates = occurrences(
+            &format!(
+                "DTSTART:20240223T090000Z\n\
+                RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=14;SKIP={skip};COUNT=3"
+            ),
+            10,
+        );
+        check_occurrences(&dates, &expected);
+    }
+}
+
+#[test]
+fn hebrew_yearly_by_year_day() {
+    // Rosh Hashanah and the day before
+    let dates = occurrences(
+        "DTSTART:20240101T090000Z\nRRULE:RSCALE=HEBREW;FREQ=YEARLY;BYYEARDAY=1,-1;COUNT=4",
+        10,
+    );
+    check_occurrences(
+        &dates,
+        &[
+            "2024-10-02T09:00:00+00:00",
+            "2024-10-03T09:00:00+00:00",
+            "2025-09-22T09:00:00+00:00",
+            "2025-09-23T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn hebrew_daily_and_monthly_with_by_rules() {
+    // 1 and 15 Nisan
+    let dates = occurrences(
+        "DTSTART:20240101T090000Z\n\
+        RRULE:RSCALE=HEBREW;FREQ=DAILY;BYMONTH=7;BYMONTHDAY=1,15;COUNT=4",
+        10,
+    );
+    check_occurrences(
+        &dates,
+        &[
+            "2024-04-09T09:00:00+00:00",
+            "2024-04-23T09:00:00+00:00",
+            "2025-03-30T09:00:00+00:00",
+            "2025-04-13T09:00:00+00:00",
+        ],
+    );
+
+    // First Saturday of Nisan
+    let dates = occurrences(
+        "DTSTART:20240101T090000Z\n\
+        RRULE:RSCALE=HEBREW;FREQ=MONTHLY;BYMONTH=7;BYDAY=SA;BYSETPOS=1;COUNT=3",
+        10,
+    );
+    check_occurrences(
+        &dates,
+        &[
+            "2024-04-13T09:00:00+00:00",
+            "2025-04-05T09:00:00+00:00",
+            "2026-03-21T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn hebrew_monthly_through_leap_year() {
+    // 14 Adar I, Adar II, Nisan, ...
+    let dates = occurrences(
+        "DTSTART:20240223T090000Z\nRRULE:RSCALE=HEBREW;FREQ=MONTHLY;INTERVAL=6;COUNT=4",
+        10,
+    );
+    check_occurrences(
+        &dates,
+        &[
+            "2024-02-23T09:00:00+00:00",
+            "2024-08-18T09:00:00+00:00",
+            "2025-02-12T09:00:00+00:00",
+            "2025-08-08T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn islamic_civil_monthly_with_skip() {
+    // 30 Ramadan 1445, Shawwal only has 29 days
+    let tests = [
+        (
+            "OMIT",
+            [
+                "2024-04-09T09:00:00+00:00",
+                "2024-06-07T09:00:00+00:00",
+                "2024-07-07T09:00:00+00:00",
+                "2024-08-06T09:00:00+00:00",
+            ],
+        ),
+        (
+            "BACKWARD",
+            [
+                "2024-04-09T09:00:00+00:00",
+                "2024-05-08T09:00:00+00:00",
+                "2024-06-07T09:00:00+00:00",
+                "2024-07-07T09:00:00+00:00",
+            ],
+        ),
+        (
+            "FORWARD",
+            [
+                "2024-04-09T09:00:00+00:00",
+                "2024-05-09T09:00:00+00:00",
+                "2024-06-07T09:00:00+00:00",
+                "2024-07-07T09:00:00+00:00",
+            ],
+        ),
+    ];
+    for (skip, expected) in tests {
+        let dates = occurrences(
+            &format!(
+                "DTSTART:20240409T090000Z\n\
+                RRULE:RSCALE=ISLAMIC-CIVIL;FREQ=MONTHLY;SKIP={skip};COUNT=4"
+            ),
+            10,
+        );
+        check_occurrences(&dates, &expected);
+    }
+}
+
+#[test]
+fn skip_forward_does_not_repeat_dates() {
+    // 30 Sha'ban moves to 1 Ramadan, which is already an occurrence
+    let dates = occurrences(
+        "DTSTART:20240223T090000Z\n\
+        RRULE:RSCALE=ISLAMIC-CIVIL;FREQ=MONTHLY;BYMONTHDAY=1,30;SKIP=FORWARD;COUNT=5",
+        10,
+    );
+    check_occurrences(
+        &dates,
+        &[
+            "2024-03-11T09:00:00+00:00",
+            "2024-04-09T09:00:00+00:00",
+            "2024-04-10T09:00:00+00:00",
+            "2024-05-09T09:00:00+00:00",
+            "2024-06-07T09:00:00+00:00",
+        ],
+    );
+}
+
+#[test]
+fn rscale_round_trip() {
+    let rrules = [
+        "RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=8;SKIP=FORWARD",
+        "RSCALE=HEBREW;FREQ=YEARLY;COUNT=5;BYMONTH=5,5L,6",
+        "RSCALE=ISLAMIC-CIVIL;FREQ=MONTHLY;BYMONTHDAY=30;SKIP=BACKWARD",
+        "RSCALE=GREGORIAN;FREQ=MONTHLY;BYMONTHDAY=31;SKIP=OMIT",
+    ];
+    for rrule in rrules {
+        let parsed: RRule<Unvalidated> = rrule.parse().unwrap();
+        assert_eq!(parsed.to_string(), rrule);
+    }
+
+    let rrule: RRule<Unvalidated> = "freq=yearly;rscale=hebrew;bymonth=5l;skip=backward"
+        .parse()
+        .unwrap();
+    assert_eq!(rrule.get_rscale(), Some(RScale::Hebrew));
+    assert_eq!(rrule.get_skip(), Some(Skip::Backward));
+    assert_eq!(rrule.get_by_leap_month(), &[5]);
+    assert!(rrule.get_by_month().is_empty());
+
+    assert!("RSCALE=CHINESE;FREQ=YEARLY"
+        .parse::<RRule<Unvalidated>>()
+        .is_err());
+    assert!("RSCALE=HEBREW;FREQ=YEARLY;SKIP=LATER"
+        .parse::<RRule<Unvalidated>>()
+        .is_err());
+    assert!("RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=13L"
+        .parse::<RRule<Unvalidated>>()
+        .is_err());
+}
+
+#[test]
+fn rejects_invalid_rscale_combinations() {
+    let dt_start = crate::tests::common::ymd_hms(2024, 1, 1, 9, 0, 0);
+    let tests = [
+        (
+            RRule::new(Frequency::Yearly).by_leap_month(vec![5]),
+            ValidationError::LeapMonthWithoutRScale,
+        ),
+        (
+            RRule::new(Frequency::Yearly)
+                .rscale(RScale::Gregorian)
+                .by_leap_month(vec![5]),
+            ValidationError::LeapMonthWithoutRScale,
+        ),
+        (
+            RRule::new(Frequency::Yearly)
+                .rscale(RScale::Hebrew)
+                .by_week_no(vec![1]),
+            ValidationError::InvalidByRuleAndRScale {
+                by_rule: "BYWEEKNO".into(),
+                rscale: RScale::Hebrew,
+            },
+        ),
+    ];
+    for (rrule, expected) in tests {
+        assert_eq!(rrule.validate(dt_start), Err(expected.into()));
+    }
+}
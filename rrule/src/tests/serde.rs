@@ -7,6 +7,7 @@ fn rrule_set_to_and_from_str() {
         "DTSTART:20120201T093000Z\nRRULE:FREQ=YEARLY;COUNT=3",
         "DTSTART:20120201T093000Z\nRRULE:FREQ=WEEKLY;INTERVAL=5;BYDAY=-2MO,FR",
         "DTSTART;TZID=America/New_York:19000201T093000Z\nRRULE:UNTIL=19990404T110000Z;FREQ=WEEKLY;BYDAY=TU,WE",
+        "DTSTART;TZID=-0330:20120201T093000\nRRULE:FREQ=DAILY;COUNT=5",
     ];
 
     for test_str in test_cases {
@@ -31,6 +32,7 @@ fn serialize_deserialize_json_to_and_from_rrule_set() {
         "DTSTART:20120201T093000Z\nRRULE:FREQ=DAILY;COUNT=5",
         "DTSTART:20120201T093000Z\nRRULE:FREQ=WEEKLY;INTERVAL=5;BYDAY=-2MO,FR",
         "DTSTART;TZID=America/New_York:19000201T093000Z\nRRULE:UNTIL=19990404T110000Z;FREQ=WEEKLY;BYDAY=TU,WE",
+        "DTSTART;TZID=-0330:20120201T093000\nRRULE:FREQ=DAILY;COUNT=5",
     ];
 
     for test_str in test_cases {
@@ -0,0 +1,589 @@
+use crate::{
+    Conjunction, English, French, Frequency, German, Japanese, Locale, NWeekday, Phrase, RRule,
+    RRuleSet, Spanish, TextErrorKind, Unvalidated,
+};
+use chrono::{NaiveDate, Weekday};
+
+#[test]
+fn describes_rrules() {
+    let tests = [
+        (
+            "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR",
+            "every 2 weeks on Monday and Friday at 9:00",
+        ),
+        ("RRULE:FREQ=DAILY", "every day at 9:00"),
+        (
+            "RRULE:FREQ=WEEKLY;BYDAY=FR,TU,WE,TH,MO",
+            "every weekday at 9:00",
+        ),
+        (
+            "RRULE:FREQ=DAILY;INTERVAL=2;BYDAY=MO,TU,WE,TH,FR",
+            "every 2 days on Monday, Tuesday, Wednesday, Thursday and Friday at 9:00",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            "every month on the last weekday at 9:00",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYDAY=SA,SU;BYSETPOS=1,-1",
+            "every month on the 1st and last Saturday or Sunday at 9:00",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=1,15;BYSETPOS=-1",
+            "every month on the 1st and 15th (only the last in each period) at 9:00",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYDAY=-1FR,2TU;COUNT=1",
+            "every month on the 2nd Tuesday and the last Friday at 9:00, once",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=-2,1,15,-1",
+            "every month on the 1st, 15th, 2nd to last day and last day at 9:00",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13",
+            "every month on Friday the 13th at 9:00",
+        ),
+        (
+            "RRULE:FREQ=YEARLY",
+            "every year in January on the 2nd at 9:00",
+        ),
+        (
+            "RRULE:FREQ=YEARLY;BYDAY=20MO",
+            "every year on the 20th Monday of the year at 9:00",
+        ),
+        (
+            "RRULE:FREQ=YEARLY;BYMONTH=3,1;BYDAY=1SU,-1SU",
+            "every year in January and March on the 1st Sunday and the last Sunday at 9:00",
+        ),
+        (
+            "RRULE:FREQ=YEARLY;BYWEEKNO=-1,1;BYDAY=MO",
+            "every year in week 1 and the last week on Monday at 9:00",
+        ),
+        (
+            "RRULE:FREQ=YEARLY;BYYEARDAY=-1,100",
+            "every year on the 100th and last day of the year at 9:00",
+        ),
+        ("RRULE:FREQ=HOURLY;INTERVAL=3", "every 3 hours at minute 0"),
+        (
+            "RRULE:FREQ=MINUTELY;INTERVAL=15;BYSECOND=15",
+            "every 15 minutes at second 15",
+        ),
+        ("RRULE:FREQ=SECONDLY;INTERVAL=1", "every second"),
+        (
+            "RRULE:FREQ=DAILY;BYHOUR=9,17;BYMINUTE=0,30",
+            "every day at 9:00, 9:30, 17:00 and 17:30",
+        ),
+        (
+            "RRULE:FREQ=DAILY;BYHOUR=9;BYMINUTE=15;BYSECOND=30",
+            "every day at 9:15:30",
+        ),
+        (
+            "RRULE:FREQ=DAILY;BYHOUR=9,10,11,12,13,14,15;BYMINUTE=0,30",
+            "every day at 9, 10, 11, 12, 13, 14 and 15 o'clock and minutes 0 and 30",
+        ),
+        (
+            "RRULE:FREQ=DAILY;COUNT=10;INTERVAL=2",
+            "every 2 days at 9:00, 10 times",
+        ),
+        (
+            "RRULE:FREQ=WEEKLY;UNTIL=20231224T000000Z",
+            "every week on Monday at 9:00 until December 24, 2023",
+        ),
+        (
+            "RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=14;SKIP=BACKWARD",
+            "every year (Hebrew calendar) in leap month 5 on the 14th \
+            (or earlier if the date does not exist) at 9:00",
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=31;SKIP=FORWARD",
+            "every month on the 31st (or later if the date does not exist) at 9:00",
+        ),
+    ];
+    for (rrule, expected) in tests {
+        let rrule_set: RRuleSet = format!("DTSTART:20230102T090000Z\n{}", rrule)
+            .parse()
+            .unwrap();
+        assert_eq!(rrule_set.get_rrule()[0].to_text(), expected, "{rrule}");
+        assert_eq!(rrule_set.to_text(), expected, "{rrule}");
+    }
+}
+
+#[test]
+fn describes_unvalidated_rrules() {
+    let rrule = RRule::new(Frequency::Monthly)
+        .by_weekday(vec![NWeekday::Nth(-1, Weekday::Fri)])
+        .interval(3);
+    assert_eq!(rrule.to_text(), "every 3 months on the last Friday");
+
+    let rrule = RRule::new(Frequency::Daily).by_hour(vec![9, 17]);
+    assert_eq!(rrule.to_text(), "every day at 9 and 17 o'clock");
+
+    let rrule = RRule::new(Frequency::Hourly).by_minute(vec![0, 30]);
+    assert_eq!(rrule.to_text(), "every hour at minutes 0 and 30");
+
+    let rrule = RRule::new(Frequency::Weekly).by_weekday(vec![NWeekday::Every(Weekday::Sun)]);
+    assert_eq!(rrule.to_text(), "every week on Sunday");
+}
+
+#[test]
+fn describes_rrule_sets() {
+    let tests = [
+        (
+            "DTSTART:20230102T093000Z\nRRULE:FREQ=DAILY;UNTIL=20230301T000000Z\n\
+            RDATE:20230110T120000Z\nEXDATE:20230104T093000Z,20230103T093000Z",
+            "every day at 9:30 until March 1, 2023 and on January 10, 2023 at 12:00, \
+            except on January 3, 2023 and January 4, 2023",
+        ),
+        (
+            "DTSTART:20230102T093000Z\nRRULE:FREQ=MONTHLY;BYMONTHDAY=1\n\
+            RRULE:FREQ=WEEKLY;BYDAY=WE",
+            "every month on the 1st at 9:30 and every week on Wednesday at 9:30",
+        ),
+        (
+            "DTSTART:20230102T093000Z\nRDATE:20230110T093000Z,20230102T093000Z",
+            "on January 2, 2023 and January 10, 2023",
+        ),
+    ];
+    for (rrule_set, expected) in tests {
+        let rrule_set: RRuleSet = rrule_set.parse().unwrap();
+        assert_eq!(rrule_set.to_text(), expected);
+    }
+}
+
+#[cfg(feature = "exrule")]
+#[test]
+fn describes_exrules() {
+    let rrule_set: RRuleSet = "DTSTART:20230102T093000Z\nRRULE:FREQ=DAILY\n\
+        EXRULE:FREQ=WEEKLY;BYDAY=SA,SU\nEXDATE:20230104T093000Z"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        rrule_set.to_text(),
+        "every day at 9:30, except every week on Saturday and Sunday at 9:30 \
+        and on January 4, 2023"
+    );
+}
+
+#[test]
+fn describes_rrules_in_locales() {
+    let tests = [
+        (
+            "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR;COUNT=10",
+            [
+                "alle 2 Wochen am Montag und Freitag um 9:00, 10 Mal",
+                "toutes les 2 semaines le lundi et le vendredi à 9:00, 10 fois",
+                "cada 2 semanas el lunes y el viernes a las 9:00, 10 veces",
+                "2週間ごと 月曜日と金曜日 9:00、10回",
+            ],
+        ),
+        (
+            "RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR",
+            [
+                "jeden Werktag um 9:00",
+                "chaque jour ouvrable à 9:00",
+                "cada día laborable a las 9:00",
+                "毎週平日 9:00",
+            ],
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYDAY=SA,SU;BYSETPOS=1,-1",
+            [
+                "jeden Monat am 1. und letzten Samstag oder Sonntag um 9:00",
+                "chaque mois le 1er et dernier samedi ou dimanche à 9:00",
+                "cada mes el 1.º y último sábado o domingo a las 9:00",
+                "毎月 第1と最終の土曜日または日曜日 9:00",
+            ],
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYDAY=-1FR,2TU;COUNT=1",
+            [
+                "jeden Monat am 2. Dienstag und letzten Freitag um 9:00, einmal",
+                "chaque mois le 2e mardi et le dernier vendredi à 9:00, une fois",
+                "cada mes el 2.º martes y el último viernes a las 9:00, una vez",
+                "毎月 第2火曜日と最終金曜日 9:00、1回",
+            ],
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYMONTHDAY=-2,1,15,-1",
+            [
+                "jeden Monat am 1., 15., vorletzten Tag und letzten Tag um 9:00",
+                "chaque mois le 1er, le 15, l'avant-dernier jour et le dernier jour à 9:00",
+                "cada mes el día 1, el día 15, el penúltimo día y el último día a las 9:00",
+                "毎月 1日、15日、最後から2番目の日と最終日 9:00",
+            ],
+        ),
+        (
+            "RRULE:FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13",
+            [
+                "jeden Monat am Freitag, den 13. um 9:00",
+                "chaque mois le vendredi 13 à 9:00",
+                "cada mes el viernes 13 a las 9:00",
+                "毎月 金曜日 13日 9:00",
+            ],
+        ),
+        (
+            "RRULE:FREQ=YEARLY;BYDAY=20MO",
+            [
+                "jedes Jahr am 20. Montag des Jahres um 9:00",
+                "chaque année le 20e lundi de l'année à 9:00",
+                "cada año el 20.º lunes del año a las 9:00",
+                "毎年 年の第20月曜日 9:00",
+            ],
+        ),
+        (
+            "RRULE:FREQ=YEARLY;BYMONTH=3,1;BYMONTHDAY=1",
+            [
+                "jedes Jahr im Januar und März am 1. um 9:00",
+                "chaque année en janvier et mars le 1er à 9:00",
+                "cada año en enero y marzo el día 1 a las 9:00",
+                "毎年 1月と3月 1日 9:00",
+            ],
+        ),
+        (
+            "RRULE:FREQ=HOURLY;INTERVAL=3;BYMINUTE=0,30",
+            [
+                "alle 3 Stunden um Minuten 0 und 30",
+                "toutes les 3 heures aux minutes 0 et 30",
+                "cada 3 horas a los minutos 0 y 30",
+                "3時間ごと 0分と30分",
+            ],
+        ),
+        (
+            "RRULE:FREQ=DAILY;UNTIL=20231224T000000Z",
+            [
+                "jeden Tag um 9:00 bis zum 24. Dezember 2023",
+                "chaque jour à 9:00 jusqu'au 24 décembre 2023",
+                "cada día a las 9:00 hasta el 24 de diciembre de 2023",
+                "毎日 9:00、2023年12月24日まで",
+            ],
+        ),
+        (
+            "RRULE:RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=14;SKIP=BACKWARD",
+            [
+                "jedes Jahr (hebräischer Kalender) im Schaltmonat 5 am 14. \
+                (oder früher, falls das Datum nicht existiert) um 9:00",
+                "chaque année (calendrier hébraïque) au mois intercalaire 5 le 14 \
+                (ou plus tôt si la date n'existe pas) à 9:00",
+                "cada año (calendario hebreo) en el mes bisiesto 5 el día 14 \
+                (o antes si la fecha no existe) a las 9:00",
+                "毎年（ヘブライ暦） 閏5月 14日（日付が存在しない場合はその前） 9:00",
+            ],
+        ),
+    ];
+    let locales: [&dyn Locale; 4] = [&German, &French, &Spanish, &Japanese];
+    for (rrule, expected) in tests {
+        let rrule_set: RRuleSet = format!("DTSTART:20230102T090000Z\n{}", rrule)
+            .parse()
+            .unwrap();
+        for (locale, expected) in locales.into_iter().zip(expected) {
+            assert_eq!(rrule_set.to_text_in(locale), expected, "{rrule}");
+        }
+    }
+}
+
+#[test]
+fn describes_rrule_sets_in_locales() {
+    let rrule_set: RRuleSet = "DTSTART:20230102T093000Z\nRRULE:FREQ=DAILY;UNTIL=20230301T000000Z\n\
+        RDATE:20230110T120000Z\nEXDATE:20230104T093000Z,20230103T093000Z"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        rrule_set.to_text_in(&German),
+        "jeden Tag um 9:30 bis zum 1. März 2023 und am 10. Januar 2023 um 12:00, \
+        außer am 3. Januar 2023 und 4. Januar 2023"
+    );
+    assert_eq!(
+        rrule_set.to_text_in(&French),
+        "chaque jour à 9:30 jusqu'au 1er mars 2023 et le 10 janvier 2023 à 12:00, \
+        sauf le 3 janvier 2023 et le 4 janvier 2023"
+    );
+    assert_eq!(
+        rrule_set.to_text_in(&Spanish),
+        "cada día a las 9:30 hasta el 1 de marzo de 2023 y el 10 de enero de 2023 a las 12:00, \
+        excepto el 3 de enero de 2023 y el 4 de enero de 2023"
+    );
+    assert_eq!(
+        rrule_set.to_text_in(&Japanese),
+        "毎日 9:30、2023年3月1日までと2023年1月10日 12:00、2023年1月3日と2023年1月4日を除く"
+    );
+}
+
+#[test]
+fn lists_weekdays_from_week_start() {
+    let rrule = RRule::new(Frequency::Weekly).by_weekday(vec![
+        NWeekday::Every(Weekday::Sat),
+        NWeekday::Every(Weekday::Mon),
+        NWeekday::Every(Weekday::Sun),
+    ]);
+    assert_eq!(rrule.to_text(), "every week on Monday, Saturday and Sunday");
+
+    let rrule = rrule.week_start(Weekday::Sun);
+    assert_eq!(rrule.to_text(), "every week on Sunday, Monday and Saturday");
+    assert_eq!(
+        rrule.to_text_in(&German),
+        "jede Woche am Sonntag, Montag und Samstag"
+    );
+
+    // Every weekday is recognized whatever the week start
+    let rrule = RRule::new(Frequency::Daily)
+        .by_weekday(
+            [
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ]
+            .map(NWeekday::Every)
+            .to_vec(),
+        )
+        .week_start(Weekday::Wed);
+    assert_eq!(rrule.to_text(), "every weekday");
+}
+
+#[test]
+fn describes_rrules_in_custom_locale() {
+    /// Describes the rules in Dutch, for the phrases of the test only.
+    struct Dutch;
+
+    impl Locale for Dutch {
+        fn weekday_name(&self, weekday: Weekday) -> String {
+            [
+                "maandag",
+                "dinsdag",
+                "woensdag",
+                "donderdag",
+                "vrijdag",
+                "zaterdag",
+                "zondag",
+            ][weekday.num_days_from_monday() as usize]
+                .to_string()
+        }
+
+        fn month_name(&self, month: u8) -> String {
+            English.month_name(month).to_lowercase()
+        }
+
+        fn ordinal(&self, n: i64) -> String {
+            match n {
+                -1 => "laatste".to_string(),
+                _ => format!("{}e", n),
+            }
+        }
+
+        fn list(&self, mut items: Vec<String>, conjunction: Conjunction) -> String {
+            let last = items.pop().unwrap_or_default();
+            match (items.is_empty(), conjunction) {
+                (true, _) => last,
+                (false, Conjunction::And) => format!("{} en {}", items.join(", "), last),
+                (false, Conjunction::Or) => format!("{} of {}", items.join(", "), last),
+            }
+        }
+
+        fn date(&self, date: NaiveDate) -> String {
+            date.format("%d-%m-%Y").to_string()
+        }
+
+        fn phrase(&self, phrase: &Phrase<'_>) -> String {
+            match *phrase {
+                Phrase::Frequency { interval: 1, .. } => "elke week".to_string(),
+                Phrase::Frequency { interval, .. } => format!("om de {} weken", interval),
+                Phrase::Weekdays { weekdays, .. } => {
+                    let weekdays = weekdays.iter().map(|weekday| match weekday {
+                        NWeekday::Every(weekday) => self.weekday_name(*weekday),
+                        NWeekday::Nth(n, weekday) => format!(
+                            "de {} {}",
+                            self.ordinal(i64::from(*n)),
+                            self.weekday_name(*weekday)
+                        ),
+                    });
+                    format!(" op {}", self.list(weekdays.collect(), Conjunction::And))
+                }
+                Phrase::Until(until) => format!(" tot {}", self.date(until.date())),
+                _ => String::new(),
+            }
+        }
+    }
+
+    let rrule_set: RRuleSet = "DTSTART:20230102T090000Z\n\
+        RRULE:FREQ=WEEKLY;INTERVAL=2;WKST=SU;BYDAY=MO,SU;UNTIL=20230301T000000Z"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        rrule_set.to_text_in(&Dutch),
+        "om de 2 weken op zondag en maandag tot 01-03-2023"
+    );
+}
+
+#[test]
+fn parses_descriptions_of_rrules() {
+    let tests = [
+        ("every other Tuesday", "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU"),
+        (
+            "last weekday of the month",
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+        ),
+        (
+            "every weekday at 9am",
+            "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0",
+        ),
+        (
+            "daily at 9:30 and 5:30 pm, 10 times",
+            "FREQ=DAILY;BYHOUR=9,17;BYMINUTE=30;COUNT=10",
+        ),
+        (
+            "monthly on the 2nd Tuesday and the last Friday",
+            "FREQ=MONTHLY;BYDAY=2TU,-1FR",
+        ),
+        (
+            "every month on the 1st and last Saturday or Sunday",
+            "FREQ=MONTHLY;BYDAY=SA,SU;BYSETPOS=1,-1",
+        ),
+        (
+            "every month on Friday the 13th",
+            "FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13",
+        ),
+        (
+            "every month on the 1st, 15th and 2nd to last day",
+            "FREQ=MONTHLY;BYMONTHDAY=1,15,-2",
+        ),
+        (
+            "every year in January and March on the first Sunday",
+            "FREQ=YEARLY;BYMONTH=1,3;BYDAY=1SU",
+        ),
+        (
+            "the 100th and last day of the year",
+            "FREQ=YEARLY;BYYEARDAY=100,-1",
+        ),
+        (
+            "every year in week 1 and the last week on Monday",
+            "FREQ=YEARLY;BYWEEKNO=1,-1;BYDAY=MO",
+        ),
+        (
+            "every 15 minutes at second 15",
+            "FREQ=MINUTELY;INTERVAL=15;BYSECOND=15",
+        ),
+        (
+            "every day at 9 and 17 o'clock and minutes 0 and 30",
+            "FREQ=DAILY;BYHOUR=9,17;BYMINUTE=0,30",
+        ),
+        (
+            "every year (Hebrew calendar) in leap month 5 on the 14th \
+            (or earlier if the date does not exist)",
+            "RSCALE=HEBREW;FREQ=YEARLY;BYMONTH=5L;BYMONTHDAY=14;SKIP=BACKWARD",
+        ),
+        (
+            "every 2 weeks on Mon, Wed and Fri until 2023-12-24",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,FR;UNTIL=20231224T235959Z",
+        ),
+        (
+            "every other Tuesday until June 1, 2024",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU;UNTIL=20240601T235959Z",
+        ),
+        ("twice every hour", "FREQ=HOURLY;COUNT=2"),
+    ];
+    for (text, expected) in tests {
+        let expected: RRule<Unvalidated> = expected.parse().unwrap();
+        assert_eq!(RRule::from_text(text), Ok(expected), "{text}");
+    }
+}
+
+#[test]
+fn parses_descriptions_of_the_describer() {
+    let tests = [
+        "every 2 weeks on Monday and Friday at 9:00",
+        "every weekday at 9:00",
+        "every 2 days on Monday, Tuesday, Wednesday, Thursday and Friday at 9:00",
+        "every month on the last weekday at 9:00",
+        "every month on the 1st and last Saturday or Sunday at 9:00",
+        "every month on the 1st and 15th (only the last in each period) at 9:00",
+        "every month on the 2nd Tuesday and the last Friday at 9:00, once",
+        "every month on the 1st, 15th, 2nd to last day and last day at 9:00",
+        "every month on Friday the 13th at 9:00",
+        "every year in January on the 2nd at 9:00",
+        "every year on the 20th Monday of the year at 9:00",
+        "every year in January and March on the 1st Sunday and the last Sunday at 9:00",
+        "every year in week 1 and the last week on Monday at 9:00",
+        "every year on the 100th and last day of the year at 9:00",
+        "every 3 hours at minute 0",
+        "every 15 minutes at second 15",
+        "every second",
+        "every day at 9:00, 9:30, 17:00 and 17:30",
+        "every day at 9:15:30",
+        "every day at 9, 10, 11, 12, 13, 14 and 15 o'clock and minutes 0 and 30",
+        "every 2 days at 9:00, 10 times",
+        "every week on Monday at 9:00 until December 24, 2023",
+        "every year (Hebrew calendar) in leap month 5 on the 14th \
+        (or earlier if the date does not exist) at 9:00",
+        "every month on the 31st (or later if the date does not exist) at 9:00",
+        "every 3 months on the last Friday",
+        "every hour at minutes 0 and 30",
+        #[cfg(feature = "by-easter")]
+        "every year 3 days before Easter",
+        #[cfg(feature = "by-easter")]
+        "every year on Easter at 9:00",
+    ];
+    for text in tests {
+        assert_eq!(RRule::from_text(text).unwrap().to_text(), text);
+    }
+}
+
+#[test]
+fn returns_errors_of_descriptions() {
+    let tests = [
+        (
+            "every week on Funday",
+            TextErrorKind::Expected {
+                expected: "a day".into(),
+                found: "Funday".into(),
+            },
+            14..20,
+        ),
+        (
+            "every week on",
+            TextErrorKind::UnexpectedEnd("a day".into()),
+            13..13,
+        ),
+        ("on Monday", TextErrorKind::MissingFrequency, 0..9),
+        (
+            "every day & night",
+            TextErrorKind::Unexpected("&".into()),
+            10..11,
+        ),
+        (
+            "daily, monthly",
+            TextErrorKind::Duplicate("frequency".into()),
+            7..14,
+        ),
+        (
+            "every day at 9:00 and 17:30",
+            TextErrorKind::UnsupportedTimes("9:00 and 17:30".into()),
+            13..27,
+        ),
+        (
+            "every month on the 32nd",
+            TextErrorKind::InvalidValue {
+                value: "32nd".into(),
+                part: "day of the month".into(),
+            },
+            19..23,
+        ),
+        (
+            "every day until February 30, 2023",
+            TextErrorKind::InvalidDate("February 30, 2023".into()),
+            16..33,
+        ),
+        #[cfg(not(feature = "by-easter"))]
+        (
+            "every year 3 days after Easter",
+            TextErrorKind::EasterNotEnabled("3 days after Easter".into()),
+            11..30,
+        ),
+    ];
+    for (text, kind, span) in tests {
+        let error = RRule::from_text(text).unwrap_err();
+        assert_eq!(error.get_kind(), &kind, "{text}");
+        assert_eq!(error.get_span(), span, "{text}");
+    }
+}
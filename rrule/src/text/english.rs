@@ -0,0 +1,237 @@
+use super::{join, Conjunction, Locale, Phrase};
+use crate::{Frequency, NWeekday, RScale, Skip};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Describes recurrence rules in English, like "every 2 weeks on Monday and Friday".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct English;
+
+impl English {
+    /// Returns `n` followed by `unit`, in plural when `n` is not 1.
+    fn quantity(&self, n: i64, unit: &str) -> String {
+        if self.is_plural(n) {
+            format!("{} {}s", n, unit)
+        } else {
+            format!("{} {}", n, unit)
+        }
+    }
+
+    /// Returns the ordinals of `positions`, like "1st and last".
+    fn ordinals(&self, positions: &[i64]) -> String {
+        let ordinals = positions.iter().map(|n| self.ordinal(*n));
+        self.list(ordinals.collect(), Conjunction::And)
+    }
+}
+
+impl Locale for English {
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        let name = match weekday {
+            Weekday::Mon => "Monday",
+            Weekday::Tue => "Tuesday",
+            Weekday::Wed => "Wednesday",
+            Weekday::Thu => "Thursday",
+            Weekday::Fri => "Friday",
+            Weekday::Sat => "Saturday",
+            Weekday::Sun => "Sunday",
+        };
+        name.to_string()
+    }
+
+    fn month_name(&self, month: u8) -> String {
+        let name = match month {
+            1 => "January",
+            2 => "February",
+            3 => "March",
+            4 => "April",
+            5 => "May",
+            6 => "June",
+            7 => "July",
+            8 => "August",
+            9 => "September",
+            10 => "October",
+            11 => "November",
+            _ => "December",
+        };
+        name.to_string()
+    }
+
+    fn ordinal(&self, n: i64) -> String {
+        match n {
+            -1 => "last".to_string(),
+            ..=-2 => format!("{} to last", self.ordinal(-n)),
+            _ => {
+                let suffix = match (n % 10, n % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{}", n, suffix)
+            }
+        }
+    }
+
+    fn list(&self, items: Vec<String>, conjunction: Conjunction) -> String {
+        match conjunction {
+            Conjunction::And => join(items, ", ", " and "),
+            Conjunction::Or => join(items, ", ", " or "),
+        }
+    }
+
+    fn date(&self, date: NaiveDate) -> String {
+        let month = u8::try_from(date.month()).expect("month is between 1 and 12");
+        format!("{} {}, {}", self.month_name(month), date.day(), date.year())
+    }
+
+    fn phrase(&self, phrase: &Phrase<'_>) -> String {
+        match *phrase {
+            Phrase::Frequency { freq, interval } => {
+                let unit = match freq {
+                    Frequency::Yearly => "year",
+                    Frequency::Monthly => "month",
+                    Frequency::Weekly => "week",
+                    Frequency::Daily => "day",
+                    Frequency::Hourly => "hour",
+                    Frequency::Minutely => "minute",
+                    Frequency::Secondly => "second",
+                };
+                match interval {
+                    1 => format!("every {}", unit),
+                    interval => format!("every {}", self.quantity(i64::from(interval), unit)),
+                }
+            }
+            Phrase::EveryWeekday => "every weekday".to_string(),
+            Phrase::Calendar(calendar) => {
+                let name = match calendar {
+                    RScale::Gregorian => "Gregorian",
+                    RScale::Hebrew => "Hebrew",
+                    RScale::IslamicCivil => "civil Islamic",
+                };
+                format!(" ({} calendar)", name)
+            }
+            Phrase::Months { months, calendar } => {
+                let months = months.iter().map(|(month, leap)| match (calendar, leap) {
+                    (None, _) => self.month_name(*month),
+                    (Some(_), false) => format!("month {}", month),
+                    (Some(_), true) => format!("leap month {}", month),
+                });
+                format!(" in {}", self.list(months.collect(), Conjunction::And))
+            }
+            Phrase::WeekNumbers(weeks) => {
+                let weeks = weeks.iter().map(|week| match week {
+                    1.. => format!("week {}", week),
+                    _ => format!("the {} week", self.ordinal(*week)),
+                });
+                format!(" in {}", self.list(weeks.collect(), Conjunction::And))
+            }
+            Phrase::YearDays(days) => {
+                format!(" on the {} day of the year", self.ordinals(days))
+            }
+            Phrase::Weekdays { weekdays, of_year } => {
+                let weekdays = weekdays.iter().map(|weekday| match weekday {
+                    NWeekday::Every(weekday) => self.weekday_name(*weekday),
+                    NWeekday::Nth(n, weekday) => {
+                        let text = format!(
+                            "the {} {}",
+                            self.ordinal(i64::from(*n)),
+                            self.weekday_name(*weekday)
+                        );
+                        if of_year {
+                            format!("{} of the year", text)
+                        } else {
+                            text
+                        }
+                    }
+                });
+                format!(" on {}", self.list(weekdays.collect(), Conjunction::And))
+            }
+            Phrase::MonthDays {
+                days,
+                after_weekdays,
+            } => {
+                let days = days.iter().map(|day| match day {
+                    1.. => self.ordinal(*day),
+                    _ => format!("{} day", self.ordinal(*day)),
+                });
+                let days = self.list(days.collect(), Conjunction::And);
+                if after_weekdays {
+                    format!(" the {}", days)
+                } else {
+                    format!(" on the {}", days)
+                }
+            }
+            Phrase::DayPositions(positions) => format!(" on the {} day", self.ordinals(positions)),
+            Phrase::WeekdayPositions(positions) => {
+                format!(" on the {} weekday", self.ordinals(positions))
+            }
+            Phrase::NamedDayPositions {
+                positions,
+                weekdays,
+            } => {
+                let weekdays = weekdays.iter().map(|day| self.weekday_name(*day));
+                format!(
+                    " on the {} {}",
+                    self.ordinals(positions),
+                    self.list(weekdays.collect(), Conjunction::Or)
+                )
+            }
+            Phrase::SetPositions(positions) => {
+                format!(" (only the {} in each period)", self.ordinals(positions))
+            }
+            Phrase::Easter(offset) => match offset {
+                0 => " on Easter".to_string(),
+                1.. => format!(" {} after Easter", self.quantity(i64::from(offset), "day")),
+                _ => format!(
+                    " {} before Easter",
+                    self.quantity(-i64::from(offset), "day")
+                ),
+            },
+            Phrase::Skip(skip) => match skip {
+                Skip::Backward => " (or earlier if the date does not exist)".to_string(),
+                Skip::Forward => " (or later if the date does not exist)".to_string(),
+                Skip::Omit => String::new(),
+            },
+            Phrase::Times(times) => {
+                let times = times.iter().map(|time| self.time(*time));
+                format!(" at {}", self.list(times.collect(), Conjunction::And))
+            }
+            Phrase::TimeParts {
+                hours,
+                minutes,
+                seconds,
+            } => {
+                let values = |values: &[u8]| {
+                    let values = values.iter().map(ToString::to_string);
+                    self.list(values.collect(), Conjunction::And)
+                };
+                let mut parts = vec![];
+                if !hours.is_empty() {
+                    parts.push(format!("{} o'clock", values(hours)));
+                }
+                for (unit, units, numbers) in [
+                    ("minute", "minutes", minutes),
+                    ("second", "seconds", seconds),
+                ] {
+                    match numbers.len() {
+                        0 => {}
+                        1 => parts.push(format!("{} {}", unit, values(numbers))),
+                        _ => parts.push(format!("{} {}", units, values(numbers))),
+                    }
+                }
+                format!(" at {}", self.list(parts, Conjunction::And))
+            }
+            Phrase::Count(1) => ", once".to_string(),
+            Phrase::Count(count) => format!(", {} times", count),
+            Phrase::Until(until) => format!(" until {}", self.date(until.date())),
+            Phrase::Dates(dates) => {
+                let dates = dates.iter().map(|(date, time)| match time {
+                    Some(time) => format!("{} at {}", self.date(*date), self.time(*time)),
+                    None => self.date(*date),
+                });
+                format!("on {}", self.list(dates.collect(), Conjunction::And))
+            }
+            Phrase::Except(exceptions) => format!(", except {}", exceptions),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+#![allow(clippy::module_name_repetitions)]
+use std::ops::Range;
+use thiserror::Error;
+
+/// Error of [`crate::RRule::from_text`], with the span of the text which caused it.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{kind} (at {}..{})", span.start, span.end)]
+pub struct TextError {
+    pub(crate) kind: TextErrorKind,
+    pub(crate) span: Range<usize>,
+}
+
+impl TextError {
+    pub(crate) fn new(kind: TextErrorKind, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+
+    /// Returns what went wrong.
+    #[must_use]
+    pub fn get_kind(&self) -> &TextErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte range of the text which caused the error.
+    ///
+    /// The range is empty and at the end of the text when the text ended too early.
+    #[must_use]
+    pub fn get_span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+/// The kinds of errors that can occur while parsing the description of a recurrence rule.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum TextErrorKind {
+    #[error("`{0}` was not expected here.")]
+    Unexpected(String),
+    #[error("Expected {expected}, but found `{found}`.")]
+    Expected { expected: String, found: String },
+    #[error("Expected {0}, but the text ended.")]
+    UnexpectedEnd(String),
+    #[error("The text has no frequency, like `every week` or `monthly`.")]
+    MissingFrequency,
+    #[error("The {0} is given more than once.")]
+    Duplicate(String),
+    #[error("`{value}` is not a valid {part}.")]
+    InvalidValue { value: String, part: String },
+    #[error("`{0}` is not a valid date.")]
+    InvalidDate(String),
+    #[error("The times `{0}` can't be described by a rule, which recurs at every combination of its hours, minutes and seconds.")]
+    UnsupportedTimes(String),
+    #[error("`{0}` is only supported with the `by-easter` feature.")]
+    EasterNotEnabled(String),
+}
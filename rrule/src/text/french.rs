@@ -0,0 +1,276 @@
+use super::{join, Conjunction, Locale, Phrase};
+use crate::{Frequency, NWeekday, RScale, Skip};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Describes recurrence rules in French, like "toutes les 2 semaines le lundi et le vendredi".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct French;
+
+impl French {
+    /// Returns `n` followed by `unit`, with an "s" when it is plural.
+    fn quantity(&self, n: i64, unit: &str) -> String {
+        if self.is_plural(n) {
+            format!("{} {}s", n, unit)
+        } else {
+            format!("{} {}", n, unit)
+        }
+    }
+
+    /// Returns the ordinals of `positions`, like "1er et dernier".
+    fn ordinals(&self, positions: &[i64]) -> String {
+        let ordinals = positions.iter().map(|n| self.ordinal(*n));
+        self.list(ordinals.collect(), Conjunction::And)
+    }
+
+    /// Returns `text` after the article "le", which is elided before a vowel, like
+    /// "l'avant-dernier jour".
+    fn the(text: String) -> String {
+        if text.starts_with(['a', 'e', 'i', 'o', 'u']) {
+            format!("l'{}", text)
+        } else {
+            format!("le {}", text)
+        }
+    }
+
+    /// Returns the day of the month, like "1er" or "15".
+    fn day(day: impl Into<i64>) -> String {
+        match day.into() {
+            1 => "1er".to_string(),
+            day => day.to_string(),
+        }
+    }
+}
+
+impl Locale for French {
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        let name = match weekday {
+            Weekday::Mon => "lundi",
+            Weekday::Tue => "mardi",
+            Weekday::Wed => "mercredi",
+            Weekday::Thu => "jeudi",
+            Weekday::Fri => "vendredi",
+            Weekday::Sat => "samedi",
+            Weekday::Sun => "dimanche",
+        };
+        name.to_string()
+    }
+
+    fn month_name(&self, month: u8) -> String {
+        let name = match month {
+            1 => "janvier",
+            2 => "février",
+            3 => "mars",
+            4 => "avril",
+            5 => "mai",
+            6 => "juin",
+            7 => "juillet",
+            8 => "août",
+            9 => "septembre",
+            10 => "octobre",
+            11 => "novembre",
+            _ => "décembre",
+        };
+        name.to_string()
+    }
+
+    fn ordinal(&self, n: i64) -> String {
+        match n {
+            -1 => "dernier".to_string(),
+            -2 => "avant-dernier".to_string(),
+            ..=-3 => format!("{}e dernier", -n),
+            1 => "1er".to_string(),
+            _ => format!("{}e", n),
+        }
+    }
+
+    /// Returns true if `n` is plural, which in French is only the case from 2 on.
+    fn is_plural(&self, n: i64) -> bool {
+        n.abs() >= 2
+    }
+
+    fn list(&self, items: Vec<String>, conjunction: Conjunction) -> String {
+        match conjunction {
+            Conjunction::And => join(items, ", ", " et "),
+            Conjunction::Or => join(items, ", ", " ou "),
+        }
+    }
+
+    fn date(&self, date: NaiveDate) -> String {
+        let month = u8::try_from(date.month()).expect("month is between 1 and 12");
+        format!(
+            "{} {} {}",
+            Self::day(date.day()),
+            self.month_name(month),
+            date.year()
+        )
+    }
+
+    fn phrase(&self, phrase: &Phrase<'_>) -> String {
+        match *phrase {
+            Phrase::Frequency { freq, interval } => {
+                let (every, all, units) = match freq {
+                    Frequency::Yearly => ("chaque année", "tous les", "ans"),
+                    Frequency::Monthly => ("chaque mois", "tous les", "mois"),
+                    Frequency::Weekly => ("chaque semaine", "toutes les", "semaines"),
+                    Frequency::Daily => ("chaque jour", "tous les", "jours"),
+                    Frequency::Hourly => ("chaque heure", "toutes les", "heures"),
+                    Frequency::Minutely => ("chaque minute", "toutes les", "minutes"),
+                    Frequency::Secondly => ("chaque seconde", "toutes les", "secondes"),
+                };
+                match interval {
+                    1 => every.to_string(),
+                    interval => format!("{} {} {}", all, interval, units),
+                }
+            }
+            Phrase::EveryWeekday => "chaque jour ouvrable".to_string(),
+            Phrase::Calendar(calendar) => {
+                let name = match calendar {
+                    RScale::Gregorian => "grégorien",
+                    RScale::Hebrew => "hébraïque",
+                    RScale::IslamicCivil => "islamique civil",
+                };
+                format!(" (calendrier {})", name)
+            }
+            Phrase::Months { months, calendar } => {
+                let names = months.iter().map(|(month, leap)| match (calendar, leap) {
+                    (None, _) => self.month_name(*month),
+                    (Some(_), false) => format!("mois {}", month),
+                    (Some(_), true) => format!("mois intercalaire {}", month),
+                });
+                let names = self.list(names.collect(), Conjunction::And);
+                match calendar {
+                    None => format!(" en {}", names),
+                    Some(_) => format!(" au {}", names),
+                }
+            }
+            Phrase::WeekNumbers(weeks) => {
+                let weeks = weeks.iter().map(|week| match week {
+                    1.. => format!("la semaine {}", week),
+                    -1 => "la dernière semaine".to_string(),
+                    -2 => "l'avant-dernière semaine".to_string(),
+                    _ => format!("la {}e dernière semaine", -week),
+                });
+                format!(" pendant {}", self.list(weeks.collect(), Conjunction::And))
+            }
+            Phrase::YearDays(days) => {
+                format!(" {} jour de l'année", Self::the(self.ordinals(days)))
+            }
+            Phrase::Weekdays { weekdays, of_year } => {
+                let weekdays = weekdays.iter().map(|weekday| match weekday {
+                    NWeekday::Every(weekday) => format!("le {}", self.weekday_name(*weekday)),
+                    NWeekday::Nth(n, weekday) => {
+                        let text = Self::the(format!(
+                            "{} {}",
+                            self.ordinal(i64::from(*n)),
+                            self.weekday_name(*weekday)
+                        ));
+                        if of_year {
+                            format!("{} de l'année", text)
+                        } else {
+                            text
+                        }
+                    }
+                });
+                format!(" {}", self.list(weekdays.collect(), Conjunction::And))
+            }
+            Phrase::MonthDays {
+                days,
+                after_weekdays,
+            } => {
+                let days = days.iter().map(|day| {
+                    let text = match day {
+                        1.. => Self::day(*day),
+                        _ => format!("{} jour", self.ordinal(*day)),
+                    };
+                    if after_weekdays {
+                        text
+                    } else {
+                        Self::the(text)
+                    }
+                });
+                format!(" {}", self.list(days.collect(), Conjunction::And))
+            }
+            Phrase::DayPositions(positions) => {
+                format!(" {} jour", Self::the(self.ordinals(positions)))
+            }
+            Phrase::WeekdayPositions(positions) => {
+                format!(" {} jour ouvrable", Self::the(self.ordinals(positions)))
+            }
+            Phrase::NamedDayPositions {
+                positions,
+                weekdays,
+            } => {
+                let weekdays = weekdays.iter().map(|day| self.weekday_name(*day));
+                format!(
+                    " {} {}",
+                    Self::the(self.ordinals(positions)),
+                    self.list(weekdays.collect(), Conjunction::Or)
+                )
+            }
+            Phrase::SetPositions(positions) => {
+                format!(
+                    " (seulement {} de chaque période)",
+                    Self::the(self.ordinals(positions))
+                )
+            }
+            Phrase::Easter(offset) => match offset {
+                0 => " à Pâques".to_string(),
+                1.. => format!(" {} après Pâques", self.quantity(i64::from(offset), "jour")),
+                _ => format!(
+                    " {} avant Pâques",
+                    self.quantity(-i64::from(offset), "jour")
+                ),
+            },
+            Phrase::Skip(skip) => match skip {
+                Skip::Backward => " (ou plus tôt si la date n'existe pas)".to_string(),
+                Skip::Forward => " (ou plus tard si la date n'existe pas)".to_string(),
+                Skip::Omit => String::new(),
+            },
+            Phrase::Times(times) => {
+                let times = times.iter().map(|time| self.time(*time));
+                format!(" à {}", self.list(times.collect(), Conjunction::And))
+            }
+            Phrase::TimeParts {
+                hours,
+                minutes,
+                seconds,
+            } => {
+                let values = |values: &[u8]| {
+                    let values = values.iter().map(ToString::to_string);
+                    self.list(values.collect(), Conjunction::And)
+                };
+                let mut parts = vec![];
+                if !hours.is_empty() {
+                    parts.push(format!("{} h", values(hours)));
+                }
+                for (unit, units, numbers) in [
+                    ("la minute", "les minutes", minutes),
+                    ("la seconde", "les secondes", seconds),
+                ] {
+                    match numbers.len() {
+                        0 => {}
+                        1 => parts.push(format!("{} {}", unit, values(numbers))),
+                        _ => parts.push(format!("{} {}", units, values(numbers))),
+                    }
+                }
+                let parts = self.list(parts, Conjunction::And);
+                // "à les" is contracted to "aux"
+                match parts.strip_prefix("les ") {
+                    Some(parts) => format!(" aux {}", parts),
+                    None => format!(" à {}", parts),
+                }
+            }
+            Phrase::Count(1) => ", une fois".to_string(),
+            Phrase::Count(count) => format!(", {} fois", count),
+            Phrase::Until(until) => format!(" jusqu'au {}", self.date(until.date())),
+            Phrase::Dates(dates) => {
+                let dates = dates.iter().map(|(date, time)| match time {
+                    Some(time) => format!("le {} à {}", self.date(*date), self.time(*time)),
+                    None => format!("le {}", self.date(*date)),
+                });
+                self.list(dates.collect(), Conjunction::And)
+            }
+            Phrase::Except(exceptions) => format!(", sauf {}", exceptions),
+        }
+    }
+}
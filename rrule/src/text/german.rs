@@ -0,0 +1,229 @@
+use super::{join, Conjunction, Locale, Phrase};
+use crate::{Frequency, NWeekday, RScale, Skip};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Describes recurrence rules in German, like "alle 2 Wochen am Montag und Freitag".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct German;
+
+impl German {
+    /// Returns the ordinals of `positions`, like "1. und letzten".
+    fn ordinals(&self, positions: &[i64]) -> String {
+        let ordinals = positions.iter().map(|n| self.ordinal(*n));
+        self.list(ordinals.collect(), Conjunction::And)
+    }
+}
+
+impl Locale for German {
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        let name = match weekday {
+            Weekday::Mon => "Montag",
+            Weekday::Tue => "Dienstag",
+            Weekday::Wed => "Mittwoch",
+            Weekday::Thu => "Donnerstag",
+            Weekday::Fri => "Freitag",
+            Weekday::Sat => "Samstag",
+            Weekday::Sun => "Sonntag",
+        };
+        name.to_string()
+    }
+
+    fn month_name(&self, month: u8) -> String {
+        let name = match month {
+            1 => "Januar",
+            2 => "Februar",
+            3 => "März",
+            4 => "April",
+            5 => "Mai",
+            6 => "Juni",
+            7 => "Juli",
+            8 => "August",
+            9 => "September",
+            10 => "Oktober",
+            11 => "November",
+            _ => "Dezember",
+        };
+        name.to_string()
+    }
+
+    /// Returns the ordinal of a position, like "2.", or "letzten" and "vorletzten" when it is
+    /// negative, in the dative case of "am letzten Freitag".
+    fn ordinal(&self, n: i64) -> String {
+        match n {
+            -1 => "letzten".to_string(),
+            -2 => "vorletzten".to_string(),
+            ..=-3 => format!("{}.-letzten", -n),
+            _ => format!("{}.", n),
+        }
+    }
+
+    fn list(&self, items: Vec<String>, conjunction: Conjunction) -> String {
+        match conjunction {
+            Conjunction::And => join(items, ", ", " und "),
+            Conjunction::Or => join(items, ", ", " oder "),
+        }
+    }
+
+    fn date(&self, date: NaiveDate) -> String {
+        let month = u8::try_from(date.month()).expect("month is between 1 and 12");
+        format!("{}. {} {}", date.day(), self.month_name(month), date.year())
+    }
+
+    fn phrase(&self, phrase: &Phrase<'_>) -> String {
+        match *phrase {
+            Phrase::Frequency { freq, interval } => {
+                let (every, unit, units) = match freq {
+                    Frequency::Yearly => ("jedes", "Jahr", "Jahre"),
+                    Frequency::Monthly => ("jeden", "Monat", "Monate"),
+                    Frequency::Weekly => ("jede", "Woche", "Wochen"),
+                    Frequency::Daily => ("jeden", "Tag", "Tage"),
+                    Frequency::Hourly => ("jede", "Stunde", "Stunden"),
+                    Frequency::Minutely => ("jede", "Minute", "Minuten"),
+                    Frequency::Secondly => ("jede", "Sekunde", "Sekunden"),
+                };
+                if self.is_plural(i64::from(interval)) {
+                    format!("alle {} {}", interval, units)
+                } else {
+                    format!("{} {}", every, unit)
+                }
+            }
+            Phrase::EveryWeekday => "jeden Werktag".to_string(),
+            Phrase::Calendar(calendar) => {
+                let name = match calendar {
+                    RScale::Gregorian => "gregorianischer",
+                    RScale::Hebrew => "hebräischer",
+                    RScale::IslamicCivil => "islamischer",
+                };
+                format!(" ({} Kalender)", name)
+            }
+            Phrase::Months { months, calendar } => {
+                let months = months.iter().map(|(month, leap)| match (calendar, leap) {
+                    (None, _) => self.month_name(*month),
+                    (Some(_), false) => format!("Monat {}", month),
+                    (Some(_), true) => format!("Schaltmonat {}", month),
+                });
+                format!(" im {}", self.list(months.collect(), Conjunction::And))
+            }
+            Phrase::WeekNumbers(weeks) => {
+                let weeks = weeks.iter().map(|week| match week {
+                    1.. => format!("Woche {}", week),
+                    _ => format!("der {} Woche", self.ordinal(*week)),
+                });
+                format!(" in {}", self.list(weeks.collect(), Conjunction::And))
+            }
+            Phrase::YearDays(days) => format!(" am {} Tag des Jahres", self.ordinals(days)),
+            Phrase::Weekdays { weekdays, of_year } => {
+                let weekdays = weekdays.iter().map(|weekday| match weekday {
+                    NWeekday::Every(weekday) => self.weekday_name(*weekday),
+                    NWeekday::Nth(n, weekday) => {
+                        let text = format!(
+                            "{} {}",
+                            self.ordinal(i64::from(*n)),
+                            self.weekday_name(*weekday)
+                        );
+                        if of_year {
+                            format!("{} des Jahres", text)
+                        } else {
+                            text
+                        }
+                    }
+                });
+                format!(" am {}", self.list(weekdays.collect(), Conjunction::And))
+            }
+            Phrase::MonthDays {
+                days,
+                after_weekdays,
+            } => {
+                let days = days.iter().map(|day| match day {
+                    1.. => self.ordinal(*day),
+                    _ => format!("{} Tag", self.ordinal(*day)),
+                });
+                let days = self.list(days.collect(), Conjunction::And);
+                if after_weekdays {
+                    format!(", den {}", days)
+                } else {
+                    format!(" am {}", days)
+                }
+            }
+            Phrase::DayPositions(positions) => format!(" am {} Tag", self.ordinals(positions)),
+            Phrase::WeekdayPositions(positions) => {
+                format!(" am {} Werktag", self.ordinals(positions))
+            }
+            Phrase::NamedDayPositions {
+                positions,
+                weekdays,
+            } => {
+                let weekdays = weekdays.iter().map(|day| self.weekday_name(*day));
+                format!(
+                    " am {} {}",
+                    self.ordinals(positions),
+                    self.list(weekdays.collect(), Conjunction::Or)
+                )
+            }
+            Phrase::SetPositions(positions) => {
+                format!(
+                    " (nur den {} Termin jedes Zeitraums)",
+                    self.ordinals(positions)
+                )
+            }
+            Phrase::Easter(offset) => {
+                let days = i64::from(offset).abs();
+                let days = if self.is_plural(days) {
+                    format!("{} Tage", days)
+                } else {
+                    format!("{} Tag", days)
+                };
+                match offset {
+                    0 => " an Ostern".to_string(),
+                    1.. => format!(" {} nach Ostern", days),
+                    _ => format!(" {} vor Ostern", days),
+                }
+            }
+            Phrase::Skip(skip) => match skip {
+                Skip::Backward => " (oder früher, falls das Datum nicht existiert)".to_string(),
+                Skip::Forward => " (oder später, falls das Datum nicht existiert)".to_string(),
+                Skip::Omit => String::new(),
+            },
+            Phrase::Times(times) => {
+                let times = times.iter().map(|time| self.time(*time));
+                format!(" um {}", self.list(times.collect(), Conjunction::And))
+            }
+            Phrase::TimeParts {
+                hours,
+                minutes,
+                seconds,
+            } => {
+                let values = |values: &[u8]| {
+                    let values = values.iter().map(ToString::to_string);
+                    self.list(values.collect(), Conjunction::And)
+                };
+                let mut parts = vec![];
+                if !hours.is_empty() {
+                    parts.push(format!("{} Uhr", values(hours)));
+                }
+                for (unit, units, numbers) in [
+                    ("Minute", "Minuten", minutes),
+                    ("Sekunde", "Sekunden", seconds),
+                ] {
+                    match numbers.len() {
+                        0 => {}
+                        1 => parts.push(format!("{} {}", unit, values(numbers))),
+                        _ => parts.push(format!("{} {}", units, values(numbers))),
+                    }
+                }
+                format!(" um {}", self.list(parts, Conjunction::And))
+            }
+            Phrase::Count(1) => ", einmal".to_string(),
+            Phrase::Count(count) => format!(", {} Mal", count),
+            Phrase::Until(until) => format!(" bis zum {}", self.date(until.date())),
+            Phrase::Dates(dates) => {
+                let dates = dates.iter().map(|(date, time)| match time {
+                    Some(time) => format!("{} um {}", self.date(*date), self.time(*time)),
+                    None => self.date(*date),
+                });
+                format!("am {}", self.list(dates.collect(), Conjunction::And))
+            }
+            Phrase::Except(exceptions) => format!(", außer {}", exceptions),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::{join, Conjunction, Locale, Phrase};
+use crate::{Frequency, NWeekday, RScale, Skip};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Describes recurrence rules in Japanese, like "2週間ごと 月曜日と金曜日".
+///
+/// The phrases are separated by spaces, like the dates and times of a calendar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Japanese;
+
+impl Japanese {
+    /// Returns the position of `unit`, like "第2火曜日", "最終週" or "最後から2番目の日".
+    fn nth(&self, n: i64, unit: &str) -> String {
+        match n {
+            ..=-2 => format!("{}の{}", self.ordinal(n), unit),
+            _ => format!("{}{}", self.ordinal(n), unit),
+        }
+    }
+
+    /// Returns the positions of `unit`, like "第1日と最終日".
+    fn nths(&self, positions: &[i64], unit: &str) -> String {
+        let positions = positions.iter().map(|n| self.nth(*n, unit));
+        self.list(positions.collect(), Conjunction::And)
+    }
+}
+
+impl Locale for Japanese {
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        let name = match weekday {
+            Weekday::Mon => "月曜日",
+            Weekday::Tue => "火曜日",
+            Weekday::Wed => "水曜日",
+            Weekday::Thu => "木曜日",
+            Weekday::Fri => "金曜日",
+            Weekday::Sat => "土曜日",
+            Weekday::Sun => "日曜日",
+        };
+        name.to_string()
+    }
+
+    fn month_name(&self, month: u8) -> String {
+        format!("{}月", month)
+    }
+
+    fn ordinal(&self, n: i64) -> String {
+        match n {
+            -1 => "最終".to_string(),
+            ..=-2 => format!("最後から{}番目", -n),
+            _ => format!("第{}", n),
+        }
+    }
+
+    /// Returns false, as Japanese has no plural.
+    fn is_plural(&self, _n: i64) -> bool {
+        false
+    }
+
+    fn list(&self, items: Vec<String>, conjunction: Conjunction) -> String {
+        match conjunction {
+            Conjunction::And => join(items, "、", "と"),
+            Conjunction::Or => join(items, "、", "または"),
+        }
+    }
+
+    fn date(&self, date: NaiveDate) -> String {
+        format!("{}年{}月{}日", date.year(), date.month(), date.day())
+    }
+
+    fn phrase(&self, phrase: &Phrase<'_>) -> String {
+        match *phrase {
+            Phrase::Frequency { freq, interval } => {
+                let (every, unit) = match freq {
+                    Frequency::Yearly => ("毎年", "年"),
+                    Frequency::Monthly => ("毎月", "か月"),
+                    Frequency::Weekly => ("毎週", "週間"),
+                    Frequency::Daily => ("毎日", "日"),
+                    Frequency::Hourly => ("毎時", "時間"),
+                    Frequency::Minutely => ("毎分", "分"),
+                    Frequency::Secondly => ("毎秒", "秒"),
+                };
+                match interval {
+                    1 => every.to_string(),
+                    interval => format!("{}{}ごと", interval, unit),
+                }
+            }
+            Phrase::EveryWeekday => "毎週平日".to_string(),
+            Phrase::Calendar(calendar) => {
+                let name = match calendar {
+                    RScale::Gregorian => "グレゴリオ暦",
+                    RScale::Hebrew => "ヘブライ暦",
+                    RScale::IslamicCivil => "イスラム暦",
+                };
+                format!("（{}）", name)
+            }
+            Phrase::Months { months, .. } => {
+                let months = months.iter().map(|(month, leap)| {
+                    if *leap {
+                        format!("閏{}", self.month_name(*month))
+                    } else {
+                        self.month_name(*month)
+                    }
+                });
+                format!(" {}", self.list(months.collect(), Conjunction::And))
+            }
+            Phrase::WeekNumbers(weeks) => format!(" {}", self.nths(weeks, "週")),
+            Phrase::YearDays(days) => format!(" 年の{}", self.nths(days, "日")),
+            Phrase::Weekdays { weekdays, of_year } => {
+                let weekdays = weekdays.iter().map(|weekday| match weekday {
+                    NWeekday::Every(weekday) => self.weekday_name(*weekday),
+                    NWeekday::Nth(n, weekday) => {
+                        let text = self.nth(i64::from(*n), &self.weekday_name(*weekday));
+                        if of_year {
+                            format!("年の{}", text)
+                        } else {
+                            text
+                        }
+                    }
+                });
+                format!(" {}", self.list(weekdays.collect(), Conjunction::And))
+            }
+            Phrase::MonthDays { days, .. } => {
+                let days = days.iter().map(|day| match day {
+                    1.. => format!("{}日", day),
+                    _ => self.nth(*day, "日"),
+                });
+                format!(" {}", self.list(days.collect(), Conjunction::And))
+            }
+            Phrase::DayPositions(positions) => format!(" {}", self.nths(positions, "日")),
+            Phrase::WeekdayPositions(positions) => format!(" {}", self.nths(positions, "平日")),
+            Phrase::NamedDayPositions {
+                positions,
+                weekdays,
+            } => {
+                let positions = positions.iter().map(|n| self.ordinal(*n));
+                let weekdays = weekdays.iter().map(|day| self.weekday_name(*day));
+                format!(
+                    " {}の{}",
+                    self.list(positions.collect(), Conjunction::And),
+                    self.list(weekdays.collect(), Conjunction::Or)
+                )
+            }
+            Phrase::SetPositions(positions) => {
+                let positions = positions.iter().map(|n| self.ordinal(*n));
+                format!(
+                    "（各期間の{}のみ）",
+                    self.list(positions.collect(), Conjunction::And)
+                )
+            }
+            Phrase::Easter(offset) => match offset {
+                0 => " イースター".to_string(),
+                1.. => format!(" イースターの{}日後", offset),
+                _ => format!(" イースターの{}日前", -i64::from(offset)),
+            },
+            Phrase::Skip(skip) => match skip {
+                Skip::Backward => "（日付が存在しない場合はその前）".to_string(),
+                Skip::Forward => "（日付が存在しない場合はその後）".to_string(),
+                Skip::Omit => String::new(),
+            },
+            Phrase::Times(times) => {
+                let times = times.iter().map(|time| self.time(*time));
+                format!(" {}", self.list(times.collect(), Conjunction::And))
+            }
+            Phrase::TimeParts {
+                hours,
+                minutes,
+                seconds,
+            } => {
+                let mut parts = vec![];
+                for (unit, numbers) in [("時", hours), ("分", minutes), ("秒", seconds)] {
+                    if !numbers.is_empty() {
+                        let values = numbers.iter().map(|n| format!("{}{}", n, unit));
+                        parts.push(self.list(values.collect(), Conjunction::And));
+                    }
+                }
+                format!(" {}", parts.join("、"))
+            }
+            Phrase::Count(count) => format!("、{}回", count),
+            Phrase::Until(until) => format!("、{}まで", self.date(until.date())),
+            Phrase::Dates(dates) => {
+                let dates = dates.iter().map(|(date, time)| match time {
+                    Some(time) => format!("{} {}", self.date(*date), self.time(*time)),
+                    None => self.date(*date),
+                });
+                self.list(dates.collect(), Conjunction::And)
+            }
+            Phrase::Except(exceptions) => format!("、{}を除く", exceptions),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Frequency, NWeekday, RScale, Skip};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+
+/// The words and grammar used to describe recurrence rules in a language, see
+/// [`crate::RRule::to_text_in`].
+///
+/// A description is made of [`Phrase`]s, which are rendered by the locale and concatenated
+/// in order. Every phrase but the first one starts with its own separator, like a space or a
+/// comma, and the order of the words inside a phrase is up to the locale.
+///
+/// # Usage
+///
+/// A locale can be implemented for another language, or to change some phrases of a bundled
+/// locale:
+///
+/// ```
+/// use rrule::{Conjunction, English, Locale, NWeekday, Phrase, RRule, Frequency, Weekday};
+/// use chrono::NaiveDate;
+///
+/// struct Short;
+///
+/// impl Locale for Short {
+///     fn weekday_name(&self, weekday: Weekday) -> String {
+///         English.weekday_name(weekday)
+///     }
+///     fn month_name(&self, month: u8) -> String {
+///         English.month_name(month)
+///     }
+///     fn ordinal(&self, n: i64) -> String {
+///         English.ordinal(n)
+///     }
+///     fn list(&self, items: Vec<String>, conjunction: Conjunction) -> String {
+///         English.list(items, conjunction)
+///     }
+///     fn date(&self, date: NaiveDate) -> String {
+///         date.format("%Y-%m-%d").to_string()
+///     }
+///     fn phrase(&self, phrase: &Phrase<'_>) -> String {
+///         match phrase {
+///             Phrase::Frequency { freq: Frequency::Weekly, interval: 1 } => "weekly".to_string(),
+///             Phrase::Until(until) => format!(" until {}", self.date(until.date())),
+///             phrase => English.phrase(phrase),
+///         }
+///     }
+/// }
+///
+/// let rrule = RRule::new(Frequency::Weekly)
+///     .by_weekday(vec![NWeekday::Every(Weekday::Mon), NWeekday::Every(Weekday::Fri)]);
+/// assert_eq!(rrule.to_text_in(&Short), "weekly on Monday and Friday");
+/// ```
+pub trait Locale {
+    /// Returns the name of a weekday, like "Monday".
+    fn weekday_name(&self, weekday: Weekday) -> String;
+
+    /// Returns the name of a month of the Gregorian calendar, from 1 for January to 12.
+    fn month_name(&self, month: u8) -> String;
+
+    /// Returns the ordinal of a position, like "2nd".
+    ///
+    /// Negative positions count from the end, like "last" for `-1`.
+    fn ordinal(&self, n: i64) -> String;
+
+    /// Returns true if a quantity of `n` takes the plural form, like "2 days".
+    fn is_plural(&self, n: i64) -> bool {
+        n != 1
+    }
+
+    /// Joins the items of a list, like "Monday, Wednesday and Friday".
+    fn list(&self, items: Vec<String>, conjunction: Conjunction) -> String;
+
+    /// Returns a date, like "March 1, 2023".
+    fn date(&self, date: NaiveDate) -> String;
+
+    /// Returns a time of day, like "9:00", or "9:00:30" when it has seconds.
+    fn time(&self, time: NaiveTime) -> String {
+        match time.second() {
+            0 => format!("{}:{:02}", time.hour(), time.minute()),
+            second => format!("{}:{:02}:{:02}", time.hour(), time.minute(), second),
+        }
+    }
+
+    /// Renders a phrase of a description.
+    fn phrase(&self, phrase: &Phrase<'_>) -> String;
+}
+
+/// The word before the last item of a list, see [`Locale::list`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Conjunction {
+    /// All the items apply, like "Monday and Friday".
+    And,
+    /// Any of the items applies, like "Saturday or Sunday".
+    Or,
+}
+
+/// A part of the description of a recurrence rule, rendered by [`Locale::phrase`].
+///
+/// The phrases of a rule come in the order of the variants below. The English rendering of
+/// each phrase is given as an example.
+///
+/// Positions, like the ones of weekdays or days of the month, are sorted with the positive
+/// ones first, and weekdays are sorted from the `WKST` of the rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phrase<'a> {
+    /// The frequency and interval of the rule, like "every 2 weeks".
+    ///
+    /// This is always the first phrase of a rule, unless it is [`Phrase::EveryWeekday`].
+    Frequency {
+        /// The frequency of the rule.
+        freq: Frequency,
+        /// The interval of the rule.
+        interval: u16,
+    },
+    /// Replaces the frequency of a daily or weekly rule recurring on every day from Monday to
+    /// Friday: "every weekday".
+    EveryWeekday,
+    /// The calendar of a rule with an `RSCALE`, like " (Hebrew calendar)".
+    Calendar(RScale),
+    /// The months, like " in January and March".
+    Months {
+        /// The number of every month and if it is a leap month.
+        months: &'a [(u8, bool)],
+        /// The calendar of the months, when it is not the Gregorian calendar and the months
+        /// have no names, like " in leap month 5".
+        calendar: Option<RScale>,
+    },
+    /// The weeks of the year, like " in week 1 and the last week".
+    WeekNumbers(&'a [i64]),
+    /// The days of the year, like " on the 100th and last day of the year".
+    YearDays(&'a [i64]),
+    /// The weekdays, like " on Monday and Friday" or " on the 2nd Tuesday".
+    Weekdays {
+        /// The weekdays, with the ones recurring every week before the nth ones.
+        weekdays: &'a [NWeekday],
+        /// True if the nth weekdays count within the year, like " on the 20th Monday of the
+        /// year", instead of within the month.
+        of_year: bool,
+    },
+    /// The days of the month, like " on the 1st and last day".
+    MonthDays {
+        /// The days of the month.
+        days: &'a [i64],
+        /// True if the phrase follows [`Phrase::Weekdays`], like " the 13th" after
+        /// " on Friday".
+        after_weekdays: bool,
+    },
+    /// The positions among all the days of a period, like " on the last day".
+    DayPositions(&'a [i64]),
+    /// The positions among the days from Monday to Friday of a period, like
+    /// " on the last weekday".
+    WeekdayPositions(&'a [i64]),
+    /// The positions among some weekdays of a period, like
+    /// " on the 1st and last Saturday or Sunday".
+    NamedDayPositions {
+        /// The positions.
+        positions: &'a [i64],
+        /// The weekdays the positions are among.
+        weekdays: &'a [Weekday],
+    },
+    /// The positions among the other dates of a period, like " (only the last in each period)".
+    SetPositions(&'a [i64]),
+    /// The offset in days from Easter Sunday, like " on Easter" or " 3 days after Easter".
+    Easter(i16),
+    /// What happens to dates which do not exist, like
+    /// " (or earlier if the date does not exist)". Never [`Skip::Omit`].
+    Skip(Skip),
+    /// The times of day, like " at 9:00 and 17:30".
+    Times(&'a [NaiveTime]),
+    /// The hours, minutes and seconds, when there are too many times of day to list them,
+    /// like " at 9 and 17 o'clock and minutes 0 and 30". Any of them can be empty.
+    TimeParts {
+        /// The hours.
+        hours: &'a [u8],
+        /// The minutes.
+        minutes: &'a [u8],
+        /// The seconds.
+        seconds: &'a [u8],
+    },
+    /// The number of recurrences, like ", 10 times".
+    Count(u32),
+    /// The last date of the rule, like " until March 1, 2023".
+    Until(NaiveDateTime),
+    /// The rdates or exdates of a set, with the time of day when it is not the one of
+    /// `DTSTART`, like "on January 2, 2023 and January 10, 2023 at 12:00".
+    ///
+    /// The phrase is an item of the list of rules of the set, so it has no separator.
+    Dates(&'a [(NaiveDate, Option<NaiveTime>)]),
+    /// The list of exrules and exdates of a set, like ", except on January 6, 2023".
+    Except(&'a str),
+}
@@ -0,0 +1,368 @@
+//! Descriptions of recurrence rules, like "every 2 weeks on Monday and Friday", in the
+//! language of a [`Locale`].
+
+mod english;
+mod error;
+mod french;
+mod german;
+mod japanese;
+mod locale;
+mod parse;
+mod spanish;
+
+pub use english::English;
+pub use error::{TextError, TextErrorKind};
+pub use french::French;
+pub use german::German;
+pub use japanese::Japanese;
+pub use locale::{Conjunction, Locale, Phrase};
+pub use spanish::Spanish;
+
+use crate::{Frequency, NWeekday, RRule, RRuleSet, Skip};
+use chrono::{DateTime, NaiveTime, TimeZone, Weekday};
+
+/// The weekdays from Monday to Friday.
+const WEEKDAYS: [Weekday; 5] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+];
+
+/// Maximum number of times of day which are listed one by one, instead of listing the hours,
+/// minutes and seconds separately.
+const MAX_LISTED_TIMES: usize = 12;
+
+impl<S> RRule<S> {
+    /// Returns a description of the rrule in English, like "every 2 weeks on Monday and Friday".
+    ///
+    /// A validated rrule has the parts derived from the start date as well, like the time of
+    /// day, so its description is more precise than the one of the unvalidated rrule.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{Frequency, NWeekday, RRule, Weekday};
+    ///
+    /// let rrule = RRule::new(Frequency::Weekly)
+    ///     .interval(2)
+    ///     .by_weekday(vec![NWeekday::Every(Weekday::Mon), NWeekday::Every(Weekday::Fri)])
+    ///     .count(10);
+    /// assert_eq!(rrule.to_text(), "every 2 weeks on Monday and Friday, 10 times");
+    ///
+    /// let rrule = RRule::new(Frequency::Monthly).by_weekday(vec![NWeekday::Nth(-1, Weekday::Fri)]);
+    /// assert_eq!(rrule.to_text(), "every month on the last Friday");
+    /// ```
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.to_text_in(&English)
+    }
+
+    /// Returns a description of the rrule in the language of `locale`, see [`RRule::to_text`].
+    ///
+    /// The weekdays are listed from the `WKST` of the rrule.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{Frequency, German, Japanese, NWeekday, RRule, Weekday};
+    ///
+    /// let rrule = RRule::new(Frequency::Weekly)
+    ///     .interval(2)
+    ///     .by_weekday(vec![NWeekday::Every(Weekday::Mon), NWeekday::Every(Weekday::Fri)])
+    ///     .count(10);
+    /// assert_eq!(rrule.to_text_in(&German), "alle 2 Wochen am Montag und Freitag, 10 Mal");
+    /// assert_eq!(rrule.to_text_in(&Japanese), "2週間ごと 月曜日と金曜日、10回");
+    /// ```
+    #[must_use]
+    pub fn to_text_in(&self, locale: &dyn Locale) -> String {
+        let weekdays = sorted_weekdays(self);
+        let every_weekday = is_every_weekday(self, &weekdays);
+        let months = sorted_months(self);
+        let week_numbers = sorted_positions(&self.by_week_no);
+        let year_days = sorted_positions(&self.by_year_day);
+        let month_days = self
+            .by_month_day
+            .iter()
+            .chain(&self.by_n_month_day)
+            .copied()
+            .collect::<Vec<_>>();
+        let month_days = sorted_positions(&month_days);
+        let set_positions = sorted_positions(&self.by_set_pos);
+        // The weekdays which the positions select among, like "the last weekday"
+        let positioned_days = every_day_weekdays(&weekdays)
+            .filter(|_| !set_positions.is_empty() && month_days.is_empty());
+        // A validated rrule always has a second, which is left out when it is the default
+        let seconds: &[u8] = if self.by_second == [0] {
+            &[]
+        } else {
+            &self.by_second
+        };
+        let times = times(self, seconds);
+
+        let mut phrases = vec![];
+        if every_weekday {
+            phrases.push(Phrase::EveryWeekday);
+        } else {
+            phrases.push(Phrase::Frequency {
+                freq: self.freq,
+                interval: self.interval,
+            });
+        }
+        if let Some(calendar) = self.calendar() {
+            phrases.push(Phrase::Calendar(calendar));
+        }
+        if !months.is_empty() {
+            phrases.push(Phrase::Months {
+                months: &months,
+                calendar: self.calendar(),
+            });
+        }
+        if !week_numbers.is_empty() {
+            phrases.push(Phrase::WeekNumbers(&week_numbers));
+        }
+        if !year_days.is_empty() {
+            phrases.push(Phrase::YearDays(&year_days));
+        }
+        if every_weekday {
+            // The weekdays are described by the frequency
+        } else if let Some(days) = &positioned_days {
+            phrases.push(match days.len() {
+                0 | 7 => Phrase::DayPositions(&set_positions),
+                _ if is_workweek(days) => Phrase::WeekdayPositions(&set_positions),
+                _ => Phrase::NamedDayPositions {
+                    positions: &set_positions,
+                    weekdays: days,
+                },
+            });
+        } else {
+            if !weekdays.is_empty() {
+                phrases.push(Phrase::Weekdays {
+                    weekdays: &weekdays,
+                    of_year: self.freq == Frequency::Yearly && self.by_month.is_empty(),
+                });
+            }
+            if !month_days.is_empty() {
+                phrases.push(Phrase::MonthDays {
+                    days: &month_days,
+                    after_weekdays: !weekdays.is_empty(),
+                });
+            }
+            if !set_positions.is_empty() {
+                phrases.push(Phrase::SetPositions(&set_positions));
+            }
+        }
+        if let Some(offset) = self.by_easter {
+            phrases.push(Phrase::Easter(offset));
+        }
+        if let Some(skip @ (Skip::Backward | Skip::Forward)) = self.skip {
+            phrases.push(Phrase::Skip(skip));
+        }
+        if let Some(times) = &times {
+            phrases.push(Phrase::Times(times));
+        } else if !self.by_hour.is_empty() || !self.by_minute.is_empty() || !seconds.is_empty() {
+            phrases.push(Phrase::TimeParts {
+                hours: &self.by_hour,
+                minutes: &self.by_minute,
+                seconds,
+            });
+        }
+        if let Some(count) = self.count {
+            phrases.push(Phrase::Count(count));
+        }
+        if let Some(until) = &self.until {
+            phrases.push(Phrase::Until(until.naive_local()));
+        }
+
+        phrases.iter().map(|phrase| locale.phrase(phrase)).collect()
+    }
+}
+
+impl<TZ: TimeZone> RRuleSet<TZ> {
+    /// Returns a description of the set in English, see [`RRule::to_text`].
+    ///
+    /// The rdates and exdates are listed by their date, with the time of day when it is not the
+    /// time of `DTSTART`.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::RRuleSet;
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20230102T090000Z\n\
+    ///     RRULE:FREQ=WEEKLY;BYDAY=MO,FR;UNTIL=20230301T000000Z\n\
+    ///     EXDATE:20230106T090000Z"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     rrule_set.to_text(),
+    ///     "every week on Monday and Friday at 9:00 until March 1, 2023, except on January 6, 2023"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.to_text_in(&English)
+    }
+
+    /// Returns a description of the set in the language of `locale`, see
+    /// [`RRuleSet::to_text`].
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{French, RRuleSet};
+    ///
+    /// let rrule_set: RRuleSet = "DTSTART:20230102T090000Z\n\
+    ///     RRULE:FREQ=WEEKLY;BYDAY=MO,FR;UNTIL=20230301T000000Z\n\
+    ///     EXDATE:20230106T090000Z"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     rrule_set.to_text_in(&French),
+    ///     "chaque semaine le lundi et le vendredi à 9:00 jusqu'au 1er mars 2023, \
+    ///     sauf le 6 janvier 2023"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_text_in(&self, locale: &dyn Locale) -> String {
+        let start_time = self.dt_start.naive_local().time();
+        let dates_text = |dates: &[DateTime<TZ>]| {
+            let mut dates = dates.iter().map(DateTime::naive_local).collect::<Vec<_>>();
+            dates.sort();
+            dates.dedup();
+            let dates = dates
+                .into_iter()
+                .map(|date| {
+                    let time = Some(date.time()).filter(|time| *time != start_time);
+                    (date.date(), time)
+                })
+                .collect::<Vec<_>>();
+            locale.phrase(&Phrase::Dates(&dates))
+        };
+
+        let mut parts = self
+            .rrule
+            .iter()
+            .map(|rrule| rrule.to_text_in(locale))
+            .collect::<Vec<_>>();
+        if !self.rdate.is_empty() {
+            parts.push(dates_text(&self.rdate));
+        }
+        let mut text = locale.list(parts, Conjunction::And);
+
+        let mut exceptions = self
+            .exrule
+            .iter()
+            .map(|rrule| rrule.to_text_in(locale))
+            .collect::<Vec<_>>();
+        if !self.exdate.is_empty() {
+            exceptions.push(dates_text(&self.exdate));
+        }
+        if !exceptions.is_empty() {
+            let exceptions = locale.list(exceptions, Conjunction::And);
+            text.push_str(&locale.phrase(&Phrase::Except(&exceptions)));
+        }
+        text
+    }
+}
+
+/// Returns true if the rrule recurs on every weekday, and nothing else selects its days.
+fn is_every_weekday<S>(rrule: &RRule<S>, weekdays: &[NWeekday]) -> bool {
+    rrule.interval == 1
+        && matches!(rrule.freq, Frequency::Daily | Frequency::Weekly)
+        && rrule.by_set_pos.is_empty()
+        && every_day_weekdays(weekdays).is_some_and(|days| is_workweek(&days))
+}
+
+/// Returns the days of the weekdays, or `None` if some of them are nth weekdays.
+fn every_day_weekdays(weekdays: &[NWeekday]) -> Option<Vec<Weekday>> {
+    weekdays
+        .iter()
+        .map(|weekday| match weekday {
+            NWeekday::Every(weekday) => Some(*weekday),
+            NWeekday::Nth(..) => None,
+        })
+        .collect()
+}
+
+/// Returns true if the distinct `days` are the days from Monday to Friday.
+fn is_workweek(days: &[Weekday]) -> bool {
+    days.len() == WEEKDAYS.len() && WEEKDAYS.iter().all(|day| days.contains(day))
+}
+
+/// Returns the weekdays of the rrule, sorted from its week start, with the nth weekdays after
+/// them.
+fn sorted_weekdays<S>(rrule: &RRule<S>) -> Vec<NWeekday> {
+    let day_of_week = |weekday: &Weekday| {
+        (weekday.num_days_from_monday() + 7 - rrule.week_start.num_days_from_monday()) % 7
+    };
+    let mut weekdays = rrule.by_weekday.clone();
+    weekdays.sort_by_key(|weekday| match weekday {
+        NWeekday::Every(weekday) => (None, day_of_week(weekday)),
+        NWeekday::Nth(n, weekday) => (Some(position_key(*n)), day_of_week(weekday)),
+    });
+    weekdays.dedup();
+    weekdays
+}
+
+/// Returns the months of the rrule and if they are leap months, with the leap months after
+/// the month with the same number.
+fn sorted_months<S>(rrule: &RRule<S>) -> Vec<(u8, bool)> {
+    let mut months = rrule
+        .by_month
+        .iter()
+        .map(|month| (*month, false))
+        .chain(rrule.by_leap_month.iter().map(|month| (*month, true)))
+        .collect::<Vec<_>>();
+    months.sort_unstable();
+    months.dedup();
+    months
+}
+
+/// Returns the key to sort positions, which puts the positive ones before the negative ones,
+/// like "the 1st and last".
+fn position_key(n: impl Into<i64>) -> (bool, i64) {
+    let n = n.into();
+    (n < 0, n)
+}
+
+/// Returns the positions sorted by [`position_key`].
+fn sorted_positions<T: Copy + Into<i64>>(positions: &[T]) -> Vec<i64> {
+    let mut positions = positions.iter().map(|n| (*n).into()).collect::<Vec<_>>();
+    positions.sort_unstable_by_key(|n| position_key(*n));
+    positions.dedup();
+    positions
+}
+
+/// Returns the times of day of the rrule, or `None` if it has no hour or minute, or too many
+/// times of day to list them.
+fn times<S>(rrule: &RRule<S>, seconds: &[u8]) -> Option<Vec<NaiveTime>> {
+    let count = rrule.by_hour.len() * rrule.by_minute.len() * seconds.len().max(1);
+    if count == 0 || count > MAX_LISTED_TIMES {
+        return None;
+    }
+    let seconds = if seconds.is_empty() { &[0] } else { seconds };
+    let mut times = vec![];
+    for hour in &rrule.by_hour {
+        for minute in &rrule.by_minute {
+            for second in seconds {
+                times.extend(NaiveTime::from_hms_opt(
+                    u32::from(*hour),
+                    u32::from(*minute),
+                    u32::from(*second),
+                ));
+            }
+        }
+    }
+    Some(times)
+}
+
+/// Joins the items of a list with `separator`, and `last_separator` before the last one.
+fn join(mut items: Vec<String>, separator: &str, last_separator: &str) -> String {
+    match items.pop() {
+        None => String::new(),
+        Some(last) if items.is_empty() => last,
+        Some(last) => format!("{}{}{}", items.join(separator), last_separator, last),
+    }
+}
@@ -0,0 +1,1260 @@
+//! Parsing of descriptions of recurrence rules in English, like "every other Tuesday until June".
+
+use super::error::{TextError, TextErrorKind};
+use super::WEEKDAYS;
+use crate::core::Unvalidated;
+use crate::{Frequency, NWeekday, RRule, RScale, Skip, Tz};
+use chrono::{Datelike, NaiveDate, TimeZone, Utc, Weekday};
+use std::ops::Range;
+
+impl RRule<Unvalidated> {
+    /// Parses a description of a rule in English, like "every other Tuesday until June" or
+    /// "last weekday of the month".
+    ///
+    /// The descriptions of [`RRule::to_text`] are parsed back into an rrule with the same
+    /// description. Other phrases which are understood include frequencies like "daily" or
+    /// "every 2nd month", weekdays like "every Tuesday and Thursday", times like "at 9am",
+    /// counts like "twice" and dates like "until 2024-06-01".
+    ///
+    /// An `UNTIL` date is inclusive, so the rule ends at the last second of the day in UTC.
+    /// A month without a day, like "until June", ends the rule before the month starts.
+    /// Dates without a year are the next ones from today.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TextError`] with the span of the text which could not be parsed.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// use rrule::{Frequency, NWeekday, RRule, Weekday};
+    ///
+    /// let rrule = RRule::from_text("every other Tuesday, 10 times").unwrap();
+    /// assert_eq!(rrule.get_freq(), Frequency::Weekly);
+    /// assert_eq!(rrule.get_interval(), 2);
+    /// assert_eq!(rrule.get_by_weekday(), &[NWeekday::Every(Weekday::Tue)]);
+    /// assert_eq!(rrule.get_count(), Some(10));
+    ///
+    /// let rrule = RRule::from_text("last weekday of the month").unwrap();
+    /// assert_eq!(rrule.to_text(), "every month on the last weekday");
+    ///
+    /// let error = RRule::from_text("every week on Funday").unwrap_err();
+    /// assert_eq!(error.get_span(), 14..20);
+    /// ```
+    pub fn from_text(text: &str) -> Result<Self, TextError> {
+        parse_text(text, Utc::now().date_naive())
+    }
+}
+
+/// Parses a description of a rule, with dates without a year being the next ones from `today`.
+pub(crate) fn parse_text(text: &str, today: NaiveDate) -> Result<RRule<Unvalidated>, TextError> {
+    let tokens = tokenize(text)?;
+    TextParser {
+        text,
+        tokens,
+        pos: 0,
+        today,
+        rrule: RRule::default(),
+        freq: None,
+        scope_freq: None,
+    }
+    .parse()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// A word in lowercase, like "every" or "o'clock".
+    Word(String),
+    /// A number, like "15".
+    Number(u32),
+    /// An ordinal number, like "2nd".
+    Ordinal(u32),
+    /// A time of day as hour, minute and second, like "9:00" or "9:00:30".
+    Time(u32, u32, Option<u32>),
+    /// A date, like "2024-06-01".
+    Date(NaiveDate),
+    Comma,
+    OpenParen,
+    CloseParen,
+}
+
+/// Splits the text into tokens and their byte ranges.
+fn tokenize(text: &str) -> Result<Vec<(Token, Range<usize>)>, TextError> {
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            ',' | ';' => Token::Comma,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            // A sentence can end with a period
+            '.' if text[start + 1..].trim().is_empty() => break,
+            c if c.is_whitespace() => continue,
+            c if c.is_alphanumeric() => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars
+                    .next_if(|(_, c)| c.is_alphanumeric() || matches!(c, '\'' | '’' | ':' | '-'))
+                {
+                    end = i + c.len_utf8();
+                }
+                let span = start..end;
+                // A time can be followed by "am" or "pm" without a space, like "9am"
+                let word = text[span.clone()].to_lowercase();
+                if let Some(number) = ["am", "pm"]
+                    .iter()
+                    .find_map(|suffix| word.strip_suffix(suffix))
+                    .filter(|number| number.starts_with(|c: char| c.is_ascii_digit()))
+                    .filter(|number| number.is_ascii())
+                {
+                    let split = start + number.len();
+                    let token = word_token(number).ok_or_else(|| unexpected(text, start..split))?;
+                    tokens.push((token, start..split));
+                    tokens.push((Token::Word(word[number.len()..].into()), split..end));
+                    continue;
+                }
+                let token = word_token(&text[span.clone()])
+                    .ok_or_else(|| unexpected(text, span.clone()))?;
+                tokens.push((token, span));
+                continue;
+            }
+            _ => return Err(unexpected(text, start..start + c.len_utf8())),
+        };
+        tokens.push((token, start..start + c.len_utf8()));
+    }
+    Ok(tokens)
+}
+
+/// Returns the token of a word, number, time or date, or `None` if it is none of them.
+fn word_token(word: &str) -> Option<Token> {
+    let word = word.to_lowercase().replace('’', "'");
+    if word.chars().all(|c| c.is_ascii_digit()) {
+        return word.parse().ok().map(Token::Number);
+    }
+    if let Some(number) = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .filter(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+    {
+        return number.parse().ok().map(Token::Ordinal);
+    }
+    if word.starts_with(|c: char| c.is_ascii_digit()) {
+        if let Ok(date) = NaiveDate::parse_from_str(&word, "%Y-%m-%d") {
+            return Some(Token::Date(date));
+        }
+        let parts = word
+            .split(':')
+            .map(|part| part.parse::<u32>().ok().filter(|_| part.len() <= 2))
+            .collect::<Option<Vec<_>>>()?;
+        return match parts[..] {
+            [hour, minute] => Some(Token::Time(hour, minute, None)),
+            [hour, minute, second] => Some(Token::Time(hour, minute, Some(second))),
+            _ => None,
+        };
+    }
+    word.chars()
+        .all(|c| c.is_alphabetic() || matches!(c, '\'' | '-'))
+        .then_some(Token::Word(word))
+}
+
+fn unexpected(text: &str, span: Range<usize>) -> TextError {
+    TextError::new(TextErrorKind::Unexpected(text[span.clone()].into()), span)
+}
+
+/// A day of [`TextParser::parse_days`], before the days are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Day {
+    /// Every week on a weekday, like "Monday".
+    Every(Weekday),
+    /// Every week on the days from Monday to Friday, like "weekdays".
+    Weekdays,
+    /// A position followed by the noun it applies to, like "the 2nd Tuesday".
+    Nth(i64, Noun),
+    /// A position which applies to the next noun, like "the 1st" in "the 1st and last day",
+    /// or to the day of the month when no noun follows, like "the 13th".
+    Position(i64),
+}
+
+/// What a position of a [`Day`] counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Noun {
+    Weekday(Weekday),
+    /// The days from Monday to Friday, like "the last weekday".
+    Weekdays,
+    Day,
+}
+
+/// The period a position counts in, like "of the month".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Period,
+    Month,
+    Year,
+}
+
+struct TextParser<'a> {
+    text: &'a str,
+    tokens: Vec<(Token, Range<usize>)>,
+    /// Index of the next token.
+    pos: usize,
+    today: NaiveDate,
+    rrule: RRule<Unvalidated>,
+    /// The frequency, like "every month".
+    freq: Option<Frequency>,
+    /// The frequency implied by the period of a position, like "of the month", when there is
+    /// no other frequency.
+    scope_freq: Option<Frequency>,
+}
+
+impl TextParser<'_> {
+    fn parse(mut self) -> Result<RRule<Unvalidated>, TextError> {
+        while self.pos < self.tokens.len() {
+            self.parse_part()?;
+        }
+        let Some(freq) = self.freq.or(self.scope_freq) else {
+            return Err(TextError::new(
+                TextErrorKind::MissingFrequency,
+                0..self.text.len(),
+            ));
+        };
+        self.rrule.freq = freq;
+
+        // The values are unique and sorted, like the ones of the `RRULE` parser
+        let rrule = &mut self.rrule;
+        sort_values(&mut rrule.by_set_pos);
+        sort_values(&mut rrule.by_month);
+        sort_values(&mut rrule.by_leap_month);
+        sort_values(&mut rrule.by_month_day);
+        sort_values(&mut rrule.by_year_day);
+        sort_values(&mut rrule.by_week_no);
+        sort_values(&mut rrule.by_hour);
+        sort_values(&mut rrule.by_minute);
+        sort_values(&mut rrule.by_second);
+        Ok(self.rrule)
+    }
+
+    /// Parses the next part of the rule, like "every week" or "until June".
+    fn parse_part(&mut self) -> Result<(), TextError> {
+        let start = self.span().start;
+        let word = self.peek_word().unwrap_or_default();
+        match (self.peek(), word) {
+            (Some(Token::Comma), _) | (_, "and") => self.pos += 1,
+            (_, "every" | "each") => self.parse_every()?,
+            (_, "in" | "during") => {
+                self.pos += 1;
+                self.parse_months_and_weeks()?;
+            }
+            (_, "on") => {
+                self.pos += 1;
+                self.parse_days()?;
+            }
+            (_, "at") => {
+                self.pos += 1;
+                self.parse_times()?;
+            }
+            (_, "until" | "till" | "through") => {
+                self.pos += 1;
+                self.parse_until(start)?;
+            }
+            (_, "for") => {
+                self.pos += 1;
+                self.parse_count(start)?;
+            }
+            (_, "once" | "twice") | (Some(Token::Number(_)), _) => self.parse_count(start)?,
+            (_, "of") => {
+                self.pos += 1;
+                self.parse_scope()?;
+            }
+            (Some(Token::OpenParen), _) => self.parse_parenthesized()?,
+            _ => {
+                if let Some(freq) = frequency_adverb(word) {
+                    self.pos += 1;
+                    self.set_freq(freq, start)?;
+                } else if self.is_day_start() {
+                    self.parse_days()?;
+                } else {
+                    return Err(self.unexpected());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a frequency like "every 2 weeks", "every other Tuesday" or "every weekday".
+    fn parse_every(&mut self) -> Result<(), TextError> {
+        let start = self.span().start;
+        self.pos += 1;
+
+        let interval = match (self.peek(), self.peek_word()) {
+            (Some(Token::Number(n) | Token::Ordinal(n)), _) => {
+                let n = *n;
+                self.pos += 1;
+                Some(n)
+            }
+            (_, Some("other")) => {
+                self.pos += 1;
+                Some(2)
+            }
+            // "every second" is a frequency, "every second week" an interval
+            (_, Some(word)) if self.peek_word_at(1).is_some_and(is_unit) => {
+                let n = ordinal_word(word);
+                if n.is_some() {
+                    self.pos += 1;
+                }
+                n.and_then(|n| u32::try_from(n).ok())
+            }
+            _ => None,
+        };
+        if let Some(interval) = interval {
+            let span = self.tokens[self.pos - 1].1.clone();
+            self.rrule.interval = u16::try_from(interval)
+                .ok()
+                .filter(|interval| *interval > 0)
+                .ok_or_else(|| self.invalid_value(span, "interval"))?;
+        }
+
+        let word = self.peek_word().unwrap_or_default();
+        if let Some(freq) = frequency_unit(word) {
+            self.pos += 1;
+            return self.set_freq(freq, start);
+        }
+        if matches!(word, "weekday" | "weekdays") {
+            self.pos += 1;
+            self.rrule.by_weekday.extend(WEEKDAYS.map(NWeekday::Every));
+            // "every 2 weekdays" can only recur every 2 days
+            let freq = if interval.is_some() {
+                Frequency::Daily
+            } else {
+                Frequency::Weekly
+            };
+            return self.set_freq(freq, start);
+        }
+        if weekday(word).is_some() {
+            self.set_freq(Frequency::Weekly, start)?;
+            return self.parse_days();
+        }
+        Err(self.expected("a unit of time like `week`, or a weekday"))
+    }
+
+    /// Parses the months or weeks of the year, like "January and March" or "week 1".
+    fn parse_months_and_weeks(&mut self) -> Result<(), TextError> {
+        loop {
+            self.eat_word("the");
+            let span = self.span();
+            let word = self.peek_word().unwrap_or_default();
+            if let Some(month) = month(word) {
+                self.pos += 1;
+                self.rrule.by_month.push(month);
+            } else if matches!(word, "month" | "leap") {
+                let leap = self.eat_word("leap");
+                self.expect_word("month")?;
+                let span = self.span();
+                let month = self.expect_number()?;
+                let month = u8::try_from(month)
+                    .ok()
+                    .filter(|month| (1..=12).contains(month))
+                    .ok_or_else(|| self.invalid_value(span, "month"))?;
+                if leap {
+                    self.rrule.by_leap_month.push(month);
+                } else {
+                    self.rrule.by_month.push(month);
+                }
+            } else if self.eat_word("week") {
+                let span = self.span();
+                let week = self.expect_number()?;
+                self.push_week(i64::from(week), span)?;
+            } else if let Some(week) = self.parse_position()? {
+                self.expect_word("week")?;
+                let span = span.start..self.tokens[self.pos - 1].1.end;
+                self.push_week(week, span)?;
+            } else {
+                return Err(self.expected("a month or a week"));
+            }
+
+            if !self.skip_separators(|parser| {
+                parser.is_week_start()
+                    || parser.peek_word().is_some_and(|word| {
+                        month(word).is_some() || matches!(word, "month" | "leap" | "week")
+                    })
+            }) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn push_week(&mut self, week: i64, span: Range<usize>) -> Result<(), TextError> {
+        let week = i8::try_from(week)
+            .ok()
+            .filter(|week| *week != 0 && (-53..=53).contains(week))
+            .ok_or_else(|| self.invalid_value(span, "week"))?;
+        self.rrule.by_week_no.push(week);
+        Ok(())
+    }
+
+    /// Parses days like "Monday and Friday", "the last weekday", "Friday the 13th",
+    /// "the 1st and last Saturday or Sunday" or "the 100th day of the year".
+    fn parse_days(&mut self) -> Result<(), TextError> {
+        let start = self.span().start;
+        let mut days = vec![];
+        let mut any_of = false;
+        loop {
+            self.eat_word("the");
+            let span = self.span();
+            let word = self.peek_word().unwrap_or_default();
+            if word == "easter" {
+                self.pos += 1;
+                self.set_easter(0, span)?;
+            } else if let Some(weekday) = weekday(word) {
+                self.pos += 1;
+                days.push((Day::Every(weekday), span));
+            } else if matches!(word, "weekday" | "weekdays") {
+                self.pos += 1;
+                days.push((Day::Weekdays, span));
+            } else if matches!(word, "weekend" | "weekends") {
+                self.pos += 1;
+                days.push((Day::Every(Weekday::Sat), span.clone()));
+                days.push((Day::Every(Weekday::Sun), span));
+            } else if let Some(position) = self.parse_position()? {
+                let word = self.peek_word().unwrap_or_default();
+                let noun = if let Some(weekday) = weekday(word) {
+                    Some(Noun::Weekday(weekday))
+                } else if matches!(word, "weekday" | "weekdays") {
+                    Some(Noun::Weekdays)
+                } else if matches!(word, "day" | "days") {
+                    Some(Noun::Day)
+                } else {
+                    None
+                };
+                if noun.is_some() {
+                    self.pos += 1;
+                }
+                let span = span.start..self.tokens[self.pos - 1].1.end;
+                match noun {
+                    Some(noun) => days.push((Day::Nth(position, noun), span)),
+                    None => days.push((Day::Position(position), span)),
+                }
+            } else {
+                return Err(self.expected("a day"));
+            }
+
+            // "Friday the 13th" has no separator
+            if self.peek_word() == Some("the") && self.is_day_start() {
+                continue;
+            }
+            let before = self.pos;
+            if self.skip_separators(|parser| parser.is_day_start()) {
+                any_of |= self.tokens[before..self.pos]
+                    .iter()
+                    .any(|(token, _)| *token == Token::Word("or".into()));
+                continue;
+            }
+            break;
+        }
+
+        let scope = if self.eat_word("of") {
+            self.parse_scope()?
+        } else {
+            Scope::Period
+        };
+        self.push_days(&days, any_of, scope, start)
+    }
+
+    /// Adds the parsed days to the rule. Positions without a noun get the noun of the next
+    /// day, so "the 1st and last Saturday or Sunday" are the positions among the weekends.
+    fn push_days(
+        &mut self,
+        days: &[(Day, Range<usize>)],
+        any_of: bool,
+        scope: Scope,
+        start: usize,
+    ) -> Result<(), TextError> {
+        let mut positions: Vec<(i64, Range<usize>)> = vec![];
+        let mut i = 0;
+        while i < days.len() {
+            let (day, span) = days[i].clone();
+            i += 1;
+            match day {
+                Day::Every(weekday) => self.rrule.by_weekday.push(NWeekday::Every(weekday)),
+                Day::Weekdays => {
+                    self.rrule.by_weekday.extend(WEEKDAYS.map(NWeekday::Every));
+                }
+                Day::Position(position) => positions.push((position, span)),
+                Day::Nth(position, Noun::Day) => {
+                    positions.push((position, span));
+                    for (position, span) in std::mem::take(&mut positions) {
+                        self.push_day(position, span, scope)?;
+                    }
+                }
+                Day::Nth(position, Noun::Weekdays) => {
+                    positions.push((position, span));
+                    self.rrule.by_weekday.extend(WEEKDAYS.map(NWeekday::Every));
+                    self.push_set_positions(&std::mem::take(&mut positions), start)?;
+                }
+                Day::Nth(position, Noun::Weekday(weekday)) => {
+                    positions.push((position, span.clone()));
+                    // The other weekdays of "the last Saturday or Sunday"
+                    let mut weekdays = vec![weekday];
+                    while let Some((Day::Every(weekday), _)) = days.get(i).filter(|_| any_of) {
+                        weekdays.push(*weekday);
+                        i += 1;
+                    }
+                    if positions.len() == 1 && weekdays.len() == 1 {
+                        let n = i16::try_from(position)
+                            .ok()
+                            .filter(|n| (-53..=53).contains(n))
+                            .ok_or_else(|| self.invalid_value(span, "weekday position"))?;
+                        self.rrule.by_weekday.push(NWeekday::Nth(n, weekday));
+                        positions.clear();
+                    } else {
+                        self.rrule
+                            .by_weekday
+                            .extend(weekdays.into_iter().map(NWeekday::Every));
+                        self.push_set_positions(&std::mem::take(&mut positions), start)?;
+                    }
+                }
+            }
+        }
+        // Positions without a noun are days, like "Friday the 13th"
+        for (position, span) in positions {
+            self.push_day(position, span, scope)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a day of the month, or of the year when the position counts in the year.
+    fn push_day(&mut self, day: i64, span: Range<usize>, scope: Scope) -> Result<(), TextError> {
+        if scope == Scope::Year {
+            let day = i16::try_from(day)
+                .ok()
+                .filter(|day| *day != 0 && (-366..=366).contains(day))
+                .ok_or_else(|| self.invalid_value(span, "day of the year"))?;
+            self.rrule.by_year_day.push(day);
+        } else {
+            let day = i8::try_from(day)
+                .ok()
+                .filter(|day| *day != 0 && (-31..=31).contains(day))
+                .ok_or_else(|| self.invalid_value(span, "day of the month"))?;
+            self.rrule.by_month_day.push(day);
+        }
+        Ok(())
+    }
+
+    fn push_set_positions(
+        &mut self,
+        positions: &[(i64, Range<usize>)],
+        start: usize,
+    ) -> Result<(), TextError> {
+        if !self.rrule.by_set_pos.is_empty() {
+            let span = start..self.tokens[self.pos - 1].1.end;
+            return Err(TextError::new(
+                TextErrorKind::Duplicate("set position".into()),
+                span,
+            ));
+        }
+        for (position, span) in positions {
+            let position = i32::try_from(*position)
+                .ok()
+                .filter(|position| *position != 0 && (-366..=366).contains(position))
+                .ok_or_else(|| self.invalid_value(span.clone(), "set position"))?;
+            self.rrule.by_set_pos.push(position);
+        }
+        Ok(())
+    }
+
+    /// Parses a position, like "2nd", "second", "last" or "2nd to last", or returns `None`
+    /// if the next token is not a position.
+    fn parse_position(&mut self) -> Result<Option<i64>, TextError> {
+        let position = match (self.peek(), self.peek_word()) {
+            (Some(Token::Ordinal(n)), _) => i64::from(*n),
+            (_, Some("last")) => {
+                self.pos += 1;
+                return Ok(Some(-1));
+            }
+            (_, Some(word)) => match ordinal_word(word) {
+                Some(n) => n,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+        if self.peek_word() == Some("to") && self.peek_word_at(1) == Some("last") {
+            self.pos += 2;
+            return Ok(Some(-position));
+        }
+        Ok(Some(position))
+    }
+
+    /// Parses the period after "of", like "the month" in "the last day of the month".
+    fn parse_scope(&mut self) -> Result<Scope, TextError> {
+        self.eat_word("the");
+        let (scope, freq) = match self.peek_word() {
+            Some("month") => (Scope::Month, Frequency::Monthly),
+            Some("year") => (Scope::Year, Frequency::Yearly),
+            _ => return Err(self.expected("`month` or `year`")),
+        };
+        self.pos += 1;
+        self.scope_freq.get_or_insert(freq);
+        Ok(scope)
+    }
+
+    /// Parses the times of day, like "9:00 and 17:30", "9am" or "9 o'clock and minutes 0 and
+    /// 30".
+    fn parse_times(&mut self) -> Result<(), TextError> {
+        let start = self.span().start;
+        let mut times = vec![];
+        let mut hours = vec![];
+        let mut minutes = vec![];
+        let mut seconds = vec![];
+        loop {
+            let span = self.span();
+            match (self.peek().cloned(), self.peek_word()) {
+                (Some(Token::Time(hour, minute, second)), _) => {
+                    self.pos += 1;
+                    let hour = self.parse_meridiem(hour, span.clone())?;
+                    let time = [hour, minute, second.unwrap_or(0)];
+                    if minute >= 60 || second.is_some_and(|second| second >= 60) {
+                        return Err(self.invalid_value(span, "time"));
+                    }
+                    times.push((time, second.is_some()));
+                }
+                (Some(Token::Number(hour)), _)
+                    if matches!(self.peek_word_at(1), Some("am" | "pm")) =>
+                {
+                    self.pos += 1;
+                    let hour = self.parse_meridiem(hour, span)?;
+                    times.push(([hour, 0, 0], false));
+                }
+                (Some(Token::Number(_)), _) => {
+                    let values = self.parse_numbers(24, "hour")?;
+                    self.expect_word("o'clock")?;
+                    hours.extend(values);
+                }
+                (_, Some("noon" | "midday")) => {
+                    self.pos += 1;
+                    times.push(([12, 0, 0], false));
+                }
+                (_, Some("midnight")) => {
+                    self.pos += 1;
+                    times.push(([0, 0, 0], false));
+                }
+                (_, Some("minute" | "minutes")) => {
+                    self.pos += 1;
+                    minutes.extend(self.parse_numbers(60, "minute")?);
+                }
+                (_, Some("second" | "seconds")) => {
+                    self.pos += 1;
+                    seconds.extend(self.parse_numbers(60, "second")?);
+                }
+                _ => return Err(self.expected("a time")),
+            }
+            if !self.skip_separators(|parser| {
+                matches!(parser.peek(), Some(Token::Time(..)))
+                    || parser.is_number()
+                    || parser.peek_word().is_some_and(|word| {
+                        matches!(
+                            word,
+                            "noon"
+                                | "midday"
+                                | "midnight"
+                                | "minute"
+                                | "minutes"
+                                | "second"
+                                | "seconds"
+                        )
+                    })
+            }) {
+                break;
+            }
+        }
+
+        if !times.is_empty() {
+            let span = start..self.tokens[self.pos - 1].1.end;
+            if !(hours.is_empty() && minutes.is_empty() && seconds.is_empty()) {
+                return Err(TextError::new(
+                    TextErrorKind::UnsupportedTimes(self.text[span.clone()].into()),
+                    span,
+                ));
+            }
+            let with_seconds = times.iter().any(|(_, with_seconds)| *with_seconds);
+            let mut times = times.into_iter().map(|(time, _)| time).collect::<Vec<_>>();
+            times.sort_unstable();
+            times.dedup();
+            for (i, values) in [&mut hours, &mut minutes, &mut seconds]
+                .into_iter()
+                .enumerate()
+            {
+                values.extend(times.iter().map(|time| time[i] as u8));
+                values.sort_unstable();
+                values.dedup();
+            }
+            if !with_seconds {
+                seconds.clear();
+            }
+            // A rule recurs at every combination of its hours, minutes and seconds
+            if times.len() != hours.len() * minutes.len() * seconds.len().max(1) {
+                return Err(TextError::new(
+                    TextErrorKind::UnsupportedTimes(self.text[span.clone()].into()),
+                    span,
+                ));
+            }
+        }
+        self.rrule.by_hour.extend(hours);
+        self.rrule.by_minute.extend(minutes);
+        self.rrule.by_second.extend(seconds);
+        Ok(())
+    }
+
+    /// Applies the "am" or "pm" after an hour, if any, and checks that the hour is valid.
+    fn parse_meridiem(&mut self, hour: u32, span: Range<usize>) -> Result<u32, TextError> {
+        let hour = match self.peek_word() {
+            Some("am") if (1..=12).contains(&hour) => hour % 12,
+            Some("pm") if (1..=12).contains(&hour) => hour % 12 + 12,
+            Some("am" | "pm") => return Err(self.invalid_value(span, "hour")),
+            _ if hour < 24 => return Ok(hour),
+            _ => return Err(self.invalid_value(span, "time")),
+        };
+        self.pos += 1;
+        Ok(hour)
+    }
+
+    /// Parses a list of numbers below `max`, like "0, 15 and 30".
+    fn parse_numbers(&mut self, max: u32, part: &str) -> Result<Vec<u8>, TextError> {
+        let mut values = vec![];
+        loop {
+            let span = self.span();
+            let value = self.expect_number()?;
+            values.push(
+                u8::try_from(value)
+                    .ok()
+                    .filter(|value| u32::from(*value) < max)
+                    .ok_or_else(|| self.invalid_value(span, part))?,
+            );
+            if !self.skip_separators(|parser| parser.is_number()) {
+                return Ok(values);
+            }
+        }
+    }
+
+    /// Parses the date after "until", like "March 1, 2023", "June" or "2023-03-01".
+    fn parse_until(&mut self, start: usize) -> Result<(), TextError> {
+        let date_start = self.span().start;
+        let date = match self.peek().cloned() {
+            Some(Token::Date(date)) => {
+                self.pos += 1;
+                Some(date)
+            }
+            Some(Token::Number(day)) => {
+                // Like "1 March 2023"
+                self.pos += 1;
+                let month = self.expect_month()?;
+                let year = self.parse_year();
+                self.date(year, month, day)
+            }
+            _ => {
+                let month = self.expect_month()?;
+                match self.peek() {
+                    Some(Token::Number(day) | Token::Ordinal(day)) if *day <= 31 => {
+                        let day = *day;
+                        self.pos += 1;
+                        self.eat(&Token::Comma);
+                        let year = self.parse_year();
+                        self.date(year, month, day)
+                    }
+                    _ => {
+                        // The rule ends before the month starts, which is the next one after
+                        // the current month when there is no year
+                        let year = self.parse_year().unwrap_or_else(|| {
+                            if month > self.today.month() {
+                                self.today.year()
+                            } else {
+                                self.today.year() + 1
+                            }
+                        });
+                        NaiveDate::from_ymd_opt(year, month, 1).and_then(|date| date.pred_opt())
+                    }
+                }
+            }
+        };
+        let span = date_start..self.tokens[self.pos - 1].1.end;
+        let date = date.ok_or_else(|| {
+            TextError::new(
+                TextErrorKind::InvalidDate(self.text[span.clone()].into()),
+                span.clone(),
+            )
+        })?;
+        if self.rrule.until.is_some() {
+            return Err(TextError::new(
+                TextErrorKind::Duplicate("until date".into()),
+                start..span.end,
+            ));
+        }
+        let until = date
+            .and_hms_opt(23, 59, 59)
+            .expect("last second of the day is a valid time");
+        self.rrule.until = Some(Tz::UTC.from_utc_datetime(&until));
+        Ok(())
+    }
+
+    /// Returns the date with `year`, or the next one from today when there is no year.
+    fn date(&self, year: Option<i32>, month: u32, day: u32) -> Option<NaiveDate> {
+        match year {
+            Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+            None => NaiveDate::from_ymd_opt(self.today.year(), month, day)
+                .filter(|date| *date >= self.today)
+                .or_else(|| NaiveDate::from_ymd_opt(self.today.year() + 1, month, day)),
+        }
+    }
+
+    /// Parses a year, which is a number above 31 so it is not taken for a day.
+    fn parse_year(&mut self) -> Option<i32> {
+        match self.peek() {
+            Some(Token::Number(year)) if *year > 31 => {
+                let year = i32::try_from(*year).ok();
+                self.pos += 1;
+                year
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses the number of recurrences, like "10 times", "for 3 occurrences" or "twice", or
+    /// the days from Easter, like "3 days after Easter".
+    fn parse_count(&mut self, start: usize) -> Result<(), TextError> {
+        let count = match (self.peek().cloned(), self.peek_word()) {
+            (_, Some("once")) => {
+                self.pos += 1;
+                1
+            }
+            (_, Some("twice")) => {
+                self.pos += 1;
+                2
+            }
+            (Some(Token::Number(n)), _) => {
+                let span = self.span();
+                self.pos += 1;
+                if matches!(self.peek_word(), Some("day" | "days")) {
+                    self.pos += 1;
+                    return self.parse_easter(n, span);
+                }
+                if !self.eat_word("times") && !self.eat_word("time") {
+                    self.expect_word("occurrences")?;
+                }
+                n
+            }
+            _ => return Err(self.expected("a number of times")),
+        };
+        if self.rrule.count.is_some() {
+            let span = start..self.tokens[self.pos - 1].1.end;
+            return Err(TextError::new(
+                TextErrorKind::Duplicate("count".into()),
+                span,
+            ));
+        }
+        self.rrule.count = Some(count);
+        Ok(())
+    }
+
+    /// Parses the rest of "3 days after Easter", after the days.
+    fn parse_easter(&mut self, days: u32, span: Range<usize>) -> Result<(), TextError> {
+        let after = match self.peek_word() {
+            Some("after") => true,
+            Some("before") => false,
+            _ => return Err(self.expected("`after` or `before`")),
+        };
+        self.pos += 1;
+        self.expect_word("easter")?;
+        let days = i16::try_from(days)
+            .ok()
+            .filter(|days| *days <= 366)
+            .ok_or_else(|| self.invalid_value(span.clone(), "number of days"))?;
+        let span = span.start..self.tokens[self.pos - 1].1.end;
+        self.set_easter(if after { days } else { -days }, span)
+    }
+
+    fn set_easter(&mut self, offset: i16, span: Range<usize>) -> Result<(), TextError> {
+        #[cfg(feature = "by-easter")]
+        {
+            let _ = span;
+            self.rrule.by_easter = Some(offset);
+            Ok(())
+        }
+        #[cfg(not(feature = "by-easter"))]
+        {
+            let _ = offset;
+            Err(TextError::new(
+                TextErrorKind::EasterNotEnabled(self.text[span.clone()].into()),
+                span,
+            ))
+        }
+    }
+
+    /// Parses a part between parentheses, like "(Hebrew calendar)",
+    /// "(only the last in each period)" or "(or earlier if the date does not exist)".
+    fn parse_parenthesized(&mut self) -> Result<(), TextError> {
+        let start = self.span().start;
+        self.pos += 1;
+        match self.peek_word() {
+            Some("only") => {
+                self.pos += 1;
+                self.eat_word("the");
+                let mut positions = vec![];
+                loop {
+                    let span = self.span();
+                    let Some(position) = self.parse_position()? else {
+                        return Err(self.expected("a position like `last`"));
+                    };
+                    positions.push((position, span));
+                    if !self.skip_separators(|parser| {
+                        parser
+                            .peek_word()
+                            .is_some_and(|word| word == "last" || ordinal_word(word).is_some())
+                            || matches!(parser.peek(), Some(Token::Ordinal(_)))
+                    }) {
+                        break;
+                    }
+                }
+                for word in ["in", "each", "period"] {
+                    self.expect_word(word)?;
+                }
+                self.push_set_positions(&positions, start)?;
+            }
+            Some("or") => {
+                self.pos += 1;
+                let skip = match self.peek_word() {
+                    Some("earlier") => Skip::Backward,
+                    Some("later") => Skip::Forward,
+                    _ => return Err(self.expected("`earlier` or `later`")),
+                };
+                self.pos += 1;
+                // The reason, like "if the date does not exist"
+                while self.peek().is_some_and(|token| *token != Token::CloseParen) {
+                    self.pos += 1;
+                }
+                self.rrule.skip = Some(skip);
+            }
+            _ => {
+                let mut words = vec![];
+                while let Some(word) = self.peek_word().filter(|word| *word != "calendar") {
+                    words.push(word.to_string());
+                    self.pos += 1;
+                }
+                let rscale = match &words.join(" ")[..] {
+                    "gregorian" => RScale::Gregorian,
+                    "hebrew" | "jewish" => RScale::Hebrew,
+                    "civil islamic" | "islamic civil" | "islamic" => RScale::IslamicCivil,
+                    _ => {
+                        let span = start + 1..self.span().start;
+                        return Err(TextError::new(
+                            TextErrorKind::Expected {
+                                expected: "a calendar like `Hebrew calendar`".into(),
+                                found: self.text[span.clone()].trim().into(),
+                            },
+                            span,
+                        ));
+                    }
+                };
+                self.expect_word("calendar")?;
+                self.rrule.rscale = Some(rscale);
+            }
+        }
+        if !self.eat(&Token::CloseParen) {
+            return Err(self.expected("`)`"));
+        }
+        Ok(())
+    }
+
+    fn set_freq(&mut self, freq: Frequency, start: usize) -> Result<(), TextError> {
+        match self.freq {
+            Some(other) if other != freq => {
+                let span = start..self.tokens[self.pos - 1].1.end;
+                Err(TextError::new(
+                    TextErrorKind::Duplicate("frequency".into()),
+                    span,
+                ))
+            }
+            _ => {
+                self.freq = Some(freq);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns true if the next token starts a day, like "Monday", "the" or "2nd".
+    fn is_day_start(&self) -> bool {
+        let word = match self.peek() {
+            Some(Token::Ordinal(_)) => return true,
+            Some(Token::Word(word)) => &word[..],
+            _ => return false,
+        };
+        let next = self.peek_word_at(1).unwrap_or_default();
+        weekday(word).is_some()
+            || matches!(
+                word,
+                "weekday" | "weekdays" | "weekend" | "weekends" | "last" | "easter"
+            )
+            || (word == "the" && !matches!(next, "week" | "month" | "year"))
+            || (ordinal_word(word).is_some() && !is_unit(next))
+    }
+
+    /// Returns true if the next tokens are the position of a week, like "the last week".
+    fn is_week_start(&mut self) -> bool {
+        let start = self.pos;
+        self.eat_word("the");
+        let found =
+            matches!(self.parse_position(), Ok(Some(_))) && self.peek_word() == Some("week");
+        self.pos = start;
+        found
+    }
+
+    /// Returns true if the next token is a number of a list, and not a number of times or
+    /// days, like "10 times".
+    fn is_number(&self) -> bool {
+        matches!(self.peek(), Some(Token::Number(_)))
+            && !matches!(
+                self.peek_word_at(1),
+                Some("time" | "times" | "occurrences" | "day" | "days")
+            )
+    }
+
+    /// Skips the separators of a list, like ", " or " and ", if the item after them is
+    /// accepted by `is_item`. Returns true if they were skipped.
+    fn skip_separators(&mut self, is_item: impl Fn(&mut Self) -> bool) -> bool {
+        let start = self.pos;
+        while self.eat(&Token::Comma) || self.eat_word("and") || self.eat_word("or") {}
+        if self.pos > start && is_item(self) {
+            return true;
+        }
+        self.pos = start;
+        false
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn peek_word(&self) -> Option<&str> {
+        self.peek_word_at(0)
+    }
+
+    fn peek_word_at(&self, offset: usize) -> Option<&str> {
+        match self.tokens.get(self.pos + offset) {
+            Some((Token::Word(word), _)) => Some(word),
+            _ => None,
+        }
+    }
+
+    /// Returns the span of the next token, or an empty span at the end of the text.
+    fn span(&self) -> Range<usize> {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.text.len()..self.text.len(), |(_, span)| span.clone())
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        self.eat(&Token::Word(word.into()))
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<(), TextError> {
+        if self.eat_word(word) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("`{}`", word)))
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<u32, TextError> {
+        match self.peek() {
+            Some(Token::Number(n)) => {
+                let n = *n;
+                self.pos += 1;
+                Ok(n)
+            }
+            _ => Err(self.expected("a number")),
+        }
+    }
+
+    fn expect_month(&mut self) -> Result<u32, TextError> {
+        match self.peek_word().and_then(month) {
+            Some(month) => {
+                self.pos += 1;
+                Ok(u32::from(month))
+            }
+            None => Err(self.expected("a date")),
+        }
+    }
+
+    /// Returns the error of finding something else than `expected` at the next token.
+    fn expected(&self, expected: &str) -> TextError {
+        let span = self.span();
+        if span.is_empty() {
+            return TextError::new(TextErrorKind::UnexpectedEnd(expected.into()), span);
+        }
+        TextError::new(
+            TextErrorKind::Expected {
+                expected: expected.into(),
+                found: self.text[span.clone()].into(),
+            },
+            span,
+        )
+    }
+
+    fn unexpected(&self) -> TextError {
+        unexpected(self.text, self.span())
+    }
+
+    fn invalid_value(&self, span: Range<usize>, part: &str) -> TextError {
+        TextError::new(
+            TextErrorKind::InvalidValue {
+                value: self.text[span.clone()].into(),
+                part: part.into(),
+            },
+            span,
+        )
+    }
+}
+
+fn sort_values<T: Ord>(values: &mut Vec<T>) {
+    values.sort_unstable();
+    values.dedup();
+}
+
+/// Returns the frequency of a unit of time, like "week" or "weeks".
+fn frequency_unit(word: &str) -> Option<Frequency> {
+    let freq = match word.strip_suffix('s').unwrap_or(word) {
+        "year" => Frequency::Yearly,
+        "month" => Frequency::Monthly,
+        "week" => Frequency::Weekly,
+        "day" => Frequency::Daily,
+        "hour" => Frequency::Hourly,
+        "minute" => Frequency::Minutely,
+        "second" => Frequency::Secondly,
+        _ => return None,
+    };
+    Some(freq)
+}
+
+fn is_unit(word: &str) -> bool {
+    frequency_unit(word).is_some()
+        || matches!(word, "weekday" | "weekdays")
+        || weekday(word).is_some()
+}
+
+/// Returns the frequency of an adverb like "weekly".
+fn frequency_adverb(word: &str) -> Option<Frequency> {
+    let freq = match word {
+        "yearly" | "annually" => Frequency::Yearly,
+        "monthly" => Frequency::Monthly,
+        "weekly" => Frequency::Weekly,
+        "daily" => Frequency::Daily,
+        "hourly" => Frequency::Hourly,
+        "minutely" => Frequency::Minutely,
+        "secondly" => Frequency::Secondly,
+        _ => return None,
+    };
+    Some(freq)
+}
+
+/// Returns the number of an ordinal word, like 2 for "second".
+fn ordinal_word(word: &str) -> Option<i64> {
+    let n = match word {
+        "first" => 1,
+        "second" => 2,
+        "third" => 3,
+        "fourth" => 4,
+        "fifth" => 5,
+        _ => return None,
+    };
+    Some(n)
+}
+
+/// Returns the weekday of a name, like "monday", "mondays" or "mon".
+fn weekday(word: &str) -> Option<Weekday> {
+    let weekday = match word.strip_suffix('s').unwrap_or(word) {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+/// Returns the number of a month name, like 1 for "january" or "jan".
+fn month(word: &str) -> Option<u8> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    MONTHS
+        .iter()
+        .position(|month| word == *month || (word.len() >= 3 && month.starts_with(word)))
+        .and_then(|index| u8::try_from(index + 1).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dates_from_today() {
+        let today = NaiveDate::from_ymd_opt(2023, 6, 15).unwrap();
+        let tests = [
+            ("every day until June", (2024, 5, 31)),
+            ("every day until July", (2023, 6, 30)),
+            ("every day until June 2025", (2025, 5, 31)),
+            ("every day until June 15", (2023, 6, 15)),
+            ("every day until March 1", (2024, 3, 1)),
+            ("every day until 1 March 2023", (2023, 3, 1)),
+            ("every day until March 1st, 2023", (2023, 3, 1)),
+            ("every day until 2023-03-01", (2023, 3, 1)),
+        ];
+        for (text, (year, month, day)) in tests {
+            let until = NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(23, 59, 59)
+                .unwrap();
+            let rrule = parse_text(text, today).unwrap();
+            assert_eq!(
+                rrule.until,
+                Some(Tz::UTC.from_utc_datetime(&until)),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn splits_times_with_meridiem() {
+        let tokens = tokenize("at 9am and 5:30PM").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                (Token::Word("at".into()), 0..2),
+                (Token::Number(9), 3..4),
+                (Token::Word("am".into()), 4..6),
+                (Token::Word("and".into()), 7..10),
+                (Token::Time(5, 30, None), 11..15),
+                (Token::Word("pm".into()), 15..17),
+            ]
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use super::{join, Conjunction, Locale, Phrase};
+use crate::{Frequency, NWeekday, RScale, Skip};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Describes recurrence rules in Spanish, like "cada 2 semanas el lunes y el viernes".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Spanish;
+
+impl Spanish {
+    /// Returns `n` followed by `unit` or `units`, depending on the plural of `n`.
+    fn quantity(&self, n: i64, unit: &str, units: &str) -> String {
+        if self.is_plural(n) {
+            format!("{} {}", n, units)
+        } else {
+            format!("{} {}", n, unit)
+        }
+    }
+
+    /// Returns the ordinals of `positions`, like "1.º y último".
+    fn ordinals(&self, positions: &[i64]) -> String {
+        let ordinals = positions.iter().map(|n| self.ordinal(*n));
+        self.list(ordinals.collect(), Conjunction::And)
+    }
+}
+
+impl Locale for Spanish {
+    fn weekday_name(&self, weekday: Weekday) -> String {
+        let name = match weekday {
+            Weekday::Mon => "lunes",
+            Weekday::Tue => "martes",
+            Weekday::Wed => "miércoles",
+            Weekday::Thu => "jueves",
+            Weekday::Fri => "viernes",
+            Weekday::Sat => "sábado",
+            Weekday::Sun => "domingo",
+        };
+        name.to_string()
+    }
+
+    fn month_name(&self, month: u8) -> String {
+        let name = match month {
+            1 => "enero",
+            2 => "febrero",
+            3 => "marzo",
+            4 => "abril",
+            5 => "mayo",
+            6 => "junio",
+            7 => "julio",
+            8 => "agosto",
+            9 => "septiembre",
+            10 => "octubre",
+            11 => "noviembre",
+            _ => "diciembre",
+        };
+        name.to_string()
+    }
+
+    fn ordinal(&self, n: i64) -> String {
+        match n {
+            -1 => "último".to_string(),
+            -2 => "penúltimo".to_string(),
+            ..=-3 => format!("{}.º último", -n),
+            _ => format!("{}.º", n),
+        }
+    }
+
+    fn list(&self, items: Vec<String>, conjunction: Conjunction) -> String {
+        match conjunction {
+            Conjunction::And => join(items, ", ", " y "),
+            Conjunction::Or => join(items, ", ", " o "),
+        }
+    }
+
+    fn date(&self, date: NaiveDate) -> String {
+        let month = u8::try_from(date.month()).expect("month is between 1 and 12");
+        format!(
+            "{} de {} de {}",
+            date.day(),
+            self.month_name(month),
+            date.year()
+        )
+    }
+
+    fn phrase(&self, phrase: &Phrase<'_>) -> String {
+        match *phrase {
+            Phrase::Frequency { freq, interval } => {
+                let (unit, units) = match freq {
+                    Frequency::Yearly => ("año", "años"),
+                    Frequency::Monthly => ("mes", "meses"),
+                    Frequency::Weekly => ("semana", "semanas"),
+                    Frequency::Daily => ("día", "días"),
+                    Frequency::Hourly => ("hora", "horas"),
+                    Frequency::Minutely => ("minuto", "minutos"),
+                    Frequency::Secondly => ("segundo", "segundos"),
+                };
+                match interval {
+                    1 => format!("cada {}", unit),
+                    interval => format!("cada {}", self.quantity(i64::from(interval), unit, units)),
+                }
+            }
+            Phrase::EveryWeekday => "cada día laborable".to_string(),
+            Phrase::Calendar(calendar) => {
+                let name = match calendar {
+                    RScale::Gregorian => "gregoriano",
+                    RScale::Hebrew => "hebreo",
+                    RScale::IslamicCivil => "islámico civil",
+                };
+                format!(" (calendario {})", name)
+            }
+            Phrase::Months { months, calendar } => {
+                let months = months.iter().map(|(month, leap)| match (calendar, leap) {
+                    (None, _) => self.month_name(*month),
+                    (Some(_), false) => format!("el mes {}", month),
+                    (Some(_), true) => format!("el mes bisiesto {}", month),
+                });
+                format!(" en {}", self.list(months.collect(), Conjunction::And))
+            }
+            Phrase::WeekNumbers(weeks) => {
+                let weeks = weeks.iter().map(|week| match week {
+                    1.. => format!("la semana {}", week),
+                    -1 => "la última semana".to_string(),
+                    -2 => "la penúltima semana".to_string(),
+                    _ => format!("la {}.ª última semana", -week),
+                });
+                format!(" en {}", self.list(weeks.collect(), Conjunction::And))
+            }
+            Phrase::YearDays(days) => format!(" el {} día del año", self.ordinals(days)),
+            Phrase::Weekdays { weekdays, of_year } => {
+                let weekdays = weekdays.iter().map(|weekday| match weekday {
+                    NWeekday::Every(weekday) => format!("el {}", self.weekday_name(*weekday)),
+                    NWeekday::Nth(n, weekday) => {
+                        let text = format!(
+                            "el {} {}",
+                            self.ordinal(i64::from(*n)),
+                            self.weekday_name(*weekday)
+                        );
+                        if of_year {
+                            format!("{} del año", text)
+                        } else {
+                            text
+                        }
+                    }
+                });
+                format!(" {}", self.list(weekdays.collect(), Conjunction::And))
+            }
+            Phrase::MonthDays {
+                days,
+                after_weekdays,
+            } => {
+                let days = days.iter().map(|day| match day {
+                    1.. if after_weekdays => day.to_string(),
+                    1.. => format!("el día {}", day),
+                    _ => format!("el {} día", self.ordinal(*day)),
+                });
+                format!(" {}", self.list(days.collect(), Conjunction::And))
+            }
+            Phrase::DayPositions(positions) => format!(" el {} día", self.ordinals(positions)),
+            Phrase::WeekdayPositions(positions) => {
+                format!(" el {} día laborable", self.ordinals(positions))
+            }
+            Phrase::NamedDayPositions {
+                positions,
+                weekdays,
+            } => {
+                let weekdays = weekdays.iter().map(|day| self.weekday_name(*day));
+                format!(
+                    " el {} {}",
+                    self.ordinals(positions),
+                    self.list(weekdays.collect(), Conjunction::Or)
+                )
+            }
+            Phrase::SetPositions(positions) => {
+                format!(" (solo el {} de cada periodo)", self.ordinals(positions))
+            }
+            Phrase::Easter(offset) => {
+                let days = self.quantity(i64::from(offset).abs(), "día", "días");
+                match offset {
+                    0 => " en Pascua".to_string(),
+                    1.. => format!(" {} después de Pascua", days),
+                    _ => format!(" {} antes de Pascua", days),
+                }
+            }
+            Phrase::Skip(skip) => match skip {
+                Skip::Backward => " (o antes si la fecha no existe)".to_string(),
+                Skip::Forward => " (o después si la fecha no existe)".to_string(),
+                Skip::Omit => String::new(),
+            },
+            Phrase::Times(times) => {
+                let times = times.iter().map(|time| self.time(*time));
+                format!(" a las {}", self.list(times.collect(), Conjunction::And))
+            }
+            Phrase::TimeParts {
+                hours,
+                minutes,
+                seconds,
+            } => {
+                let values = |values: &[u8]| {
+                    let values = values.iter().map(ToString::to_string);
+                    self.list(values.collect(), Conjunction::And)
+                };
+                let mut parts = vec![];
+                if !hours.is_empty() {
+                    parts.push(format!("las {} h", values(hours)));
+                }
+                for (unit, units, numbers) in [
+                    ("el minuto", "los minutos", minutes),
+                    ("el segundo", "los segundos", seconds),
+                ] {
+                    match numbers.len() {
+                        0 => {}
+                        1 => parts.push(format!("{} {}", unit, values(numbers))),
+                        _ => parts.push(format!("{} {}", units, values(numbers))),
+                    }
+                }
+                let parts = self.list(parts, Conjunction::And);
+                // "a el" is contracted to "al"
+                match parts.strip_prefix("el ") {
+                    Some(parts) => format!(" al {}", parts),
+                    None => format!(" a {}", parts),
+                }
+            }
+            Phrase::Count(1) => ", una vez".to_string(),
+            Phrase::Count(count) => format!(", {} veces", count),
+            Phrase::Until(until) => format!(" hasta el {}", self.date(until.date())),
+            Phrase::Dates(dates) => {
+                let dates = dates.iter().map(|(date, time)| match time {
+                    Some(time) => format!("el {} a las {}", self.date(*date), self.time(*time)),
+                    None => format!("el {}", self.date(*date)),
+                });
+                self.list(dates.collect(), Conjunction::And)
+            }
+            Phrase::Except(exceptions) => format!(", excepto {}", exceptions),
+        }
+    }
+}
@@ -2,9 +2,11 @@
 
 use thiserror::Error;
 
-use crate::Frequency;
+use crate::{Frequency, RScale};
 
+/// Errors that can occur while validating an [`crate::RRule`].
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[allow(missing_docs)]
 pub enum ValidationError {
     #[error("BYSETPOS should only be used in conjunction with another BYxxx rule part.")]
     BySetPosWithoutByRule,
@@ -32,6 +34,10 @@ pub enum ValidationError {
     },
     #[error("`{by_rule}` can not be used with the current frequency ({freq}).")]
     InvalidByRuleAndFrequency { by_rule: String, freq: Frequency },
+    #[error("`{by_rule}` can not be used with the current calendar ({rscale}).")]
+    InvalidByRuleAndRScale { by_rule: String, rscale: RScale },
+    #[error("Leap months in `BYMONTH` can only be used with a non-Gregorian `RSCALE`.")]
+    LeapMonthWithoutRScale,
     #[error("`UNTIL` is `{until}`, but `DTSTART` (`{dt_start}`) is later. That should not be happening.")]
     UntilBeforeStart { until: String, dt_start: String },
     #[error(
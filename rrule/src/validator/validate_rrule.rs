@@ -15,18 +15,20 @@ pub(crate) static YEAR_RANGE: RangeInclusive<i32> = -10_000..=10_000;
 type Validator =
     &'static dyn Fn(&RRule<Unvalidated>, &chrono::DateTime<Tz>) -> Result<(), ValidationError>;
 
-const VALIDATION_PIPELINE: [Validator; 11] = [
-    &validate_until,
-    &validate_by_set_pos,
-    &validate_by_month,
-    &validate_by_month_day,
-    &validate_by_year_day,
-    &validate_by_week_number,
-    &validate_by_weekday,
-    &validate_by_hour,
-    &validate_by_minute,
-    &validate_by_second,
-    &validate_by_easter,
+/// The validators, with the rule part each of them validates.
+const VALIDATION_PIPELINE: [(&str, Validator); 12] = [
+    ("UNTIL", &validate_until),
+    ("BYSETPOS", &validate_by_set_pos),
+    ("BYMONTH", &validate_by_month),
+    ("BYMONTHDAY", &validate_by_month_day),
+    ("BYYEARDAY", &validate_by_year_day),
+    ("BYWEEKNO", &validate_by_week_number),
+    ("BYDAY", &validate_by_weekday),
+    ("BYHOUR", &validate_by_hour),
+    ("BYMINUTE", &validate_by_minute),
+    ("BYSECOND", &validate_by_second),
+    ("BYEASTER", &validate_by_easter),
+    ("RSCALE", &validate_rscale),
 ];
 
 /// Check if rules are valid as defined by the RFC and crate limitations.
@@ -41,7 +43,36 @@ pub(crate) fn validate_rrule_forced(
 ) -> Result<(), ValidationError> {
     VALIDATION_PIPELINE
         .into_iter()
-        .try_for_each(|validator| validator(rrule, dt_start))
+        .try_for_each(|(_, validator)| validator(rrule, dt_start))
+}
+
+/// Runs all the checks of [`validate_rrule_forced`], instead of stopping at the first one that
+/// fails, and returns the errors with the rule part they concern.
+pub(crate) fn validate_rrule_all(
+    rrule: &RRule<Unvalidated>,
+    dt_start: &chrono::DateTime<Tz>,
+) -> Vec<(&'static str, ValidationError)> {
+    VALIDATION_PIPELINE
+        .into_iter()
+        .filter_map(|(part, validator)| validator(rrule, dt_start).err().map(|err| (part, err)))
+        .collect()
+}
+
+/// Checks that it is possible to generate the times of the day of the rule.
+pub(crate) fn validate_timeset(rrule: &RRule<Unvalidated>) -> Result<(), ValidationError> {
+    let can_generate = match rrule.freq {
+        Frequency::Hourly => !rrule.by_minute.is_empty() || !rrule.by_second.is_empty(),
+        Frequency::Minutely => !rrule.by_second.is_empty(),
+        Frequency::Secondly => true,
+        _ => {
+            !rrule.by_hour.is_empty() || !rrule.by_minute.is_empty() || !rrule.by_second.is_empty()
+        }
+    };
+    if can_generate {
+        Ok(())
+    } else {
+        Err(ValidationError::UnableToGenerateTimeset)
+    }
 }
 
 // Until:
@@ -58,8 +89,8 @@ fn validate_until(
                     let allowed_timezones = vec![Tz::LOCAL, Tz::UTC];
                     if !allowed_timezones.contains(&until.timezone()) {
                         return Err(ValidationError::DtStartUntilMismatchTimezone {
-                            dt_start_tz: dt_start.timezone().name().into(),
-                            until_tz: until.timezone().name().into(),
+                            dt_start_tz: dt_start.timezone().to_string(),
+                            until_tz: until.timezone().to_string(),
                             expected: allowed_timezones
                                 .into_iter()
                                 .map(|tz| tz.name().into())
@@ -67,11 +98,11 @@ fn validate_until(
                         });
                     }
                 }
-                Tz::Tz(_) => {
+                Tz::Tz(_) | Tz::VTimezone(_) | Tz::Fixed(_) => {
                     if until.timezone() != Tz::UTC {
                         return Err(ValidationError::DtStartUntilMismatchTimezone {
-                            dt_start_tz: dt_start.timezone().name().into(),
-                            until_tz: until.timezone().name().into(),
+                            dt_start_tz: dt_start.timezone().to_string(),
+                            until_tz: until.timezone().to_string(),
                             expected: vec!["UTC".into()],
                         });
                     }
@@ -121,6 +152,7 @@ fn validate_by_set_pos(
         && rrule.by_second.is_empty()
         && rrule.by_month_day.is_empty()
         && rrule.by_month.is_empty()
+        && rrule.by_leap_month.is_empty()
         && rrule.by_year_day.is_empty()
         && rrule.by_week_no.is_empty()
         && rrule.by_weekday.is_empty()
@@ -136,7 +168,8 @@ fn validate_by_month(
     rrule: &RRule<Unvalidated>,
     _dt_start: &chrono::DateTime<Tz>,
 ) -> Result<(), ValidationError> {
-    validate_range_for_vec(&MONTH_RANGE, &rrule.by_month, "BYMONTH")
+    validate_range_for_vec(&MONTH_RANGE, &rrule.by_month, "BYMONTH")?;
+    validate_range_for_vec(&MONTH_RANGE, &rrule.by_leap_month, "BYMONTH")
 }
 
 // By_month_day:
@@ -313,6 +346,34 @@ fn validate_by_easter(
     Ok(())
 }
 
+// Rscale:
+// - Leap months only exist in non-Gregorian calendars.
+// - `BYWEEKNO` and `BYEASTER` are only defined for the Gregorian calendar.
+fn validate_rscale(
+    rrule: &RRule<Unvalidated>,
+    _dt_start: &chrono::DateTime<Tz>,
+) -> Result<(), ValidationError> {
+    let Some(rscale) = rrule.calendar() else {
+        if !rrule.by_leap_month.is_empty() {
+            return Err(ValidationError::LeapMonthWithoutRScale);
+        }
+        return Ok(());
+    };
+    if !rrule.by_week_no.is_empty() {
+        return Err(ValidationError::InvalidByRuleAndRScale {
+            by_rule: "BYWEEKNO".into(),
+            rscale,
+        });
+    }
+    if rrule.by_easter.is_some() {
+        return Err(ValidationError::InvalidByRuleAndRScale {
+            by_rule: "BYEASTER".into(),
+            rscale,
+        });
+    }
+    Ok(())
+}
+
 fn validate_range_for_vec_error<'a, T: PartialOrd>(
     range: &RangeInclusive<T>,
     list: &'a [T],
@@ -361,7 +422,7 @@ fn validate_not_equal_for_vec<T: PartialEq<T> + ToString>(
 
 #[cfg(test)]
 mod tests {
-    use chrono::TimeZone;
+    use chrono::{FixedOffset, TimeZone};
 
     use crate::core::Tz;
 
@@ -389,6 +450,43 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn collects_errors_of_all_rule_parts() {
+        let rrule = RRule {
+            freq: Frequency::Weekly,
+            by_month_day: vec![1],
+            by_hour: vec![24],
+            ..Default::default()
+        };
+        let dt_start = UTC.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+        let errors = validate_rrule_all(&rrule, &dt_start);
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    "BYMONTHDAY",
+                    ValidationError::InvalidByRuleAndFrequency {
+                        by_rule: "BYMONTHDAY".into(),
+                        freq: Frequency::Weekly,
+                    }
+                ),
+                (
+                    "BYHOUR",
+                    ValidationError::InvalidFieldValueRange {
+                        field: "BYHOUR".into(),
+                        value: "24".into(),
+                        start_idx: "0".into(),
+                        end_idx: "23".into(),
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            validate_rrule_forced(&rrule, &dt_start),
+            Err(errors[0].1.clone())
+        );
+    }
+
     #[test]
     fn rejects_by_rule_field_with_invalid_value() {
         let tests = [
@@ -600,7 +698,13 @@ mod tests {
             )
         }
 
-        let tests = [t(Tz::LOCAL, Tz::LOCAL), t(Tz::LOCAL, UTC), t(UTC, UTC)];
+        let fixed = Tz::Fixed(FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap());
+        let tests = [
+            t(Tz::LOCAL, Tz::LOCAL),
+            t(Tz::LOCAL, UTC),
+            t(UTC, UTC),
+            t(fixed, UTC),
+        ];
 
         for (start_date, until) in tests {
             let rrule = RRule {
@@ -625,6 +729,7 @@ mod tests {
             t(Tz::UTC, Tz::LOCAL),
             t(Tz::Europe__Berlin, Tz::LOCAL),
             t(Tz::LOCAL, Tz::Europe__Berlin),
+            t(Tz::Fixed(FixedOffset::east_opt(3600).unwrap()), Tz::LOCAL),
         ];
 
         for (start_date, until) in tests {